@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    record::SeqRecord,
+    seq::{Alphabet, Error, Kind, Seq},
+};
+
+// ===== ABIF Directory ============================================================================
+
+// NOTE: ABIF (`.ab1`) files start with a 6-byte header and a root entry pointing at a directory of
+// 28-byte entries, all big-endian. Each entry is a tag (a 4-character name and a number) and a block
+// of data, which is stored inside the entry's offset field when it fits in 4 bytes
+struct Entry<'a> {
+    name: [u8; 4],
+    number: i32,
+    data: &'a [u8],
+}
+
+fn be_u32(bytes: &[u8], pos: usize) -> Option<usize> {
+    let b = bytes.get(pos..pos + 4)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize)
+}
+
+fn entry(bytes: &[u8], pos: usize) -> Result<Entry<'_>, Error> {
+    let truncated = || Error::Abif(format!("truncated directory entry at byte {pos}"));
+    let raw = bytes.get(pos..pos + 28).ok_or_else(truncated)?;
+    let size = be_u32(raw, 16).ok_or_else(truncated)?;
+    let data = if size <= 4 {
+        &raw[20..20 + size]
+    } else {
+        let offset = be_u32(raw, 20).ok_or_else(truncated)?;
+        bytes
+            .get(offset..offset + size)
+            .ok_or_else(|| Error::Abif(format!("truncated data for entry at byte {pos}")))?
+    };
+    Ok(Entry {
+        name: [raw[0], raw[1], raw[2], raw[3]],
+        number: i32::from_be_bytes([raw[4], raw[5], raw[6], raw[7]]),
+        data,
+    })
+}
+
+fn directory(bytes: &[u8]) -> Result<Vec<Entry<'_>>, Error> {
+    if !bytes.starts_with(b"ABIF") {
+        return Err(Error::Abif("missing the ABIF file header".to_string()));
+    }
+    let truncated = || Error::Abif("truncated root entry".to_string());
+    let count = be_u32(bytes, 18).ok_or_else(truncated)?;
+    let offset = be_u32(bytes, 26).ok_or_else(truncated)?;
+    (0..count).map(|i| entry(bytes, offset + i * 28)).collect()
+}
+
+fn shorts(data: &[u8]) -> Vec<i16> {
+    data.chunks_exact(2)
+        .map(|c| i16::from_be_bytes([c[0], c[1]]))
+        .collect()
+}
+
+// ===== Chromatograms =============================================================================
+
+// NOTE: A Sanger trace: the called bases and their qualities, the scan where each base's peak was
+// called, and the four fluorescence traces (stored in ACGT order, whatever order the dyes were in)
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Chromatogram {
+    record: SeqRecord,
+    peaks: Vec<usize>,
+    traces: [Vec<i16>; 4],
+}
+
+impl Chromatogram {
+    // NOTE: The basecaller's calls (PBAS 2, PCON 2, and PLOC 2) are used over any manual edits (the
+    // matching tags numbered 1), which are only a fallback. The record's ID is the sample name, and
+    // anything in the file beyond the calls and analysed traces is skipped
+    pub fn from_ab1(bytes: &[u8]) -> Result<Self, Error> {
+        let entries = directory(bytes)?;
+        let find = |name: &[u8; 4], numbers: &[i32]| {
+            numbers.iter().find_map(|&n| {
+                entries
+                    .iter()
+                    .find(|e| &e.name == name && e.number == n)
+                    .map(|e| e.data)
+            })
+        };
+
+        let bases = find(b"PBAS", &[2, 1])
+            .ok_or_else(|| Error::Abif("missing the called bases".to_string()))?;
+        let seq = Seq::new_with_kind(bases, [Kind::Dna], Alphabet::Iupac)?;
+        let id = find(b"SMPL", &[1])
+            .and_then(|s| s.split_first())
+            .map(|(&len, name)| String::from_utf8_lossy(&name[..name.len().min(len as usize)]))
+            .unwrap_or_default();
+        let record = match find(b"PCON", &[2, 1]) {
+            Some(qual) => SeqRecord::with_qual(id, seq, qual.to_vec())?,
+            None => SeqRecord::new(id, seq),
+        };
+
+        let peaks = find(b"PLOC", &[2, 1])
+            .map(|p| shorts(p).into_iter().map(|p| p as u16 as usize).collect())
+            .unwrap_or_default();
+        let order = find(b"FWO_", &[1]).unwrap_or(b"GATC");
+        let mut traces: [Vec<i16>; 4] = Default::default();
+        for (number, &base) in (9..).zip(order.iter().take(4)) {
+            let Some(i) = b"ACGT".iter().position(|&b| b == base.to_ascii_uppercase()) else {
+                return Err(Error::Abif(format!("unknown dye base {}", base as char)));
+            };
+            traces[i] = find(b"DATA", &[number]).map(shorts).unwrap_or_default();
+        }
+
+        Ok(Self {
+            record,
+            peaks,
+            traces,
+        })
+    }
+
+    // ===== Getters ===============================================================================
+
+    pub fn record(&self) -> &SeqRecord {
+        &self.record
+    }
+
+    pub fn into_record(self) -> SeqRecord {
+        self.record
+    }
+
+    pub fn seq(&self) -> &Seq {
+        self.record.seq()
+    }
+
+    pub fn peaks(&self) -> &[usize] {
+        &self.peaks
+    }
+
+    pub fn traces(&self) -> &[Vec<i16>; 4] {
+        &self.traces
+    }
+
+    pub fn trace(&self, base: u8) -> Option<&[i16]> {
+        let i = b"ACGT"
+            .iter()
+            .position(|&b| b == base.to_ascii_uppercase())?;
+        Some(&self.traces[i])
+    }
+}
+
+impl AsRef<Seq> for Chromatogram {
+    fn as_ref(&self) -> &Seq {
+        self.seq()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn short_data(values: &[i16]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    // NOTE: Builds an ABIF file with the data blocks first, followed by the directory
+    fn abif(entries: &[(&[u8; 4], i32, Vec<u8>)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut directory = Vec::new();
+        for (name, number, block) in entries {
+            directory.extend_from_slice(*name);
+            directory.extend_from_slice(&number.to_be_bytes());
+            directory.extend_from_slice(&[0; 4]);
+            directory.extend_from_slice(&(block.len() as u32).to_be_bytes());
+            directory.extend_from_slice(&(block.len() as u32).to_be_bytes());
+            if block.len() <= 4 {
+                let mut inline = block.clone();
+                inline.resize(4, 0);
+                directory.extend_from_slice(&inline);
+            } else {
+                directory.extend_from_slice(&(128 + data.len() as u32).to_be_bytes());
+                data.extend_from_slice(block);
+            }
+            directory.extend_from_slice(&[0; 4]);
+        }
+
+        let mut bytes = b"ABIF\x00\x65tdir\x00\x00\x00\x01\x03\xff\x00\x1c".to_vec();
+        bytes.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&(directory.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&(128 + data.len() as u32).to_be_bytes());
+        bytes.resize(128, 0);
+        bytes.extend(data);
+        bytes.extend(directory);
+        bytes
+    }
+
+    fn sanger_trace() -> Vec<u8> {
+        abif(&[
+            (b"SMPL", 1, b"\x07pTest-F".to_vec()),
+            (b"PBAS", 1, b"ACGTA".to_vec()),
+            (b"PBAS", 2, b"ACGTN".to_vec()),
+            (b"PCON", 2, vec![40, 30, 20, 10, 2]),
+            (b"PLOC", 2, short_data(&[5, 15, 25, 35, 45])),
+            (b"FWO_", 1, b"GATC".to_vec()),
+            (b"DATA", 9, short_data(&[0, 0, 900, 10])),
+            (b"DATA", 10, short_data(&[800, 5, 0, 0])),
+            (b"DATA", 11, short_data(&[0, 0, 10, 700])),
+            (b"DATA", 12, short_data(&[3, 600, 0, 0])),
+        ])
+    }
+
+    #[test]
+    fn read_ab1() -> Result<(), Error> {
+        let trace = Chromatogram::from_ab1(&sanger_trace())?;
+        let record = trace.record();
+        assert_eq!(record.id(), "pTest-F");
+        assert_eq!(record.seq(), &Seq::dna_iupac("ACGTN")?);
+        assert_eq!(record.qual(), Some(&[40, 30, 20, 10, 2][..]));
+        assert_eq!(trace.peaks(), [5, 15, 25, 35, 45]);
+        assert_eq!(trace.trace(b'G'), Some(&[0, 0, 900, 10][..]));
+        assert_eq!(trace.trace(b'a'), Some(&[800, 5, 0, 0][..]));
+        assert_eq!(trace.traces()[1], [3, 600, 0, 0]);
+        assert_eq!(trace.trace(b'N'), None);
+
+        let fastq = bio::io::fastq::Record::try_from(trace.into_record())?;
+        assert_eq!(fastq.qual(), b"I?5+#");
+        Ok(())
+    }
+
+    #[test]
+    fn read_edited_ab1() -> Result<(), Error> {
+        let bytes = abif(&[(b"PBAS", 1, b"ACG".to_vec()), (b"PCON", 1, vec![3, 2, 1])]);
+        let trace = Chromatogram::from_ab1(&bytes)?;
+        assert_eq!(trace.record().id(), "");
+        assert_eq!(trace.seq(), &Seq::dna("ACG")?);
+        assert_eq!(trace.record().qual(), Some(&[3, 2, 1][..]));
+        assert!(trace.peaks().is_empty());
+        assert!(trace.traces().iter().all(Vec::is_empty));
+        Ok(())
+    }
+
+    #[test]
+    fn read_invalid_ab1() {
+        assert_eq!(
+            Chromatogram::from_ab1(b"SnapGene"),
+            Err(Error::Abif("missing the ABIF file header".to_string()))
+        );
+        assert_eq!(
+            Chromatogram::from_ab1(&abif(&[(b"PCON", 2, vec![40, 30])])),
+            Err(Error::Abif("missing the called bases".to_string()))
+        );
+        assert_eq!(
+            Chromatogram::from_ab1(&abif(&[
+                (b"PBAS", 2, b"ACG".to_vec()),
+                (b"PCON", 2, vec![40, 30])
+            ])),
+            Err(Error::QualityLength(3, 2))
+        );
+        let mut truncated = sanger_trace();
+        truncated.truncate(200);
+        assert!(matches!(
+            Chromatogram::from_ab1(&truncated),
+            Err(Error::Abif(_))
+        ));
+    }
+}
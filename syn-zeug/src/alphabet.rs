@@ -0,0 +1,166 @@
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+
+use crate::{
+    data::ALPHABET_MAP,
+    seq::{Alphabet, Error, Kind},
+};
+
+// ===== Custom Alphabet Registry ==================================================================
+
+#[derive(Copy, Clone, Debug)]
+struct CustomAlphabet {
+    name: &'static str,
+    symbols: &'static [u8],
+    alphabet: &'static bio::alphabets::Alphabet,
+}
+
+// NOTE: Registered alphabets live for the rest of the program (they're leaked, so the rest of the
+// crate can hand out `'static` references to them), which is fine for the handful of alphabets a
+// program would ever register
+static CUSTOM_ALPHABETS: Lazy<RwLock<Vec<CustomAlphabet>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+// NOTE: Like the built-in alphabets, custom alphabets accept both cases of every letter (so soft
+// masking works), and their symbols are stored uppercase. They only have a single tier, so
+// sequences using them always have the `Base` alphabet. Registering the same name again with the
+// same symbols returns the original kind
+pub fn register_alphabet(
+    name: impl Into<String>,
+    symbols: impl AsRef<[u8]>,
+) -> Result<Kind, Error> {
+    let name = name.into();
+    let mut symbols = symbols.as_ref().to_ascii_uppercase();
+    symbols.sort_unstable();
+    symbols.dedup();
+    if symbols.is_empty() || !symbols.iter().all(u8::is_ascii_graphic) {
+        return Err(Error::InvalidAlphabet(name));
+    }
+
+    let mut registry = CUSTOM_ALPHABETS
+        .write()
+        .expect("The alphabet registry should never be poisoned");
+    if let Some(id) = registry.iter().position(|a| a.name == name) {
+        return if registry[id].symbols == symbols {
+            Ok(Kind::Custom(id as u32))
+        } else {
+            Err(Error::DuplicateAlphabet(name))
+        };
+    }
+
+    let alphabet = bio::alphabets::Alphabet::new(&symbols)
+        .union(&bio::alphabets::Alphabet::new(symbols.to_ascii_lowercase()));
+    registry.push(CustomAlphabet {
+        name: Box::leak(name.into_boxed_str()),
+        symbols: Box::leak(symbols.into_boxed_slice()),
+        alphabet: Box::leak(Box::new(alphabet)),
+    });
+    Ok(Kind::Custom(registry.len() as u32 - 1))
+}
+
+fn custom_alphabet(id: u32) -> Option<CustomAlphabet> {
+    CUSTOM_ALPHABETS
+        .read()
+        .expect("The alphabet registry should never be poisoned")
+        .get(id as usize)
+        .copied()
+}
+
+pub fn custom_name(id: u32) -> Option<&'static str> {
+    custom_alphabet(id).map(|a| a.name)
+}
+
+pub fn custom_symbols(id: u32) -> Option<&'static [u8]> {
+    custom_alphabet(id).map(|a| a.symbols)
+}
+
+// NOTE: The alphabet backing any (kind, alphabet) pair, built-in or custom
+pub(crate) fn lookup(kind: Kind, alphabet: Alphabet) -> Option<&'static bio::alphabets::Alphabet> {
+    match kind {
+        Kind::Custom(id) if alphabet == Alphabet::Base => custom_alphabet(id).map(|a| a.alphabet),
+        Kind::Custom(_) => None,
+        _ => ALPHABET_MAP.get(&(kind, alphabet)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Seq;
+    use std::ops::Range;
+
+    #[test]
+    fn register_custom_alphabets() -> Result<(), Error> {
+        let kind = register_alphabet("Methylated DNA", "ACGTM")?;
+        let Kind::Custom(id) = kind else {
+            panic!("A custom kind should have been returned");
+        };
+        assert_eq!(custom_name(id), Some("Methylated DNA"));
+        assert_eq!(custom_symbols(id), Some(&b"ACGMT"[..]));
+        assert_eq!(kind.to_string(), "Methylated DNA");
+        assert_eq!(register_alphabet("Methylated DNA", "mtgca")?, kind);
+        assert_eq!(custom_name(u32::MAX), None);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_custom_alphabets() {
+        assert_eq!(
+            register_alphabet("Empty", ""),
+            Err(Error::InvalidAlphabet("Empty".to_string()))
+        );
+        assert_eq!(
+            register_alphabet("Spaced", "AC GT"),
+            Err(Error::InvalidAlphabet("Spaced".to_string()))
+        );
+        assert!(register_alphabet("Taken", "AB").is_ok());
+        assert_eq!(
+            register_alphabet("Taken", "ABC"),
+            Err(Error::DuplicateAlphabet("Taken".to_string()))
+        );
+    }
+
+    #[test]
+    fn custom_alphabet_seqs() -> Result<(), Error> {
+        let kind = register_alphabet("Hachimoji", "ACGTPZSB")?;
+        let seq = Seq::new_with_kind("ACGTPZSBAP", [kind], Alphabet::Base)?;
+        assert_eq!(seq.kind(), kind);
+        assert_eq!(seq.alphabet(), Alphabet::Base);
+        assert_eq!(seq.count_elements()[b'P'], 2);
+        assert_eq!(
+            Seq::new_with_kind("ACGTX", [kind], Alphabet::Iupac),
+            Err(Error::InvalidSeq(vec![(kind, Alphabet::Base)]))
+        );
+        assert_eq!(
+            Seq::new_with_kind("acgtpz", [kind], Alphabet::Base)?.kind(),
+            kind
+        );
+
+        // NOTE: Built-in kinds are still preferred when they fit
+        let seq = Seq::new_with_kind("ACGT", [Kind::Dna, kind], Alphabet::Iupac)?;
+        assert_eq!(seq.kind(), Kind::Dna);
+        let seq = Seq::new_with_kind("ACGTP", [Kind::Dna, kind], Alphabet::Iupac)?;
+        assert_eq!(seq.kind(), kind);
+        Ok(())
+    }
+
+    #[test]
+    fn custom_alphabet_tools() -> Result<(), Error> {
+        let kind = register_alphabet("Modified RNA", "ACGUI")?;
+        let seq = Seq::new_with_kind("ACGUIA", [kind], Alphabet::Base)?;
+        assert_eq!(seq.gc_content(), Err(Error::GcContent(kind)));
+        assert_eq!(
+            seq.reverse_complement(),
+            Err(Error::ReverseComplement(kind))
+        );
+        assert_eq!(seq.hard_mask(Some(0..2)), seq);
+        let masked = seq.soft_mask(Some(0..2));
+        assert_eq!(masked.to_string(), "acGUIA");
+        assert_eq!(masked.masked_regions(), [Range { start: 0, end: 2 }]);
+        assert_eq!(seq.ambiguous_content().total(), 0);
+        assert_eq!(seq.one_hot().len(), 30);
+
+        let json = serde_json::to_string(&seq).unwrap();
+        assert_eq!(serde_json::from_str::<Seq>(&json).unwrap(), seq);
+        Ok(())
+    }
+}
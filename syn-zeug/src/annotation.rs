@@ -0,0 +1,276 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    record::SeqRecord,
+    region::Region,
+    seq::{Error, Seq},
+    types::{Strand, Topology},
+};
+
+// ===== Features ==================================================================================
+
+// NOTE: Most features are a single region, but joined features (like spliced genes, or features
+// spanning the origin of a circular sequence) have several parts, stored in the order they are read.
+// Qualifiers keep their order (and duplicates), and flags like `/pseudo` have empty values
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Feature {
+    kind: String,
+    parts: Vec<Region>,
+    qualifiers: Vec<(String, String)>,
+}
+
+impl Feature {
+    // ===== Constructors ==========================================================================
+
+    pub fn new(kind: impl Into<String>, region: Region) -> Self {
+        Self {
+            kind: kind.into(),
+            parts: vec![region],
+            qualifiers: Vec::new(),
+        }
+    }
+
+    pub fn from_parts(
+        kind: impl Into<String>,
+        parts: impl IntoIterator<Item = Region>,
+    ) -> Result<Self, Error> {
+        let kind = kind.into();
+        let parts: Vec<_> = parts.into_iter().collect();
+        if parts.is_empty() {
+            return Err(Error::EmptyFeature(kind));
+        }
+        Ok(Self {
+            kind,
+            parts,
+            qualifiers: Vec::new(),
+        })
+    }
+
+    pub fn with_qualifier(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.qualifiers.push((key.into(), value.into()));
+        self
+    }
+
+    // ===== Getters ===============================================================================
+
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn parts(&self) -> &[Region] {
+        &self.parts
+    }
+
+    pub fn qualifiers(&self) -> &[(String, String)] {
+        &self.qualifiers
+    }
+
+    pub fn qualifier(&self, key: &str) -> Option<&str> {
+        self.qualifiers
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    // NOTE: Benchling and SnapGene store display names in `/label`, but NCBI records only have the
+    // usual descriptive qualifiers, so those are tried in turn
+    pub fn label(&self) -> Option<&str> {
+        ["label", "gene", "product", "locus_tag", "note"]
+            .into_iter()
+            .find_map(|key| self.qualifier(key))
+    }
+
+    pub fn strand(&self) -> Strand {
+        self.parts[0].strand()
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.iter().map(Region::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // ===== Tools =================================================================================
+
+    pub fn extract(&self, seq: &Seq) -> Result<Seq, Error> {
+        let mut bytes = Vec::with_capacity(self.len());
+        for part in &self.parts {
+            bytes.extend_from_slice(seq.extract(part)?.as_bytes());
+        }
+        Seq::from_vec_with_kind(bytes, [seq.kind()], seq.alphabet())
+    }
+}
+
+// ===== Annotated Records =========================================================================
+
+// NOTE: The record's ID and description double as a GenBank record's LOCUS name and DEFINITION.
+// Any other header fields (like ACCESSION or SOURCE) are kept, in order, as metadata
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct AnnotatedRecord {
+    record: SeqRecord,
+    topology: Topology,
+    division: Option<String>,
+    date: Option<String>,
+    metadata: Vec<(String, String)>,
+    features: Vec<Feature>,
+}
+
+impl AnnotatedRecord {
+    // ===== Constructors ==========================================================================
+
+    pub fn new(record: SeqRecord, topology: Topology) -> Self {
+        Self {
+            record,
+            topology,
+            division: None,
+            date: None,
+            metadata: Vec::new(),
+            features: Vec::new(),
+        }
+    }
+
+    pub fn with_division(self, division: impl Into<String>) -> Self {
+        Self {
+            division: Some(division.into()),
+            ..self
+        }
+    }
+
+    pub fn with_date(self, date: impl Into<String>) -> Self {
+        Self {
+            date: Some(date.into()),
+            ..self
+        }
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_feature(mut self, feature: Feature) -> Result<Self, Error> {
+        if let Some(part) = feature.parts.iter().find(|p| p.end() > self.len()) {
+            return Err(Error::RegionBounds(*part, self.len()));
+        }
+        self.features.push(feature);
+        Ok(self)
+    }
+
+    // ===== Getters ===============================================================================
+
+    pub fn record(&self) -> &SeqRecord {
+        &self.record
+    }
+
+    pub fn id(&self) -> &str {
+        self.record.id()
+    }
+
+    pub fn desc(&self) -> Option<&str> {
+        self.record.desc()
+    }
+
+    pub fn seq(&self) -> &Seq {
+        self.record.seq()
+    }
+
+    pub fn len(&self) -> usize {
+        self.record.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.record.is_empty()
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn division(&self) -> Option<&str> {
+        self.division.as_deref()
+    }
+
+    pub fn date(&self) -> Option<&str> {
+        self.date.as_deref()
+    }
+
+    pub fn metadata(&self) -> &[(String, String)] {
+        &self.metadata
+    }
+
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+}
+
+impl AsRef<Seq> for AnnotatedRecord {
+    fn as_ref(&self) -> &Seq {
+        self.seq()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature_qualifiers() -> Result<(), Error> {
+        let feature = Feature::new("CDS", Region::new(0..9, Strand::Forward)?)
+            .with_qualifier("gene", "lacZ")
+            .with_qualifier("note", "first")
+            .with_qualifier("note", "second")
+            .with_qualifier("pseudo", "");
+        assert_eq!(feature.kind(), "CDS");
+        assert_eq!(feature.qualifier("note"), Some("first"));
+        assert_eq!(feature.qualifier("pseudo"), Some(""));
+        assert_eq!(feature.qualifier("product"), None);
+        assert_eq!(feature.label(), Some("lacZ"));
+        let labelled = feature.with_qualifier("label", "lacZα");
+        assert_eq!(labelled.label(), Some("lacZα"));
+        Ok(())
+    }
+
+    #[test]
+    fn joined_features() -> Result<(), Error> {
+        let seq = Seq::dna("AACCGGTTAC")?;
+        let feature = Feature::from_parts(
+            "CDS",
+            [
+                Region::new(6..10, Strand::Reverse)?,
+                Region::new(0..2, Strand::Reverse)?,
+            ],
+        )?;
+        assert_eq!(feature.len(), 6);
+        assert_eq!(feature.strand(), Strand::Reverse);
+        assert_eq!(feature.extract(&seq)?.to_string(), "GTAATT");
+        assert_eq!(
+            Feature::from_parts("CDS", []),
+            Err(Error::EmptyFeature("CDS".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn annotated_records() -> Result<(), Error> {
+        let record = SeqRecord::new("pTest", Seq::dna("AACCGGTTAC")?).with_desc("A test plasmid");
+        let inside = Region::new(2..8, Strand::Forward)?;
+        let outside = Region::new(8..12, Strand::Forward)?;
+        let annotated = AnnotatedRecord::new(record, Topology::Circular)
+            .with_division("SYN")
+            .with_metadata("KEYWORDS", "test")
+            .with_feature(Feature::new("misc_feature", inside))?;
+        assert_eq!(annotated.id(), "pTest");
+        assert_eq!(annotated.desc(), Some("A test plasmid"));
+        assert_eq!(annotated.topology(), Topology::Circular);
+        assert_eq!(annotated.division(), Some("SYN"));
+        assert_eq!(annotated.date(), None);
+        assert_eq!(annotated.features().len(), 1);
+        assert_eq!(
+            annotated.with_feature(Feature::new("misc_feature", outside)),
+            Err(Error::RegionBounds(outside, 10))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enzyme::{Enzyme, Site},
+    seq::{Error, Kind, Seq},
+    types::{Case, Topology},
+};
+
+// ===== BioBrick Standard (RFC10) =================================================================
+
+// NOTE: The prefix carries EcoRI, NotI, and XbaI sites, and the suffix SpeI, NotI, and PstI sites.
+// Coding parts use a shorter prefix, so that the XbaI site runs straight into their start codon
+const PREFIX: &str = "GAATTCGCGGCCGCTTCTAGAG";
+const CODING_PREFIX: &str = "GAATTCGCGGCCGCTTCTAG";
+const SUFFIX: &str = "TACTAGTAGCGGCCGCTGCAG";
+
+// NOTE: Joining a SpeI end to an XbaI end leaves a mixed site that neither enzyme can cut. The scar
+// is two bases shorter in front of a coding part, keeping the spacing used by the coding prefix
+const SCAR: &str = "TACTAGAG";
+const CODING_SCAR: &str = "TACTAG";
+
+const ILLEGAL_ENZYMES: [&str; 5] = ["EcoRI", "XbaI", "SpeI", "PstI", "NotI"];
+
+// NOTE: Any RFC10 sites inside a part (on either strand), by the position of the site. Parts with
+// none of these can be used in standard assembly
+pub fn illegal_sites(part: &Seq) -> Result<Vec<(String, Site)>, Error> {
+    let mut sites = Vec::new();
+    for name in ILLEGAL_ENZYMES {
+        let enzyme = Enzyme::builtin(name).expect("RFC10 enzymes are all built in");
+        for site in enzyme.find_sites(part, Topology::Linear)? {
+            sites.push((name.to_string(), site));
+        }
+    }
+    sites.sort_by_key(|(_, s)| s.position());
+    Ok(sites)
+}
+
+// NOTE: A BioBrick only holds its insert (the part between the prefix and suffix), which is always
+// uppercase DNA. Parts starting with ATG are treated as coding sequences
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct BioBrick {
+    insert: Seq,
+}
+
+impl BioBrick {
+    pub fn new(insert: &Seq) -> Result<Self, Error> {
+        let insert = insert.convert(Kind::Dna)?.normalize_case(Case::Upper);
+        if let Some((enzyme, site)) = illegal_sites(&insert)?.into_iter().next() {
+            return Err(Error::IllegalSite(enzyme, site.position()));
+        }
+        Ok(Self { insert })
+    }
+
+    pub fn insert(&self) -> &Seq {
+        &self.insert
+    }
+
+    pub fn is_coding(&self) -> bool {
+        self.insert.as_bytes().starts_with(b"ATG")
+    }
+
+    // NOTE: The full part, flanked by the prefix and suffix
+    pub fn to_seq(&self) -> Seq {
+        let prefix = if self.is_coding() {
+            CODING_PREFIX
+        } else {
+            PREFIX
+        };
+        let bytes = [prefix.as_bytes(), self.insert.as_bytes(), SUFFIX.as_bytes()].concat();
+        Seq::dna(bytes).expect("BioBricks are always valid DNA")
+    }
+
+    // NOTE: Standard (and 3A) assembly places this part upstream of `downstream`, joined by a scar.
+    // The composite is a BioBrick in its own right, unless the join happens to create a new site
+    pub fn assemble(&self, downstream: &Self) -> Result<Self, Error> {
+        let scar = if downstream.is_coding() {
+            CODING_SCAR
+        } else {
+            SCAR
+        };
+        let bytes = [
+            self.insert.as_bytes(),
+            scar.as_bytes(),
+            downstream.insert.as_bytes(),
+        ]
+        .concat();
+        Self::new(&Seq::dna(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Strand;
+
+    #[test]
+    fn rfc10_compatibility() -> Result<(), Error> {
+        let part = Seq::dna("AAACTGCAGAAAGCGGCCGCAA")?;
+        let sites: Vec<_> = illegal_sites(&part)?
+            .into_iter()
+            .map(|(enzyme, s)| (enzyme, s.position()))
+            .collect();
+        assert_eq!(sites, [("PstI".to_string(), 3), ("NotI".to_string(), 12)]);
+        assert_eq!(
+            BioBrick::new(&part),
+            Err(Error::IllegalSite("PstI".to_string(), 3))
+        );
+        assert!(illegal_sites(&Seq::dna("AAAGAGGAGAAA")?)?.is_empty());
+        assert_eq!(
+            illegal_sites(&Seq::rna("AUCUAGAU")?)?[0].1.strand(),
+            Strand::Forward
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn flank_parts() -> Result<(), Error> {
+        let rbs = BioBrick::new(&Seq::dna("aaagaggagaaa")?)?;
+        assert!(!rbs.is_coding());
+        assert_eq!(
+            rbs.to_seq().to_string(),
+            "GAATTCGCGGCCGCTTCTAGAGAAAGAGGAGAAATACTAGTAGCGGCCGCTGCAG"
+        );
+        let cds = BioBrick::new(&Seq::rna("AUGAAAUAA")?)?;
+        assert!(cds.is_coding());
+        assert_eq!(
+            cds.to_seq().to_string(),
+            "GAATTCGCGGCCGCTTCTAGATGAAATAATACTAGTAGCGGCCGCTGCAG"
+        );
+        assert!(BioBrick::new(&Seq::protein("MAMAPRTEIN")?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn standard_assembly() -> Result<(), Error> {
+        let promoter = BioBrick::new(&Seq::dna("TTGACAATTAATCATCGAACTAGTTAACTAGTACGCA")?);
+        assert_eq!(promoter, Err(Error::IllegalSite("SpeI".to_string(), 18)));
+
+        let promoter = BioBrick::new(&Seq::dna("TTGACAATTAATCATCGGCTCGTATAATGTGTGGA")?)?;
+        let rbs = BioBrick::new(&Seq::dna("AAAGAGGAGAAA")?)?;
+        let cds = BioBrick::new(&Seq::dna("ATGAAATAA")?)?;
+        let expression = promoter.assemble(&rbs)?.assemble(&cds)?;
+        assert_eq!(
+            expression.insert().to_string(),
+            "TTGACAATTAATCATCGGCTCGTATAATGTGTGGA\
+             TACTAGAG\
+             AAAGAGGAGAAA\
+             TACTAG\
+             ATGAAATAA"
+        );
+        assert!(!expression.is_coding());
+        assert_eq!(
+            promoter.assemble(&rbs.assemble(&cds)?)?,
+            promoter.assemble(&rbs)?.assemble(&cds)?
+        );
+
+        // NOTE: A downstream part starting with AATTC completes an EcoRI site across the scar
+        let part = BioBrick::new(&Seq::dna("AATTCAAA")?)?;
+        assert_eq!(
+            rbs.assemble(&part),
+            Err(Error::IllegalSite("EcoRI".to_string(), 19))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+use crate::seq::{Error, Seq};
+
+// ===== Hashing Primitives ========================================================================
+
+// NOTE: SHA-1 is broken as a cryptographic hash, but SEGUIDs are defined in terms of it and are
+// only ever used as identifiers
+fn sha1(bytes: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [
+        0x6745_2301,
+        0xefcd_ab89,
+        0x98ba_dcfe,
+        0x1032_5476,
+        0xc3d2_e1f0,
+    ];
+
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&(bytes.len() as u64 * 8).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+                _ => (b ^ c ^ d, 0xca62_c1d6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(wi);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (h, x) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(x);
+        }
+    }
+
+    let mut digest = [0; 20];
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+// NOTE: SEGUIDs never include base64 padding, so it's never added
+fn base64(bytes: &[u8], url_safe: bool) -> String {
+    let alphabet: &[u8; 64] = if url_safe {
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+    } else {
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+    };
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            encoded.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+// NOTE: This is the CRC-64 used by SWISS-PROT and UniProt (the ISO 3309 polynomial, reflected, with
+// no initial value or final XOR)
+const CRC64_TABLE: [u64; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xd800_0000_0000_0000
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc64(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0, |crc, b| {
+        CRC64_TABLE[((crc ^ b as u64) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+// NOTE: Finds the start of the lexicographically smallest rotation in linear time, using the
+// two-pointer "minimum expression" algorithm
+fn min_rotation(seq: &[u8]) -> Vec<u8> {
+    let n = seq.len();
+    let (mut i, mut j, mut k) = (0, 1, 0);
+    while i < n && j < n && k < n {
+        let (a, b) = (seq[(i + k) % n], seq[(j + k) % n]);
+        if a == b {
+            k += 1;
+            continue;
+        }
+        if a > b {
+            i += k + 1;
+        } else {
+            j += k + 1;
+        }
+        if i == j {
+            j += 1;
+        }
+        k = 0;
+    }
+    let start = i.min(j).min(n);
+    [&seq[start..], &seq[..start]].concat()
+}
+
+// ===== Sequence Checksums ========================================================================
+
+// NOTE: Every checksum here is computed from the uppercased sequence, so soft-masking never changes
+// a sequence's identity. Neither the kind nor the alphabet are included, so (as in registries)
+// "ACGA" has the same SEGUID as DNA or as a protein
+impl Seq {
+    // NOTE: The original SEGUID (Babnigg & Giometti, 2006): the base64-encoded SHA-1 digest, as
+    // used by Biopython's `seguid()`
+    pub fn seguid(&self) -> String {
+        base64(&sha1(&self.upper_bytes()), false)
+    }
+
+    // NOTE: The SEGUID v2 checksums (Pereira et al., 2024) use URL-safe base64 and are prefixed
+    // with their variant: linear or circular, and single- or double-stranded
+    pub fn lsseguid(&self) -> String {
+        format!("lsseguid={}", base64(&sha1(&self.upper_bytes()), true))
+    }
+
+    // NOTE: Circular sequences are identified by their smallest rotation, so every rotation of a
+    // sequence has the same checksum
+    pub fn csseguid(&self) -> String {
+        let rotated = min_rotation(&self.upper_bytes());
+        format!("csseguid={}", base64(&sha1(&rotated), true))
+    }
+
+    // NOTE: Double-stranded checksums hash both strands (each read 5' to 3'), so a sequence and its
+    // reverse complement share a checksum
+    pub fn ldseguid(&self) -> Result<String, Error> {
+        let watson = self.upper_bytes();
+        let crick = self.reverse_complement()?.upper_bytes();
+        Ok(format!("ldseguid={}", ds_seguid(&watson, &crick)))
+    }
+
+    pub fn cdseguid(&self) -> Result<String, Error> {
+        let watson = min_rotation(&self.upper_bytes());
+        let crick = min_rotation(&self.reverse_complement()?.upper_bytes());
+        let strand = watson.min(crick);
+        let seq = Seq::new_with_kind(&strand, [self.kind()], self.alphabet())?;
+        let crick = seq.reverse_complement()?.upper_bytes();
+        Ok(format!("cdseguid={}", ds_seguid(&strand, &crick)))
+    }
+
+    // NOTE: The SWISS-PROT CRC64 checksum, which UniProt formats as 16 uppercase hexadecimal digits
+    // (`format!("{:016X}", seq.crc64())`)
+    pub fn crc64(&self) -> u64 {
+        crc64(self.upper_bytes())
+    }
+
+    // NOTE: The CRC64 of the smallest rotation, for identifying circular sequences like plasmids
+    pub fn crc64_circular(&self) -> u64 {
+        crc64(min_rotation(&self.upper_bytes()))
+    }
+
+    fn upper_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_ascii_uppercase()
+    }
+}
+
+fn ds_seguid(watson: &[u8], crick: &[u8]) -> String {
+    let (first, second) = if watson < crick {
+        (watson, crick)
+    } else {
+        (crick, watson)
+    };
+    base64(&sha1(&[first, b";", second].concat()), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Kind;
+
+    #[test]
+    fn sha1_digests() {
+        let hex = |d: [u8; 20]| d.iter().map(|b| format!("{b:02x}")).collect::<String>();
+        assert_eq!(hex(sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(
+            hex(sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            hex(sha1(&[b'a'; 1000])),
+            "291e9a6c66994949b57ba5e650361e98fc36b1ba"
+        );
+    }
+
+    #[test]
+    fn base64_encoding() {
+        assert_eq!(base64(b"", false), "");
+        assert_eq!(base64(b"f", false), "Zg");
+        assert_eq!(base64(b"fo", false), "Zm8");
+        assert_eq!(base64(b"foo", false), "Zm9v");
+        assert_eq!(base64(&[0xfb, 0xff], false), "+/8");
+        assert_eq!(base64(&[0xfb, 0xff], true), "-_8");
+    }
+
+    #[test]
+    fn smallest_rotations() {
+        assert_eq!(min_rotation(b"CABAB"), b"ABABC");
+        assert_eq!(min_rotation(b"AAAA"), b"AAAA");
+        assert_eq!(min_rotation(b"BA"), b"AB");
+        assert_eq!(min_rotation(b""), b"");
+    }
+
+    #[test]
+    fn seguids() -> Result<(), Error> {
+        let dna = Seq::dna("ACGT")?;
+        assert_eq!(dna.seguid(), "IQiZThf2zKn/I1KtqStlEdsHYDQ");
+        assert_eq!(dna.lsseguid(), "lsseguid=IQiZThf2zKn_I1KtqStlEdsHYDQ");
+        assert_eq!(Seq::dna("acgT")?.seguid(), dna.seguid());
+        assert_eq!(Seq::protein("ACGT")?.seguid(), dna.seguid());
+        assert_eq!(dna.ldseguid()?, "ldseguid=FPz6SYzrWB7axUL7dA0IsDBE-do");
+        Ok(())
+    }
+
+    #[test]
+    fn circular_seguids() -> Result<(), Error> {
+        let dna = Seq::dna("GCAATTA")?;
+        let rotated = Seq::dna("ATTAGCA")?;
+        assert_eq!(dna.lsseguid(), "lsseguid=Xvh3taJciXv9zTDS1pws1N6dzgk");
+        assert_ne!(dna.lsseguid(), rotated.lsseguid());
+        assert_eq!(dna.csseguid(), "csseguid=6WtD2EhR9Kq58AxqtMmaYxV4ZCk");
+        assert_eq!(rotated.csseguid(), dna.csseguid());
+
+        // NOTE: TAATTGC is a rotation of the reverse complement
+        let reverse = Seq::dna("TAATTGC")?;
+        assert_eq!(dna.cdseguid()?, "cdseguid=ixUr5CUmsLrLMl-stYRhoOZyfMo");
+        assert_eq!(reverse.cdseguid()?, dna.cdseguid()?);
+        assert_eq!(rotated.cdseguid()?, dna.cdseguid()?);
+        assert_eq!(dna.ldseguid()?, "ldseguid=LdiTKhduSw4f3bH2vQP7TMOK9bk");
+        Ok(())
+    }
+
+    #[test]
+    fn double_stranded_proteins() -> Result<(), Error> {
+        let protein = Seq::protein("MAMAPRTEIN")?;
+        assert_eq!(
+            protein.ldseguid(),
+            Err(Error::ReverseComplement(Kind::Protein))
+        );
+        assert_eq!(
+            protein.cdseguid(),
+            Err(Error::ReverseComplement(Kind::Protein))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn crc64_checksums() -> Result<(), Error> {
+        assert_eq!(crc64(*b"123456789"), 0x46a5_a938_8a5b_effe);
+        let protein = Seq::protein("MAMAPRTEIN")?;
+        assert_eq!(format!("{:016X}", protein.crc64()), "AD1A6201B4176DD6");
+        assert_eq!(Seq::protein("mamaprtein")?.crc64(), protein.crc64());
+        assert_eq!(
+            format!("{:016X}", Seq::dna("ACGT")?.crc64()),
+            "71A87EBDB0000000"
+        );
+        assert_eq!(
+            Seq::dna("GTAC")?.crc64_circular(),
+            Seq::dna("ACGT")?.crc64()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,496 @@
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::{
+    data::CODON_TABLE,
+    seq::{Error, Kind, Seq},
+    types::Case,
+};
+
+// ===== Codon Indexing ============================================================================
+
+// NOTE: Codons are indexed in the order of the standard codon table (U, C, A, then G at each
+// position), which is also the order codon usage databases like Kazusa list them in
+const BASES: [u8; 4] = *b"UCAG";
+
+fn base_index(base: u8) -> Option<usize> {
+    match base.to_ascii_uppercase() {
+        b'U' | b'T' => Some(0),
+        b'C' => Some(1),
+        b'A' => Some(2),
+        b'G' => Some(3),
+        _ => None,
+    }
+}
+
+pub(crate) fn codon_index(codon: &[u8]) -> Option<usize> {
+    match codon {
+        &[b1, b2, b3] => Some(base_index(b1)? * 16 + base_index(b2)? * 4 + base_index(b3)?),
+        _ => None,
+    }
+}
+
+pub(crate) fn codon(index: usize) -> [u8; 3] {
+    [BASES[index / 16], BASES[index / 4 % 4], BASES[index % 4]]
+}
+
+pub(crate) fn residue(index: usize) -> u8 {
+    CODON_TABLE[&codon(index)[..]]
+}
+
+// NOTE: Overwrites a codon in place, keeping the case of the original and writing T or U to match
+// the kind of nucleic acid
+pub(crate) fn replace_codon(original: &mut [u8], index: usize, kind: Kind) {
+    let lowercase = original[0].is_ascii_lowercase();
+    for (b, new) in original.iter_mut().zip(codon(index)) {
+        let new = if new == b'U' && kind != Kind::Rna {
+            b'T'
+        } else {
+            new
+        };
+        *b = if lowercase {
+            new.to_ascii_lowercase()
+        } else {
+            new
+        };
+    }
+}
+
+pub(crate) fn synonymous(index: usize) -> impl Iterator<Item = usize> {
+    let aa = residue(index);
+    (0..64).filter(move |&j| residue(j) == aa)
+}
+
+// NOTE: Ambiguous codons (those containing N or other IUPAC codes) are skipped, as is any trailing
+// partial codon
+pub(crate) fn codon_indices(seq: &Seq) -> Result<impl Iterator<Item = (usize, usize)>, Error> {
+    if !seq.kind().is_nucleic_acid() {
+        return Err(Error::CodonCount(seq.kind()));
+    }
+    let bytes = seq.normalize_case(Case::Upper).into_bytes();
+    let indices: Vec<_> = bytes
+        .chunks_exact(3)
+        .enumerate()
+        .filter_map(|(i, c)| Some((i, codon_index(c)?)))
+        .collect();
+    Ok(indices.into_iter())
+}
+
+// ===== Codon Usage ===============================================================================
+
+// NOTE: Usage is stored as a (possibly fractional) count for each of the 64 codons. Tables can hold
+// raw counts or frequencies per thousand codons, as only their relative sizes are ever used
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CodonUsage {
+    pub(crate) counts: [f64; 64],
+}
+
+impl Default for CodonUsage {
+    fn default() -> Self {
+        Self { counts: [0.0; 64] }
+    }
+}
+
+impl CodonUsage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_seqs<T: AsRef<Seq>>(cds: impl IntoIterator<Item = T>) -> Result<Self, Error> {
+        let mut usage = Self::new();
+        for seq in cds {
+            usage.add(seq.as_ref())?;
+        }
+        Ok(usage)
+    }
+
+    pub fn add(&mut self, cds: &Seq) -> Result<(), Error> {
+        for (_, i) in codon_indices(cds)? {
+            self.counts[i] += 1.0;
+        }
+        Ok(())
+    }
+
+    pub fn count(&self, codon: impl AsRef<[u8]>) -> f64 {
+        codon_index(codon.as_ref()).map_or(0.0, |i| self.counts[i])
+    }
+
+    pub fn per_thousand(&self, codon: impl AsRef<[u8]>) -> f64 {
+        let total: f64 = self.counts.iter().sum();
+        self.count(codon) * 1000.0 / total
+    }
+
+    // NOTE: The share of an amino acid's codons that are this one
+    pub fn fraction(&self, codon: impl AsRef<[u8]>) -> f64 {
+        let Some(i) = codon_index(codon.as_ref()) else {
+            return 0.0;
+        };
+        self.counts[i] / synonymous(i).map(|j| self.counts[j]).sum::<f64>()
+    }
+
+    // NOTE: This is the `w` of Sharp & Li (1987): a codon's count relative to the most used codon
+    // for the same amino acid. As they suggest, unused codons are given half a count so that a
+    // single one doesn't drag the CAI of a whole sequence to zero
+    pub fn relative_adaptiveness(&self, codon: impl AsRef<[u8]>) -> f64 {
+        codon_index(codon.as_ref()).map_or(0.0, |i| self.adaptiveness(i))
+    }
+
+    pub(crate) fn adaptiveness(&self, index: usize) -> f64 {
+        let max = synonymous(index)
+            .map(|j| self.counts[j])
+            .fold(0.0, f64::max);
+        self.counts[index].max(0.5) / max.max(0.5)
+    }
+}
+
+// ===== Codon Adaptation Index ====================================================================
+
+impl Seq {
+    // NOTE: Stop codons and amino acids with a single codon (Met and Trp) tell you nothing about
+    // codon choice, so they are left out of the geometric mean. A sequence with no codons left to
+    // score has a CAI of NaN
+    pub fn cai(&self, usage: &CodonUsage) -> Result<f64, Error> {
+        let (log_sum, n) = codon_indices(self)?
+            .map(|(_, i)| i)
+            .filter(|&i| residue(i) != b'*' && synonymous(i).count() > 1)
+            .fold((0.0, 0), |(sum, n), i| {
+                (sum + usage.adaptiveness(i).ln(), n + 1)
+            });
+        Ok((log_sum / n as f64).exp())
+    }
+}
+
+// ===== Synonymous Shuffling ======================================================================
+
+impl Seq {
+    // NOTE: Recodes a CDS without changing its protein. Given a codon usage table, every codon is
+    // redrawn from its amino acid's codons in proportion to their usage (or uniformly, if the table
+    // has none of them). Without a table, codons are instead shuffled between the positions coding
+    // for the same amino acid, so the sequence keeps exactly the same codon usage. Stop codons,
+    // ambiguous codons, and any trailing partial codon are left untouched, and each new codon takes
+    // the case of the one it replaces
+    pub fn synonymous_shuffle(
+        &self,
+        rng: &mut impl Rng,
+        table: Option<&CodonUsage>,
+    ) -> Result<Self, Error> {
+        let codons: Vec<_> = codon_indices(self)?
+            .filter(|&(_, i)| residue(i) != b'*')
+            .collect();
+
+        let mut replacements = vec![0; codons.len()];
+        match table {
+            Some(usage) => {
+                for (new, &(_, i)) in replacements.iter_mut().zip(&codons) {
+                    let synonyms: Vec<_> = synonymous(i).collect();
+                    *new = synonyms
+                        .choose_weighted(rng, |&j| usage.counts[j])
+                        .map_or_else(|_| *synonyms.choose(rng).unwrap_or(&i), |&j| j);
+                }
+            }
+            None => {
+                let mut by_residue: Vec<Vec<usize>> = vec![Vec::new(); 256];
+                for (n, &(_, i)) in codons.iter().enumerate() {
+                    by_residue[residue(i) as usize].push(n);
+                }
+                for positions in by_residue.iter().filter(|p| !p.is_empty()) {
+                    let mut shuffled: Vec<_> = positions.iter().map(|&n| codons[n].1).collect();
+                    shuffled.shuffle(rng);
+                    for (&n, i) in positions.iter().zip(shuffled) {
+                        replacements[n] = i;
+                    }
+                }
+            }
+        }
+
+        let mut bytes = self.as_bytes().to_vec();
+        for (&(n, _), &i) in codons.iter().zip(&replacements) {
+            replace_codon(&mut bytes[n * 3..n * 3 + 3], i, self.kind());
+        }
+        Self::from_vec_with_kind(bytes, [self.kind()], self.alphabet())
+    }
+}
+
+// ===== Rare Codon Detection ======================================================================
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RareCodon {
+    pub position: usize,
+    pub codon: [u8; 3],
+    pub per_thousand: f64,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct RareCodonCluster {
+    pub range: Range<usize>,
+    pub rare: usize,
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct RareCodons {
+    pub codons: Vec<RareCodon>,
+    pub clusters: Vec<RareCodonCluster>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct RareCodonFinder<'a> {
+    usage: &'a CodonUsage,
+    threshold: f64,
+    window: usize,
+    min_cluster: usize,
+}
+
+impl<'a> RareCodonFinder<'a> {
+    // NOTE: By default, codons used less than 5 times per thousand are rare (which, in E. coli,
+    // picks out the classic AGG, AGA, CGA, CUA, and AUA codons), and a cluster is at least 3 rare
+    // codons within a window of 10
+    pub fn new(usage: &'a CodonUsage) -> Self {
+        Self {
+            usage,
+            threshold: 5.0,
+            window: 10,
+            min_cluster: 3,
+        }
+    }
+
+    pub fn threshold(self, threshold: f64) -> Self {
+        Self { threshold, ..self }
+    }
+
+    pub fn window(self, window: usize) -> Self {
+        Self { window, ..self }
+    }
+
+    pub fn min_cluster(self, min_cluster: usize) -> Self {
+        Self {
+            min_cluster,
+            ..self
+        }
+    }
+
+    // NOTE: Positions and cluster ranges are in nucleotides, with clusters running from the start
+    // of their first rare codon to the end of their last. Stop codons are never counted as rare
+    pub fn find(&self, cds: &Seq) -> Result<RareCodons, Error> {
+        let codons: Vec<_> = codon_indices(cds)?
+            .filter(|&(_, i)| residue(i) != b'*')
+            .map(|(n, i)| RareCodon {
+                position: n * 3,
+                codon: codon(i),
+                per_thousand: self.usage.per_thousand(codon(i)),
+            })
+            .filter(|c| c.per_thousand < self.threshold)
+            .collect();
+        let numbers: Vec<_> = codons.iter().map(|c| c.position / 3).collect();
+
+        // NOTE: Every window of `min_cluster` rare codons spanning fewer than `window` codons is a
+        // cluster, and clusters sharing rare codons are merged
+        let min_cluster = self.min_cluster.max(1);
+        let mut spans = Vec::new();
+        let mut current: Option<(usize, usize)> = None;
+        for (i, w) in numbers.windows(min_cluster).enumerate() {
+            if w[min_cluster - 1] - w[0] >= self.window {
+                continue;
+            }
+            let last = i + min_cluster - 1;
+            current = match current {
+                Some((first, end)) if i <= end => Some((first, last)),
+                Some(span) => {
+                    spans.push(span);
+                    Some((i, last))
+                }
+                None => Some((i, last)),
+            };
+        }
+        spans.extend(current);
+
+        let clusters = spans
+            .into_iter()
+            .map(|(first, last)| RareCodonCluster {
+                range: numbers[first] * 3..(numbers[last] + 1) * 3,
+                rare: last - first + 1,
+            })
+            .collect();
+
+        Ok(RareCodons { codons, clusters })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Kind;
+
+    fn leucine_usage() -> Result<CodonUsage, Error> {
+        CodonUsage::from_seqs([Seq::dna("CTGCTGCTGCTA")?, Seq::rna("cug")?])
+    }
+
+    #[test]
+    fn codon_indexing() {
+        assert_eq!(codon_index(b"UUU"), Some(0));
+        assert_eq!(codon_index(b"ttc"), Some(1));
+        assert_eq!(codon_index(b"GGG"), Some(63));
+        assert_eq!(codon_index(b"GGN"), None);
+        assert_eq!(codon_index(b"GG"), None);
+        assert!((0..64).all(|i| codon_index(&codon(i)) == Some(i)));
+        assert_eq!(residue(codon_index(b"AUG").unwrap()), b'M');
+    }
+
+    #[test]
+    fn usage_from_seqs() -> Result<(), Error> {
+        let usage = leucine_usage()?;
+        assert_eq!(usage.count("CUG"), 4.0);
+        assert_eq!(usage.count("CTA"), 1.0);
+        assert_eq!(usage.count("UUA"), 0.0);
+        assert_eq!(usage.per_thousand("CUG"), 800.0);
+        assert_eq!(usage.fraction("CUG"), 0.8);
+        assert_eq!(usage.relative_adaptiveness("CUA"), 0.25);
+        assert_eq!(usage.relative_adaptiveness("UUA"), 0.125);
+        Ok(())
+    }
+
+    #[test]
+    fn usage_skips_ambiguous_codons() -> Result<(), Error> {
+        let usage = CodonUsage::from_seqs([Seq::dna_iupac("CTGCTNCTGCT")?])?;
+        assert_eq!(usage.count("CUG"), 2.0);
+        assert_eq!(usage.counts.iter().sum::<f64>(), 2.0);
+        assert_eq!(
+            CodonUsage::from_seqs([Seq::protein("MAMAPRTEIN")?]),
+            Err(Error::CodonCount(Kind::Protein))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cai_of_cds() -> Result<(), Error> {
+        let usage = leucine_usage()?;
+        assert_eq!(Seq::dna("CTGCTG")?.cai(&usage)?, 1.0);
+        let cai = Seq::dna("ATGCTGCTATGGTAA")?.cai(&usage)?;
+        assert!((cai - 0.5).abs() < 1e-12);
+        assert!(Seq::dna("ATGTGGTAA")?.cai(&usage)?.is_nan());
+        assert_eq!(
+            Seq::protein("MAMAPRTEIN")?.cai(&usage),
+            Err(Error::CodonCount(Kind::Protein))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cai_against_host_tables() -> Result<(), Error> {
+        use crate::data::{E_COLI_K12_CODON_USAGE, S_CEREVISIAE_CODON_USAGE};
+
+        let e_coli_cds = Seq::dna("ATGCTGCGTAAAGAACCGTAA")?;
+        let yeast_cds = Seq::dna("ATGTTGAGAAAAGAACCATAA")?;
+        assert!(e_coli_cds.cai(&E_COLI_K12_CODON_USAGE)? > yeast_cds.cai(&E_COLI_K12_CODON_USAGE)?);
+        assert!(
+            yeast_cds.cai(&S_CEREVISIAE_CODON_USAGE)?
+                > e_coli_cds.cai(&S_CEREVISIAE_CODON_USAGE)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn shuffle_with_usage_table() -> Result<(), Error> {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let cds = Seq::dna("ATGTTACTTCTCCTACTGTTGAAAGGTtggctaTAA")?;
+        let protein = cds.convert(Kind::Protein)?;
+        let usage = CodonUsage::from_seqs([Seq::dna("CTGAAA")?])?;
+        for _ in 0..10 {
+            let shuffled = cds.synonymous_shuffle(&mut rng, Some(&usage))?;
+            assert_eq!(shuffled.convert(Kind::Protein)?, protein);
+            assert_eq!(
+                shuffled.to_string().get(..24),
+                Some("ATGCTGCTGCTGCTGCTGCTGAAA")
+            );
+            assert_eq!(shuffled.to_string().get(27..), Some("tggctgTAA"));
+        }
+
+        let rna = Seq::rna_iupac("AUGCUNCUUGCUAA")?;
+        let shuffled = rna.synonymous_shuffle(&mut rng, Some(&usage))?;
+        assert_eq!(shuffled.to_string().get(..9), Some("AUGCUNCUG"));
+        assert_eq!(shuffled.to_string().get(12..), Some("AA"));
+        assert_eq!(
+            shuffled.convert(Kind::Protein)?,
+            rna.convert(Kind::Protein)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn shuffle_preserving_usage() -> Result<(), Error> {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let cds = Seq::dna("ATGTTACTTCTCCTACTGTTGAAAAAGGGTGGCGGAGGGTGGTAA")?;
+        let usage = CodonUsage::from_seqs([&cds])?;
+        let shuffles: Vec<_> = (0..10)
+            .map(|_| cds.synonymous_shuffle(&mut rng, None))
+            .collect::<Result<_, _>>()?;
+        for shuffled in &shuffles {
+            assert_eq!(
+                shuffled.convert(Kind::Protein)?,
+                cds.convert(Kind::Protein)?
+            );
+            assert_eq!(CodonUsage::from_seqs([shuffled])?, usage);
+        }
+        assert!(shuffles.iter().any(|s| s != &cds));
+        assert_eq!(
+            Seq::protein("MAMAPRTEIN")?.synonymous_shuffle(&mut rng, None),
+            Err(Error::CodonCount(Kind::Protein))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_rare_codons() -> Result<(), Error> {
+        use crate::data::E_COLI_K12_CODON_USAGE;
+
+        let finder = RareCodonFinder::new(&E_COLI_K12_CODON_USAGE);
+        let cds = Seq::dna("ATGAGGCTGACACTAGAACCGTAG")?;
+        let rare = finder.find(&cds)?;
+        let positions: Vec<_> = rare.codons.iter().map(|c| c.position).collect();
+        assert_eq!(positions, [3, 12]);
+        assert_eq!(&rare.codons[0].codon, b"AGG");
+        assert_eq!(rare.codons[0].per_thousand.round(), 1.0);
+        assert!(rare.clusters.is_empty());
+
+        let rare = finder.threshold(10.0).find(&cds)?;
+        assert_eq!(rare.codons.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn find_rare_codon_clusters() -> Result<(), Error> {
+        use crate::data::E_COLI_K12_CODON_USAGE;
+
+        let finder = RareCodonFinder::new(&E_COLI_K12_CODON_USAGE);
+        // NOTE: Rare codons are at codons 1, 2, 4, 5, and 20
+        let cds = Seq::dna("ATGAGGAGACTGCTAATAGAAGAAGAAGAAGAAGAAGAAGAAGAAGAAGAAGAAGAAGAAAGGTAA")?;
+        let rare = finder.find(&cds)?;
+        assert_eq!(rare.codons.len(), 5);
+        assert_eq!(
+            rare.clusters,
+            [RareCodonCluster {
+                range: 3..18,
+                rare: 4
+            }]
+        );
+        let rare = finder.min_cluster(2).window(2).find(&cds)?;
+        assert_eq!(
+            rare.clusters,
+            [
+                RareCodonCluster {
+                    range: 3..9,
+                    rare: 2
+                },
+                RareCodonCluster {
+                    range: 12..18,
+                    rare: 2
+                }
+            ]
+        );
+        Ok(())
+    }
+}
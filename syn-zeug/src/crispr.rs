@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::{
+    data::IUPAC_DNA,
+    region::Region,
+    seq::{Error, Kind, Seq},
+    types::{Case, Strand},
+};
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum PamSide {
+    Five,
+    Three,
+}
+
+// NOTE: `region` is where the protospacer sits (on the strand the guide targets), and `protospacer`
+// and `pam` are always read 5' to 3' along that strand
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Guide {
+    pub protospacer: Seq,
+    pub pam: Seq,
+    pub region: Region,
+    pub gc_content: f64,
+    pub score: f64,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct OffTarget {
+    pub region: Region,
+    pub mismatches: usize,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct GuideDesigner {
+    pam: Vec<u8>,
+    pam_side: PamSide,
+    guide_len: usize,
+}
+
+// NOTE: The uppercase bytes of one strand, along with the range of every PAM-adjacent protospacer
+// on it (in the strand's own coordinates)
+struct StrandSites {
+    strand: Strand,
+    bytes: Vec<u8>,
+    sites: Vec<Range<usize>>,
+}
+
+// NOTE: A PAM base matches any of the bases its IUPAC code stands for
+fn pam_matches(pam: &[u8], site: &[u8]) -> bool {
+    pam.iter()
+        .zip(site)
+        .all(|(&p, &b)| p == b || IUPAC_DNA.get(&p).is_some_and(|bases| bases.contains(&b)))
+}
+
+impl GuideDesigner {
+    pub fn new(pam: &Seq, pam_side: PamSide, guide_len: usize) -> Result<Self, Error> {
+        if pam.kind() != Kind::Dna {
+            return Err(Error::GuideDesign(pam.kind()));
+        }
+        Ok(Self {
+            pam: pam.normalize_case(Case::Upper).into_bytes(),
+            pam_side,
+            guide_len,
+        })
+    }
+
+    pub fn spcas9() -> Self {
+        Self {
+            pam: b"NGG".to_vec(),
+            pam_side: PamSide::Three,
+            guide_len: 20,
+        }
+    }
+
+    pub fn cas12a() -> Self {
+        Self {
+            pam: b"TTTV".to_vec(),
+            pam_side: PamSide::Five,
+            guide_len: 23,
+        }
+    }
+
+    fn sites(&self, seq: &Seq) -> Result<Vec<StrandSites>, Error> {
+        if seq.kind() != Kind::Dna {
+            return Err(Error::GuideDesign(seq.kind()));
+        }
+
+        let upper = seq.normalize_case(Case::Upper);
+        let (guide_len, pam_len) = (self.guide_len, self.pam.len());
+        let mut strands = Vec::new();
+        for (strand, bytes) in [
+            (Strand::Forward, upper.as_bytes().to_vec()),
+            (Strand::Reverse, upper.reverse_complement()?.into_bytes()),
+        ] {
+            let sites = (0..(bytes.len() + 1).saturating_sub(guide_len + pam_len))
+                .filter_map(|start| {
+                    let (protospacer, pam) = match self.pam_side {
+                        PamSide::Three => (start..start + guide_len, start + guide_len),
+                        PamSide::Five => (start + pam_len..start + pam_len + guide_len, start),
+                    };
+                    pam_matches(&self.pam, &bytes[pam..pam + pam_len]).then_some(protospacer)
+                })
+                .collect();
+            strands.push(StrandSites {
+                strand,
+                bytes,
+                sites,
+            });
+        }
+        Ok(strands)
+    }
+
+    fn forward_region(range: Range<usize>, strand: Strand, len: usize) -> Result<Region, Error> {
+        match strand {
+            Strand::Forward => Region::new(range, strand),
+            Strand::Reverse => Region::new(len - range.end..len - range.start, strand),
+        }
+    }
+
+    // NOTE: Only guides whose protospacer lies wholly within `region` (of the forward strand) are
+    // returned, best first
+    pub fn design(&self, target: &Seq, region: Range<usize>) -> Result<Vec<Guide>, Error> {
+        let mut guides = Vec::new();
+        for StrandSites {
+            strand,
+            bytes,
+            sites,
+        } in self.sites(target)?
+        {
+            for protospacer in sites {
+                let site = Self::forward_region(protospacer.clone(), strand, target.len())?;
+                if site.start() < region.start || site.end() > region.end {
+                    continue;
+                }
+                let pam = match self.pam_side {
+                    PamSide::Three => protospacer.end..protospacer.end + self.pam.len(),
+                    PamSide::Five => protospacer.start - self.pam.len()..protospacer.start,
+                };
+                let protospacer =
+                    Seq::new_with_kind(&bytes[protospacer], [Kind::Dna], target.alphabet())?;
+                let gc_content = protospacer.gc_content()?;
+                guides.push(Guide {
+                    pam: Seq::new_with_kind(&bytes[pam], [Kind::Dna], target.alphabet())?,
+                    region: site,
+                    gc_content,
+                    score: on_target_score(&protospacer, gc_content, self.pam_side),
+                    protospacer,
+                });
+            }
+        }
+        guides.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(guides)
+    }
+
+    // NOTE: Every PAM-adjacent site within `max_mismatches` is reported, so if the background
+    // contains the guide's own target, its on-target site comes first with no mismatches (and should
+    // be skipped by callers that only want the true off-targets)
+    // OPTIMISATION: This is a brute-force scan of every PAM-adjacent site in the background, which
+    // is fine for plasmids and small genomes — larger backgrounds will want an index
+    pub fn off_targets(
+        &self,
+        protospacer: &Seq,
+        background: &Seq,
+        max_mismatches: usize,
+    ) -> Result<Vec<OffTarget>, Error> {
+        if protospacer.kind() != Kind::Dna {
+            return Err(Error::GuideDesign(protospacer.kind()));
+        }
+        let guide = protospacer.normalize_case(Case::Upper);
+        let mut off_targets = Vec::new();
+        for StrandSites {
+            strand,
+            bytes,
+            sites,
+        } in self.sites(background)?
+        {
+            for site in sites {
+                if guide.len() != site.len() {
+                    continue;
+                }
+                let mismatches = guide
+                    .as_bytes()
+                    .iter()
+                    .zip(&bytes[site.clone()])
+                    .filter(|(g, b)| g != b)
+                    .count();
+                if mismatches <= max_mismatches {
+                    off_targets.push(OffTarget {
+                        region: Self::forward_region(site, strand, background.len())?,
+                        mismatches,
+                    });
+                }
+            }
+        }
+        off_targets.sort_by_key(|o| (o.mismatches, o.region.start(), o.region.strand()));
+        Ok(off_targets)
+    }
+}
+
+// NOTE: A simple, additive on-target heuristic (starting from 1.0) in the spirit of the early
+// design rules: GC content between 40% and 80% is preferred, a TTTT run (which terminates Pol III
+// transcription of the guide) is heavily penalised, other long homopolymers are mildly penalised,
+// and a G at the PAM-proximal end of a 3' PAM guide is rewarded
+fn on_target_score(protospacer: &Seq, gc_content: f64, pam_side: PamSide) -> f64 {
+    let bytes = protospacer.as_bytes();
+    let mut score: f64 = 1.0;
+    if !(0.4..=0.8).contains(&gc_content) {
+        score -= 0.3;
+    }
+    if bytes.windows(4).any(|w| w == b"TTTT") {
+        score -= 0.5;
+    } else if protospacer
+        .max_homopolymer_run()
+        .is_some_and(|(_, run)| run.len() >= 5)
+    {
+        score -= 0.1;
+    }
+    if pam_side == PamSide::Three && bytes.last() == Some(&b'G') {
+        score += 0.1;
+    }
+    score.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // NOTE: One NGG PAM on the forward strand (at 22) and one on the reverse strand (the CCN at 2)
+    const TARGET: &str = "AACCAGCTAGCTAGGACTGACTTGGAA";
+
+    #[test]
+    fn design_spcas9_guides() -> Result<(), Error> {
+        let target = Seq::dna(TARGET)?;
+        let guides = GuideDesigner::spcas9().design(&target, 0..target.len())?;
+        assert_eq!(guides.len(), 2);
+        let forward = guides
+            .iter()
+            .find(|g| g.region.strand() == Strand::Forward)
+            .unwrap();
+        assert_eq!(forward.region.range(), 2..22);
+        assert_eq!(forward.protospacer.to_string(), &TARGET[2..22]);
+        assert_eq!(forward.pam.to_string(), "TGG");
+        let reverse = guides
+            .iter()
+            .find(|g| g.region.strand() == Strand::Reverse)
+            .unwrap();
+        assert_eq!(reverse.region.range(), 5..25);
+        assert_eq!(
+            reverse.protospacer,
+            Seq::dna(&TARGET[5..25])?.reverse_complement()?
+        );
+        assert_eq!(reverse.pam.to_string(), "TGG");
+        Ok(())
+    }
+
+    #[test]
+    fn design_within_region() -> Result<(), Error> {
+        let target = Seq::dna(TARGET)?;
+        let guides = GuideDesigner::spcas9().design(&target, 0..24)?;
+        assert_eq!(guides.len(), 1);
+        assert_eq!(guides[0].region.strand(), Strand::Forward);
+        Ok(())
+    }
+
+    #[test]
+    fn design_five_prime_pam() -> Result<(), Error> {
+        let target = Seq::dna("TTTAGCTAGCTAGGACTGACTAGCTAGCCC")?;
+        let designer = GuideDesigner::new(&Seq::dna_iupac("TTTV")?, PamSide::Five, 23)?;
+        let guides = designer.design(&target, 0..target.len())?;
+        assert_eq!(guides.len(), 1);
+        assert_eq!(guides[0].region.range(), 4..27);
+        assert_eq!(guides[0].pam.to_string(), "TTTA");
+        assert_eq!(designer, GuideDesigner::cas12a());
+        Ok(())
+    }
+
+    #[test]
+    fn on_target_scores() -> Result<(), Error> {
+        let score = |s| -> Result<f64, Error> {
+            let seq = Seq::dna(s)?;
+            Ok(on_target_score(&seq, seq.gc_content()?, PamSide::Three))
+        };
+        assert_eq!(score("ACGTACGTACGTACGTACGG")?, 1.0);
+        assert_eq!(score("ACGTACGTACGTACGTACGA")?, 1.0);
+        assert!((score("ACGTACGTTTTTACGTACGA")? - 0.5).abs() < 1e-12);
+        assert!((score("AAAAAAAAAAAAAAAAAAAA")? - 0.6).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn find_off_targets() -> Result<(), Error> {
+        let designer = GuideDesigner::spcas9();
+        let guide = Seq::dna("GCTAGCTAGGACTGACTTAC")?;
+        let background = Seq::dna(
+            "GCTAGCTAGGACTGACTTACAGGTTTTGCTAGCTAGGACTGTCTTACTGGTTTTGCTTGCTAGGACAGTCTTACCGG",
+        )?;
+        let hits = designer.off_targets(&guide, &background, 1)?;
+        assert_eq!(
+            hits,
+            [
+                OffTarget {
+                    region: Region::new(0..20, Strand::Forward)?,
+                    mismatches: 0
+                },
+                OffTarget {
+                    region: Region::new(27..47, Strand::Forward)?,
+                    mismatches: 1
+                }
+            ]
+        );
+        assert_eq!(designer.off_targets(&guide, &background, 3)?.len(), 3);
+        let rc = background.reverse_complement()?;
+        let hits = designer.off_targets(&guide, &rc, 0)?;
+        assert_eq!(
+            hits[0].region,
+            Region::new(rc.len() - 20..rc.len(), Strand::Reverse)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn off_targets_include_the_target() -> Result<(), Error> {
+        let target = Seq::dna(TARGET)?;
+        let designer = GuideDesigner::spcas9();
+        for guide in designer.design(&target, 0..target.len())? {
+            let hits = designer.off_targets(&guide.protospacer, &target, 0)?;
+            assert_eq!(
+                hits,
+                [OffTarget {
+                    region: guide.region,
+                    mismatches: 0
+                }]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn guide_design_non_dna() -> Result<(), Error> {
+        assert_eq!(
+            GuideDesigner::spcas9().design(&Seq::rna("ACGU")?, 0..4),
+            Err(Error::GuideDesign(Kind::Rna))
+        );
+        Ok(())
+    }
+}
@@ -5,11 +5,12 @@ use std::{collections::HashMap, iter};
 use once_cell::sync::Lazy;
 
 use crate::{
+    codon::CodonUsage,
     seq::{Alphabet, Kind},
     utils::expand_iupac,
 };
 
-pub const ALPHABETS: [(Kind, Alphabet); 8] = [
+pub const ALPHABETS: [(Kind, Alphabet); 9] = [
     (Kind::Dna, Alphabet::Base),
     (Kind::Rna, Alphabet::Base),
     (Kind::Dna, Alphabet::N),
@@ -18,6 +19,7 @@ pub const ALPHABETS: [(Kind, Alphabet); 8] = [
     (Kind::Dna, Alphabet::Iupac),
     (Kind::Rna, Alphabet::Iupac),
     (Kind::Protein, Alphabet::Iupac),
+    (Kind::Protein, Alphabet::Extended),
 ];
 
 // NOTE: I might need to add a gap character (`.` or `-`) to these alphabets someday
@@ -39,6 +41,14 @@ pub static ALPHABET_MAP: Lazy<HashMap<(Kind, Alphabet), bio::alphabets::Alphabet
                 (Kind::Protein, Alphabet::Iupac),
                 protein::iupac_alphabet().union(&stop_codon),
             ),
+            // NOTE: Adds selenocysteine (U), pyrrolysine (O), and the leucine / isoleucine
+            // ambiguity code (J), all of which turn up in UniProt entries
+            (
+                (Kind::Protein, Alphabet::Extended),
+                protein::iupac_alphabet()
+                    .union(&stop_codon)
+                    .union(&bio::alphabets::Alphabet::new(b"UOJuoj")),
+            ),
         ])
     });
 
@@ -94,6 +104,7 @@ pub const IUPAC_RNA: Map<u8, &[u8]> = phf_map! {
 pub const IUPAC_PROTEIN: Map<u8, &[u8]> = phf_map! {
     b'B' => b"DN",
     b'Z' => b"EQ",
+    b'J' => b"IL",
 
     b'X' => b"ABCDEFGHIKLMNPQRSTVWYZ",
 };
@@ -225,6 +236,225 @@ pub const CODON_TABLE: Map<&[u8], u8> = phf_map! {
     b"GGG" => b'G',
 };
 
+// ===== Host Codon Usage Tables ===================================================================
+
+// NOTE: These are frequencies per thousand codons from the Kazusa codon usage database (the species
+// ID follows each name), listed in the standard codon table order used by `CodonUsage`
+
+// Escherichia coli K-12 (83333)
+pub const E_COLI_K12_CODON_USAGE: CodonUsage = CodonUsage {
+    counts: [
+        22.4, 16.6, 13.9, 13.7, // UUx
+        8.5, 8.6, 7.2, 8.9, // UCx
+        16.3, 12.2, 2.0, 0.2, // UAx
+        5.2, 6.4, 1.0, 15.2, // UGx
+        11.0, 11.0, 3.9, 52.6, // CUx
+        7.0, 5.5, 8.4, 23.2, // CCx
+        12.9, 9.7, 15.4, 28.8, // CAx
+        20.9, 22.0, 3.6, 5.4, // CGx
+        30.3, 25.0, 4.4, 27.8, // AUx
+        8.9, 23.4, 7.1, 14.4, // ACx
+        17.7, 21.7, 33.6, 10.3, // AAx
+        8.7, 16.0, 2.1, 1.2, // AGx
+        18.3, 15.3, 10.9, 26.4, // GUx
+        15.3, 25.5, 20.3, 33.7, // GCx
+        32.1, 19.1, 39.4, 17.8, // GAx
+        24.7, 29.6, 7.9, 11.0, // GGx
+    ],
+};
+
+// Saccharomyces cerevisiae (4932)
+pub const S_CEREVISIAE_CODON_USAGE: CodonUsage = CodonUsage {
+    counts: [
+        26.1, 18.4, 26.2, 27.2, // UUx
+        23.5, 14.2, 18.7, 8.6, // UCx
+        18.8, 14.8, 1.1, 0.5, // UAx
+        8.1, 4.8, 0.7, 10.4, // UGx
+        12.3, 5.4, 13.4, 10.5, // CUx
+        13.5, 6.8, 18.3, 5.3, // CCx
+        13.6, 7.8, 27.3, 12.1, // CAx
+        6.4, 2.6, 3.0, 1.7, // CGx
+        30.1, 17.2, 17.8, 20.9, // AUx
+        20.3, 12.7, 17.8, 8.0, // ACx
+        35.7, 24.8, 41.9, 30.8, // AAx
+        14.2, 9.8, 21.3, 9.2, // AGx
+        22.1, 11.8, 11.8, 10.8, // GUx
+        21.2, 12.6, 16.2, 6.2, // GCx
+        37.6, 20.2, 45.6, 19.2, // GAx
+        23.9, 9.8, 10.9, 6.0, // GGx
+    ],
+};
+
+// Homo sapiens (9606)
+pub const H_SAPIENS_CODON_USAGE: CodonUsage = CodonUsage {
+    counts: [
+        17.6, 20.3, 7.7, 12.9, // UUx
+        15.2, 17.7, 12.2, 4.4, // UCx
+        12.2, 15.3, 1.0, 0.8, // UAx
+        10.6, 12.6, 1.6, 13.2, // UGx
+        13.2, 19.6, 7.2, 39.6, // CUx
+        17.5, 19.8, 16.9, 6.9, // CCx
+        10.9, 15.1, 12.3, 34.2, // CAx
+        4.5, 10.4, 6.2, 11.4, // CGx
+        16.0, 20.8, 7.5, 22.0, // AUx
+        13.1, 18.9, 15.1, 6.1, // ACx
+        17.0, 19.1, 24.4, 31.9, // AAx
+        12.1, 19.5, 12.2, 12.0, // AGx
+        11.0, 14.5, 7.1, 28.1, // GUx
+        18.4, 27.7, 15.8, 7.4, // GCx
+        21.8, 25.1, 29.0, 39.6, // GAx
+        10.8, 22.2, 16.5, 16.5, // GGx
+    ],
+};
+
+// TODO: Add Bacillus subtilis (1423), once there's a table that has been checked against Kazusa
+
+// ===== Promoter Elements =========================================================================
+
+// NOTE: The consensus base (and the percentage of σ70 promoters with it) at each position of the −35
+// and −10 boxes, from Rosenberg & Court (1979)
+pub const SIGMA70_MINUS_35: [(u8, f64); 6] = [
+    (b'T', 82.0),
+    (b'T', 84.0),
+    (b'G', 78.0),
+    (b'A', 65.0),
+    (b'C', 54.0),
+    (b'A', 45.0),
+];
+
+pub const SIGMA70_MINUS_10: [(u8, f64); 6] = [
+    (b'T', 80.0),
+    (b'A', 95.0),
+    (b'T', 45.0),
+    (b'A', 60.0),
+    (b'A', 50.0),
+    (b'T', 96.0),
+];
+
+// NOTE: Phage promoters run up to (and include) the first transcribed base, and the J23 series
+// are the constitutive Anderson promoters from the iGEM Registry
+pub const KNOWN_PROMOTERS: [(&str, &[u8]); 9] = [
+    ("T7", b"TAATACGACTCACTATAG"),
+    ("T3", b"AATTAACCCTCACTAAAG"),
+    ("SP6", b"ATTTAGGTGACACTATAG"),
+    ("J23100", b"TTGACGGCTAGCTCAGTCCTAGGTACAGTGCTAGC"),
+    ("J23101", b"TTTACAGCTAGCTCAGTCCTAGGTATTATGCTAGC"),
+    ("J23102", b"TTGACAGCTAGCTCAGTCCTAGGTACTGTGCTAGC"),
+    ("J23104", b"TTGACAGCTAGCTCAGTCCTAGGTATTGTGCTAGC"),
+    ("J23106", b"TTTACGGCTAGCTCAGTCCTAGGTATAGTGCTAGC"),
+    ("J23119", b"TTGACAGCTAGCTCAGTCCTAGGTATAATGCTAGC"),
+];
+
+// ===== Ribosome Binding Sites ====================================================================
+
+// NOTE: The 3' end of the E. coli 16S rRNA (written 5' to 3'), which pairs with Shine–Dalgarno
+// sequences upstream of start codons
+pub const ANTI_SHINE_DALGARNO: &[u8] = b"ACCUCCUUA";
+
+// ===== Restriction Enzymes =======================================================================
+
+// NOTE: Each enzyme's recognition site (in IUPAC codes) and where it cuts the top and bottom
+// strands, both counted in bases from the start of the site on the top strand. Type IIS enzymes
+// (like BsaI) cut outside of their sites, so their cuts can be past the end of the site
+pub const RESTRICTION_ENZYMES: [(&str, &[u8], usize, usize); 27] = [
+    ("AarI", b"CACCTGC", 11, 15),
+    ("AgeI", b"ACCGGT", 1, 5),
+    ("AscI", b"GGCGCGCC", 2, 6),
+    ("AvaI", b"CYCGRG", 1, 5),
+    ("BamHI", b"GGATCC", 1, 5),
+    ("BbsI", b"GAAGAC", 8, 12),
+    ("BglII", b"AGATCT", 1, 5),
+    ("BsaI", b"GGTCTC", 7, 11),
+    ("BsmBI", b"CGTCTC", 7, 11),
+    ("EcoRI", b"GAATTC", 1, 5),
+    ("EcoRV", b"GATATC", 3, 3),
+    ("HindIII", b"AAGCTT", 1, 5),
+    ("KpnI", b"GGTACC", 5, 1),
+    ("NcoI", b"CCATGG", 1, 5),
+    ("NdeI", b"CATATG", 2, 4),
+    ("NheI", b"GCTAGC", 1, 5),
+    ("NotI", b"GCGGCCGC", 2, 6),
+    ("PacI", b"TTAATTAA", 5, 3),
+    ("PstI", b"CTGCAG", 5, 1),
+    ("SacI", b"GAGCTC", 5, 1),
+    ("SalI", b"GTCGAC", 1, 5),
+    ("SapI", b"GCTCTTC", 8, 11),
+    ("SmaI", b"CCCGGG", 3, 3),
+    ("SpeI", b"ACTAGT", 1, 5),
+    ("XbaI", b"TCTAGA", 1, 5),
+    ("XhoI", b"CTCGAG", 1, 5),
+    ("XmaI", b"CCCGGG", 1, 5),
+];
+
+// ===== Amino Acid Properties =====================================================================
+
+// NOTE: The hydropathy index of each amino acid, from Kyte & Doolittle (1982)
+pub const KYTE_DOOLITTLE: Map<u8, f64> = phf_map! {
+    b'A' => 1.8,
+    b'R' => -4.5,
+    b'N' => -3.5,
+    b'D' => -3.5,
+    b'C' => 2.5,
+    b'Q' => -3.5,
+    b'E' => -3.5,
+    b'G' => -0.4,
+    b'H' => -3.2,
+    b'I' => 4.5,
+    b'L' => 3.8,
+    b'K' => -3.9,
+    b'M' => 1.9,
+    b'F' => 2.8,
+    b'P' => -1.6,
+    b'S' => -0.8,
+    b'T' => -0.7,
+    b'W' => -0.9,
+    b'Y' => -1.3,
+    b'V' => 4.2,
+};
+
+// NOTE: Ionisable group pKa values, from EMBOSS. Terminal groups are counted once for every chain,
+// and side chains once for every residue that carries them
+pub const PKA_N_TERMINUS: f64 = 8.6;
+pub const PKA_C_TERMINUS: f64 = 3.6;
+
+pub const PKA_POSITIVE: Map<u8, f64> = phf_map! {
+    b'H' => 6.5,
+    b'K' => 10.8,
+    b'R' => 12.5,
+};
+
+pub const PKA_NEGATIVE: Map<u8, f64> = phf_map! {
+    b'C' => 8.5,
+    b'D' => 3.9,
+    b'E' => 4.1,
+    b'Y' => 10.1,
+};
+
+// NOTE: The (helix, sheet, turn) conformational parameters of each amino acid, from Chou & Fasman
+// (1978)
+pub const CHOU_FASMAN: Map<u8, (f64, f64, f64)> = phf_map! {
+    b'A' => (1.42, 0.83, 0.66),
+    b'R' => (0.98, 0.93, 0.95),
+    b'N' => (0.67, 0.89, 1.56),
+    b'D' => (1.01, 0.54, 1.46),
+    b'C' => (0.70, 1.19, 1.19),
+    b'Q' => (1.11, 1.10, 0.98),
+    b'E' => (1.51, 0.37, 0.74),
+    b'G' => (0.57, 0.75, 1.56),
+    b'H' => (1.00, 0.87, 0.95),
+    b'I' => (1.08, 1.60, 0.47),
+    b'L' => (1.21, 1.30, 0.59),
+    b'K' => (1.14, 0.74, 1.01),
+    b'M' => (1.45, 1.05, 0.60),
+    b'F' => (1.13, 1.38, 0.60),
+    b'P' => (0.57, 0.55, 1.52),
+    b'S' => (0.77, 0.75, 1.43),
+    b'T' => (0.83, 1.19, 0.96),
+    b'W' => (1.08, 1.37, 0.96),
+    b'Y' => (0.69, 1.47, 1.14),
+    b'V' => (1.06, 1.70, 0.50),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,4 +476,26 @@ mod tests {
         values.dedup();
         assert_eq!(values.len(), 24);
     }
+
+    #[test]
+    fn host_codon_usage_tables() {
+        // NOTE: The per-thousand frequencies of UAA, UAG, and UGA in each table
+        let tables = [
+            (E_COLI_K12_CODON_USAGE, [2.0, 0.2, 1.0]),
+            (S_CEREVISIAE_CODON_USAGE, [1.1, 0.5, 0.7]),
+            (H_SAPIENS_CODON_USAGE, [1.0, 0.8, 1.6]),
+        ];
+        for (table, stops) in tables {
+            let total: f64 = table.counts.iter().sum();
+            assert!((total - 1000.0).abs() < 1.0);
+            assert!(table.counts.iter().all(|&c| c > 0.0));
+            for (stop, expected) in ["UAA", "UAG", "UGA"].into_iter().zip(stops) {
+                assert!((table.per_thousand(stop) - expected).abs() < 0.01);
+            }
+        }
+        assert_eq!(E_COLI_K12_CODON_USAGE.per_thousand("CUG").round(), 53.0);
+        assert!(E_COLI_K12_CODON_USAGE.relative_adaptiveness("AGG") < 0.1);
+        assert_eq!(S_CEREVISIAE_CODON_USAGE.relative_adaptiveness("AGA"), 1.0);
+        assert_eq!(H_SAPIENS_CODON_USAGE.relative_adaptiveness("CUG"), 1.0);
+    }
 }
@@ -0,0 +1,485 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    region::Region,
+    seq::{Alphabet, Error, Seq},
+};
+
+// NOTE: Each edit replaces `delete` elements of the original sequence, starting at `pos`, with the
+// `insert`ed elements. Positions always refer to the original sequence, and edits never overlap
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Edit {
+    pub pos: usize,
+    pub delete: usize,
+    pub insert: String,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct SeqDiff {
+    pub old_len: usize,
+    pub new_len: usize,
+    pub edits: Vec<Edit>,
+}
+
+impl SeqDiff {
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Op {
+    Equal(u8),
+    Delete(u8),
+    Insert(u8),
+}
+
+// NOTE: This is Myers' O(ND) difference algorithm. Only the `2d + 1` diagonals reachable after `d`
+// edits are kept from each round, so the trace takes O(D²) rather than O(ND) memory
+fn myers(a: &[u8], b: &[u8]) -> Vec<Op> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+    let mut v = vec![0; 2 * max + 3];
+    let mut trace = Vec::new();
+
+    'search: for d in 0..=max as isize {
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let i = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = if d == 0 { 0 } else { at(prev_k) };
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            ops.push(Op::Equal(a[x as usize - 1]));
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == prev_x {
+                ops.push(Op::Insert(b[prev_y as usize]));
+            } else {
+                ops.push(Op::Delete(a[prev_x as usize]));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+
+    ops.reverse();
+
+    // NOTE: Myers' algorithm doesn't care where edits land in a run of repeated elements, so
+    // substitutions can come out as a deletion and insertion with a match between them. Sliding
+    // every edit as far left as it can go (past equal elements of the same value) canonicalises
+    // the script and groups neighbouring edits together
+    for i in 1..ops.len() {
+        let mut j = i;
+        while j > 0 {
+            match (ops[j - 1], ops[j]) {
+                (Op::Equal(e), Op::Insert(c) | Op::Delete(c)) if e == c => ops.swap(j - 1, j),
+                _ => break,
+            }
+            j -= 1;
+        }
+    }
+
+    ops
+}
+
+impl Seq {
+    pub fn diff(&self, other: &Self) -> SeqDiff {
+        let mut edits: Vec<Edit> = Vec::new();
+        let mut pos = 0;
+        let mut in_edit = false;
+        for op in myers(self.as_bytes(), other.as_bytes()) {
+            let equal = matches!(op, Op::Equal(_));
+            if !equal && !in_edit {
+                edits.push(Edit {
+                    pos,
+                    delete: 0,
+                    insert: String::new(),
+                });
+            }
+            in_edit = !equal;
+
+            let edit = edits.last_mut();
+            match (op, edit) {
+                (Op::Equal(_), _) => pos += 1,
+                (Op::Delete(_), Some(edit)) => {
+                    edit.delete += 1;
+                    pos += 1;
+                }
+                (Op::Insert(b), Some(edit)) => edit.insert.push(b as char),
+                _ => unreachable!("An edit should have been started"),
+            }
+        }
+
+        SeqDiff {
+            old_len: self.len(),
+            new_len: other.len(),
+            edits,
+        }
+    }
+
+    pub fn apply_patch(&self, diff: &SeqDiff) -> Result<Self, Error> {
+        if diff.old_len != self.len() {
+            return Err(Error::PatchLength(diff.old_len, self.len()));
+        }
+
+        let bytes = self.as_bytes();
+        // NOTE: `new_len` is only trusted once it has been checked against the patched sequence
+        let inserted: usize = diff.edits.iter().map(|e| e.insert.len()).sum();
+        let mut patched = Vec::with_capacity(diff.new_len.min(bytes.len() + inserted));
+        let mut pos = 0;
+        for edit in &diff.edits {
+            let end = edit
+                .pos
+                .checked_add(edit.delete)
+                .filter(|&end| edit.pos >= pos && end <= bytes.len())
+                .ok_or(Error::InvalidEdit(edit.pos))?;
+            patched.extend_from_slice(&bytes[pos..edit.pos]);
+            patched.extend_from_slice(edit.insert.as_bytes());
+            pos = end;
+        }
+        patched.extend_from_slice(&bytes[pos..]);
+        if patched.len() != diff.new_len {
+            return Err(Error::PatchedLength(diff.new_len, patched.len()));
+        }
+
+        // NOTE: Insertions can require a wider alphabet than the original sequence had (though never
+        // a narrower one than it already uses)
+        Self::from_vec_with_kind(patched, [self.kind()], self.alphabet().max(Alphabet::Iupac))
+    }
+}
+
+// ===== Coordinate Remapping ======================================================================
+
+// NOTE: Translates positions in the original sequence of a diff into positions in the edited one,
+// so that regions (like features) can follow the sequence through edits
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CoordinateMap {
+    old_len: usize,
+    edits: Vec<(usize, usize, usize)>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Boundary {
+    Start,
+    End,
+}
+
+impl SeqDiff {
+    pub fn coordinate_map(&self) -> CoordinateMap {
+        CoordinateMap {
+            old_len: self.old_len,
+            edits: self
+                .edits
+                .iter()
+                .map(|e| (e.pos, e.delete, e.insert.len()))
+                .collect(),
+        }
+    }
+}
+
+impl CoordinateMap {
+    // NOTE: Returns `None` for positions that were deleted (or replaced) by an edit
+    pub fn map_position(&self, pos: usize) -> Option<usize> {
+        if pos >= self.old_len {
+            return None;
+        }
+        let mut new_pos = pos;
+        for &(start, delete, insert) in &self.edits {
+            if start + delete <= pos {
+                new_pos = new_pos + insert - delete;
+            } else if start <= pos {
+                return None;
+            } else {
+                break;
+            }
+        }
+        Some(new_pos)
+    }
+
+    // NOTE: Regions shrink when edits delete their ends, grow when edits insert inside them, and
+    // are `None` once every element has been deleted. Insertions exactly at either end of a region
+    // are kept outside of it
+    pub fn map_region(&self, region: &Region) -> Result<Option<Region>, Error> {
+        if region.end() > self.old_len {
+            return Err(Error::RegionBounds(*region, self.old_len));
+        }
+        let start = self.map_boundary(region.start(), Boundary::Start);
+        let end = self.map_boundary(region.end(), Boundary::End).max(start);
+        if start == end && !region.is_empty() {
+            return Ok(None);
+        }
+        Region::new(start..end, region.strand()).map(Some)
+    }
+
+    fn map_boundary(&self, boundary: usize, side: Boundary) -> usize {
+        let mut new_boundary = boundary;
+        for &(start, delete, insert) in &self.edits {
+            let end = start + delete;
+            if end < boundary || (end == boundary && (delete > 0 || side == Boundary::Start)) {
+                new_boundary = new_boundary + insert - delete;
+            } else if start < boundary {
+                // NOTE: The boundary falls inside of a replaced stretch, so it's moved to whichever
+                // end of the replacement keeps the replacement outside of the region
+                let replaced = new_boundary - (boundary - start);
+                return match side {
+                    Boundary::Start => replaced + insert,
+                    Boundary::End => replaced,
+                };
+            } else {
+                break;
+            }
+        }
+        new_boundary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Strand;
+
+    #[test]
+    fn diff_identical() -> Result<(), Error> {
+        let dna = Seq::dna("ACGTACGT")?;
+        let diff = dna.diff(&dna);
+        assert!(diff.is_empty());
+        assert_eq!(dna.apply_patch(&diff)?, dna);
+        Ok(())
+    }
+
+    #[test]
+    fn diff_substitution() -> Result<(), Error> {
+        let old = Seq::dna("ACGTACGT")?;
+        let new = Seq::dna("ACGAACGT")?;
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.edits,
+            [Edit {
+                pos: 3,
+                delete: 1,
+                insert: "A".to_string()
+            }]
+        );
+        assert_eq!(old.apply_patch(&diff)?, new);
+        Ok(())
+    }
+
+    #[test]
+    fn diff_indels() -> Result<(), Error> {
+        let old = Seq::dna("AAAACCCCGGGG")?;
+        let new = Seq::dna("TAAAACCGGGGTT")?;
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.edits,
+            [
+                Edit {
+                    pos: 0,
+                    delete: 0,
+                    insert: "T".to_string()
+                },
+                Edit {
+                    pos: 4,
+                    delete: 2,
+                    insert: String::new()
+                },
+                Edit {
+                    pos: 12,
+                    delete: 0,
+                    insert: "TT".to_string()
+                }
+            ]
+        );
+        assert_eq!(old.apply_patch(&diff)?, new);
+        Ok(())
+    }
+
+    #[test]
+    fn diff_random_round_trips() -> Result<(), Error> {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut random_seq = |len| {
+            let bytes: Vec<_> = (0..len).map(|_| b"ACGT"[rng.gen_range(0..4)]).collect();
+            Seq::dna(bytes)
+        };
+        for len in 0..50 {
+            let (old, new) = (random_seq(len)?, random_seq(50 - len)?);
+            let diff = old.diff(&new);
+            assert_eq!(old.apply_patch(&diff)?, new);
+            let edited: usize = diff.edits.iter().map(|e| e.delete + e.insert.len()).sum();
+            assert!(edited <= old.len() + new.len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn diff_to_and_from_empty() -> Result<(), Error> {
+        let empty = Seq::dna("")?;
+        let dna = Seq::dna("ACGT")?;
+        assert_eq!(empty.apply_patch(&empty.diff(&dna))?, dna);
+        assert_eq!(dna.apply_patch(&dna.diff(&empty))?, empty);
+        assert!(empty.diff(&empty).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn patch_widens_alphabet() -> Result<(), Error> {
+        let old = Seq::dna("ACGTACGT")?;
+        let new = Seq::dna_n("ACGTNNACGT")?;
+        let patched = old.apply_patch(&old.diff(&new))?;
+        assert_eq!(patched, new);
+        assert_eq!(patched.alphabet(), Alphabet::N);
+
+        let extended = Seq::protein_extended("MAUO")?;
+        assert_eq!(extended.apply_patch(&extended.diff(&extended))?, extended);
+        let patched = extended.apply_patch(&extended.diff(&Seq::protein_extended("MAJUO")?))?;
+        assert_eq!(patched.alphabet(), Alphabet::Extended);
+        Ok(())
+    }
+
+    #[test]
+    fn patch_wrong_seq() -> Result<(), Error> {
+        let old = Seq::dna("ACGTACGT")?;
+        let diff = old.diff(&Seq::dna("ACGT")?);
+        assert_eq!(
+            Seq::dna("ACG")?.apply_patch(&diff),
+            Err(Error::PatchLength(8, 3))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn patch_invalid_edits() -> Result<(), Error> {
+        let dna = Seq::dna("ACGTACGT")?;
+        let edit = |pos, delete, insert: &str| Edit {
+            pos,
+            delete,
+            insert: insert.to_string(),
+        };
+        let diff = |new_len, edits| SeqDiff {
+            old_len: 8,
+            new_len,
+            edits,
+        };
+        assert_eq!(
+            dna.apply_patch(&diff(0, vec![edit(1, usize::MAX, "")])),
+            Err(Error::InvalidEdit(1))
+        );
+        assert_eq!(
+            dna.apply_patch(&diff(2, vec![edit(6, 4, "")])),
+            Err(Error::InvalidEdit(6))
+        );
+        assert_eq!(
+            dna.apply_patch(&diff(6, vec![edit(2, 2, ""), edit(3, 1, "G")])),
+            Err(Error::InvalidEdit(3))
+        );
+        assert_eq!(
+            dna.apply_patch(&diff(8, vec![edit(2, 1, "")])),
+            Err(Error::PatchedLength(8, 7))
+        );
+        assert_eq!(
+            dna.apply_patch(&diff(usize::MAX, Vec::new())),
+            Err(Error::PatchedLength(usize::MAX, 8))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diff_serializes() -> Result<(), Error> {
+        let diff = Seq::dna("ACGT")?.diff(&Seq::dna("AGGT")?);
+        assert_eq!(
+            serde_json::to_string(&diff).unwrap(),
+            r#"{"old_len":4,"new_len":4,"edits":[{"pos":1,"delete":1,"insert":"G"}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn map_positions() -> Result<(), Error> {
+        let old = Seq::dna("AAAACCCCGGGG")?;
+        let new = Seq::dna("TAAAACCGGGGTT")?;
+        let map = old.diff(&new).coordinate_map();
+        assert_eq!(map.map_position(0), Some(1));
+        assert_eq!(map.map_position(4), None);
+        assert_eq!(map.map_position(5), None);
+        assert_eq!(map.map_position(6), Some(5));
+        assert_eq!(map.map_position(11), Some(10));
+        assert_eq!(map.map_position(12), None);
+        for pos in 0..old.len() {
+            if let Some(new_pos) = map.map_position(pos) {
+                assert_eq!(old.as_bytes()[pos], new.as_bytes()[new_pos]);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn map_regions() -> Result<(), Error> {
+        let old = Seq::dna("AAAACCCCGGGG")?;
+        let new = Seq::dna("TAAAACCGGGGTT")?;
+        let map = old.diff(&new).coordinate_map();
+        let mapped = |range, strand| -> Result<Option<String>, Error> {
+            let region = map.map_region(&Region::new(range, strand)?)?;
+            Ok(region.map(|r| new.subseq(r.range()).to_string()))
+        };
+        assert_eq!(mapped(0..4, Strand::Forward)?.as_deref(), Some("AAAA"));
+        assert_eq!(mapped(4..8, Strand::Reverse)?.as_deref(), Some("CC"));
+        assert_eq!(mapped(3..6, Strand::Forward)?.as_deref(), Some("A"));
+        assert_eq!(mapped(5..8, Strand::Forward)?.as_deref(), Some("CC"));
+        assert_eq!(mapped(8..12, Strand::Forward)?.as_deref(), Some("GGGG"));
+        assert_eq!(mapped(4..6, Strand::Forward)?, None);
+
+        let region = Region::new(4..8, Strand::Reverse)?;
+        assert_eq!(map.map_region(&region)?.unwrap().strand(), Strand::Reverse);
+        let outside = Region::new(10..14, Strand::Forward)?;
+        assert_eq!(
+            map.map_region(&outside),
+            Err(Error::RegionBounds(outside, 12))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn map_regions_through_insertions() -> Result<(), Error> {
+        let old = Seq::dna("AAAATTTT")?;
+        let new = Seq::dna("AAAAGGGTTTT")?;
+        let map = old.diff(&new).coordinate_map();
+        let region = |range| Region::new(range, Strand::Forward);
+        assert_eq!(map.map_region(&region(0..8)?)?, Some(region(0..11)?));
+        assert_eq!(map.map_region(&region(0..4)?)?, Some(region(0..4)?));
+        assert_eq!(map.map_region(&region(4..8)?)?, Some(region(7..11)?));
+        Ok(())
+    }
+}
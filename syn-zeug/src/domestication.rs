@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    codon::{self, CodonUsage},
+    enzyme::Enzyme,
+    seq::{Error, Seq},
+    types::Topology,
+};
+
+// ===== Sequence Domestication ====================================================================
+
+// NOTE: Codons are reported in uppercase, using T or U to match the kind of the sequence
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct SilentChange {
+    pub position: usize,
+    pub from: [u8; 3],
+    pub to: [u8; 3],
+    pub site: String,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Domesticated {
+    pub seq: Seq,
+    pub changes: Vec<SilentChange>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Domesticator<'a> {
+    sites: Vec<Enzyme>,
+    usage: Option<&'a CodonUsage>,
+}
+
+impl<'a> Domesticator<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enzyme(mut self, enzyme: &Enzyme) -> Self {
+        self.sites.push(enzyme.clone());
+        self
+    }
+
+    // NOTE: Arbitrary motifs (like homopolymer runs or a cloning standard's forbidden sequences) can
+    // use IUPAC codes, and are reported by the motif itself. They are searched for on both strands,
+    // just like an enzyme's recognition site
+    pub fn motif(mut self, motif: &str) -> Result<Self, Error> {
+        let motif = motif.to_ascii_uppercase();
+        self.sites.push(Enzyme::new(&motif, &motif, 0, 0)?);
+        Ok(self)
+    }
+
+    // NOTE: With a codon usage table, the best-adapted of the equally good replacement codons is
+    // picked, otherwise the first in the standard codon table order is
+    pub fn usage(self, usage: &'a CodonUsage) -> Self {
+        Self {
+            usage: Some(usage),
+            ..self
+        }
+    }
+
+    // NOTE: Sites are removed one at a time from the 5' end. Each is broken by the single synonymous
+    // codon change (within the site) that leaves the fewest sites around it, so any sites a change
+    // would create count against it. Stop codons, ambiguous codons, and any trailing partial codon
+    // are never changed, and a site that no single change can remove is an error
+    pub fn domesticate(&self, cds: &Seq) -> Result<Domesticated, Error> {
+        let codons: Vec<_> = codon::codon_indices(cds)?.collect();
+        let kind = cds.kind();
+        let flank = self.sites.iter().map(|e| e.site().len()).max().unwrap_or(0);
+        let mut bytes = cds.as_bytes().to_vec();
+        let mut changes = Vec::new();
+
+        while let Some((position, enzyme)) = self.first_site(&bytes, cds)? {
+            let end = position + enzyme.site().len();
+            let mut best: Option<(isize, f64, usize, usize)> = None;
+            for &(n, i) in codons
+                .iter()
+                .filter(|&&(n, _)| n * 3 < end && n * 3 + 3 > position)
+            {
+                let current = codon::codon_index(&bytes[n * 3..n * 3 + 3]).unwrap_or(i);
+                if codon::residue(current) == b'*' {
+                    continue;
+                }
+                let window = (n * 3).saturating_sub(flank)..(n * 3 + 3 + flank).min(bytes.len());
+                let before = self.count_sites(&bytes[window.clone()], cds)?;
+                for j in codon::synonymous(current).filter(|&j| j != current) {
+                    let mut edited = bytes[window.clone()].to_vec();
+                    let offset = n * 3 - window.start;
+                    codon::replace_codon(&mut edited[offset..offset + 3], j, kind);
+                    let delta = self.count_sites(&edited, cds)? as isize - before as isize;
+                    let adaptiveness = self.usage.map_or(0.0, |u| u.adaptiveness(j));
+                    if best.is_none_or(|(d, a, ..)| delta < d || (delta == d && adaptiveness > a)) {
+                        best = Some((delta, adaptiveness, n, j));
+                    }
+                }
+            }
+
+            let Some((_, _, n, j)) = best.filter(|&(delta, ..)| delta < 0) else {
+                return Err(Error::Domestication(enzyme.name().to_string(), position));
+            };
+            let codon = &mut bytes[n * 3..n * 3 + 3];
+            let from = uppercase_codon(codon);
+            codon::replace_codon(codon, j, kind);
+            changes.push(SilentChange {
+                position: n * 3,
+                from,
+                to: uppercase_codon(codon),
+                site: enzyme.name().to_string(),
+            });
+        }
+
+        let seq = Seq::from_vec_with_kind(bytes, [kind], cds.alphabet())?;
+        Ok(Domesticated { seq, changes })
+    }
+
+    fn first_site(&self, bytes: &[u8], cds: &Seq) -> Result<Option<(usize, &Enzyme)>, Error> {
+        let seq = Seq::from_vec_with_kind(bytes.to_vec(), [cds.kind()], cds.alphabet())?;
+        let mut first: Option<(usize, &Enzyme)> = None;
+        for enzyme in &self.sites {
+            let sites = enzyme.find_sites(&seq, Topology::Linear)?;
+            if let Some(site) = sites.first() {
+                if first.is_none_or(|(p, _)| site.position() < p) {
+                    first = Some((site.position(), enzyme));
+                }
+            }
+        }
+        Ok(first)
+    }
+
+    fn count_sites(&self, bytes: &[u8], cds: &Seq) -> Result<usize, Error> {
+        let seq = Seq::from_vec_with_kind(bytes.to_vec(), [cds.kind()], cds.alphabet())?;
+        self.sites.iter().try_fold(0, |count, enzyme| {
+            Ok(count + enzyme.find_sites(&seq, Topology::Linear)?.len())
+        })
+    }
+}
+
+fn uppercase_codon(codon: &[u8]) -> [u8; 3] {
+    [codon[0], codon[1], codon[2]].map(|b| b.to_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Kind;
+
+    #[test]
+    fn remove_type_iis_sites() -> Result<(), Error> {
+        let bsa_i = Enzyme::builtin("BsaI").unwrap();
+        let cds = Seq::dna("ATGGGTCTCAAATAA")?;
+        let domesticated = Domesticator::new().enzyme(&bsa_i).domesticate(&cds)?;
+        assert_eq!(domesticated.seq, Seq::dna("ATGGGCCTCAAATAA")?);
+        assert_eq!(
+            domesticated.changes,
+            [SilentChange {
+                position: 3,
+                from: *b"GGT",
+                to: *b"GGC",
+                site: "BsaI".to_string()
+            }]
+        );
+        assert_eq!(
+            domesticated.seq.convert(Kind::Protein)?,
+            cds.convert(Kind::Protein)?
+        );
+
+        // NOTE: A table where CTG is the best leucine codon, but GGT is the best glycine codon
+        let usage = CodonUsage::from_seqs([Seq::dna("CTGGGTGGTGGTGGAGGCGGG")?])?;
+        let domesticated = Domesticator::new()
+            .enzyme(&bsa_i)
+            .usage(&usage)
+            .domesticate(&cds)?;
+        assert_eq!(domesticated.seq, Seq::dna("ATGGGTCTGAAATAA")?);
+        Ok(())
+    }
+
+    #[test]
+    fn remove_reverse_sites_and_motifs() -> Result<(), Error> {
+        let bsm_bi = Enzyme::builtin("BsmBI").unwrap();
+        let cds = Seq::dna("ATGGAGACGTAA")?;
+        let domesticated = Domesticator::new().enzyme(&bsm_bi).domesticate(&cds)?;
+        assert_eq!(domesticated.seq, Seq::dna("ATGGAAACGTAA")?);
+
+        let cds = Seq::rna("augaaaaaauaa")?;
+        let domesticated = Domesticator::new().motif("aaaaaa")?.domesticate(&cds)?;
+        assert_eq!(domesticated.seq, Seq::rna("augaagaaauaa")?);
+        assert_eq!(domesticated.changes[0].site, "AAAAAA");
+        assert_eq!(
+            (domesticated.changes[0].from, domesticated.changes[0].to),
+            (*b"AAA", *b"AAG")
+        );
+        let cds = Seq::rna("AUGGGUCUCAAAUAA")?;
+        let bsa_i = Enzyme::builtin("BsaI").unwrap();
+        let domesticated = Domesticator::new().enzyme(&bsa_i).domesticate(&cds)?;
+        assert_eq!(domesticated.changes[0].from, *b"GGU");
+        Ok(())
+    }
+
+    #[test]
+    fn remove_several_sites() -> Result<(), Error> {
+        let domesticator = Domesticator::new()
+            .enzyme(&Enzyme::builtin("EcoRI").unwrap())
+            .enzyme(&Enzyme::builtin("BsaI").unwrap());
+        let cds = Seq::dna("ATGGAATTCGGTCTCTAA")?;
+        let domesticated = domesticator.domesticate(&cds)?;
+        assert_eq!(domesticated.seq, Seq::dna("ATGGAGTTCGGCCTCTAA")?);
+        let sites: Vec<_> = domesticated
+            .changes
+            .iter()
+            .map(|c| c.site.as_str())
+            .collect();
+        assert_eq!(sites, ["EcoRI", "BsaI"]);
+
+        let clean = Seq::dna("ATGAAATAA")?;
+        assert!(domesticator.domesticate(&clean)?.changes.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn unremovable_sites() -> Result<(), Error> {
+        let domesticator = Domesticator::new().motif("ATGTGG")?;
+        assert_eq!(
+            domesticator.domesticate(&Seq::dna("ATGTGGTAA")?),
+            Err(Error::Domestication("ATGTGG".to_string(), 0))
+        );
+        assert_eq!(
+            domesticator.domesticate(&Seq::protein("MAMAPRTEIN")?),
+            Err(Error::CodonCount(Kind::Protein))
+        );
+        assert!(Domesticator::new().motif("ATGXGG").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(feature = "ndarray")]
+use ndarray::{Array1, Array2};
+
+use crate::{
+    pwm::{symbol_index, symbols},
+    seq::{Kind, Seq},
+};
+
+// NOTE: Channels are the unambiguous symbols of each kind, in alphabetical order:
+//   - DNA: A, C, G, T
+//   - RNA: A, C, G, U
+//   - Protein: A, C, D, E, F, G, H, I, K, L, M, N, P, Q, R, S, T, V, W, Y
+pub fn channels(kind: Kind) -> &'static [u8] {
+    symbols(kind)
+}
+
+impl Seq {
+    // NOTE: The encoding is flattened row-major, so the channels of element `i` are found at
+    // `i * channels(kind).len()..(i + 1) * channels(kind).len()`. Ambiguity codes are all zeros
+    pub fn one_hot(&self) -> Vec<f32> {
+        let width = channels(self.kind()).len();
+        let index = symbol_index(self.kind());
+        let mut encoding = vec![0.0; self.len() * width];
+        for (i, &b) in self.as_bytes().iter().enumerate() {
+            if let Some(c) = index[b] {
+                encoding[i * width + c] = 1.0;
+            }
+        }
+        encoding
+    }
+
+    // NOTE: Each element is replaced by its channel index, with ambiguity codes taking the index
+    // one past the last channel (4 for nucleic acids, 20 for proteins)
+    pub fn to_indices(&self) -> Vec<u8> {
+        let unknown = channels(self.kind()).len() as u8;
+        let index = symbol_index(self.kind());
+        self.as_bytes()
+            .iter()
+            .map(|&b| index[b].map_or(unknown, |c| c as u8))
+            .collect()
+    }
+
+    #[cfg(feature = "ndarray")]
+    pub fn one_hot_array(&self) -> Array2<f32> {
+        let shape = (self.len(), channels(self.kind()).len());
+        Array2::from_shape_vec(shape, self.one_hot())
+            .expect("The encoding should have one row per element")
+    }
+
+    #[cfg(feature = "ndarray")]
+    pub fn to_indices_array(&self) -> Array1<u8> {
+        Array1::from_vec(self.to_indices())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Error;
+
+    #[test]
+    fn one_hot_dna() -> Result<(), Error> {
+        let encoding = Seq::dna_n("AcGTN")?.one_hot();
+        assert_eq!(
+            encoding,
+            [
+                1.0, 0.0, 0.0, 0.0, //
+                0.0, 1.0, 0.0, 0.0, //
+                0.0, 0.0, 1.0, 0.0, //
+                0.0, 0.0, 0.0, 1.0, //
+                0.0, 0.0, 0.0, 0.0,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn one_hot_protein() -> Result<(), Error> {
+        let encoding = Seq::protein("MY")?.one_hot();
+        assert_eq!(encoding.len(), 40);
+        assert_eq!(encoding[10], 1.0);
+        assert_eq!(encoding[39], 1.0);
+        assert_eq!(encoding.iter().sum::<f32>(), 2.0);
+        Ok(())
+    }
+
+    #[test]
+    fn integer_indices() -> Result<(), Error> {
+        assert_eq!(Seq::rna_iupac("ACGUn")?.to_indices(), [0, 1, 2, 3, 4]);
+        assert_eq!(Seq::protein_iupac("ACYX")?.to_indices(), [0, 1, 19, 20]);
+        assert_eq!(channels(Kind::Rna), b"ACGU");
+        Ok(())
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn ndarray_encodings() -> Result<(), Error> {
+        let dna = Seq::dna("ACGTA")?;
+        let one_hot = dna.one_hot_array();
+        assert_eq!(one_hot.shape(), [5, 4]);
+        assert_eq!(one_hot[[3, 3]], 1.0);
+        assert_eq!(dna.to_indices_array().to_vec(), [0, 1, 2, 3, 0]);
+        Ok(())
+    }
+}
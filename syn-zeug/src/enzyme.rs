@@ -0,0 +1,451 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::{IUPAC_DNA, RESTRICTION_ENZYMES},
+    region::Region,
+    seq::{Error, Kind, Seq},
+    types::{Case, Strand, Topology},
+};
+
+// ===== Restriction Enzymes =======================================================================
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Enzyme {
+    name: String,
+    site: Vec<u8>,
+    cut: usize,
+    complement_cut: usize,
+}
+
+// NOTE: Like features, sites and fragments are made of regions, with those spanning the origin of
+// a circular sequence split into two parts (stored in the order they are read). Cuts are positions
+// between bases on the top strand (so a cut at `i` falls between bases `i - 1` and `i`), and are
+// missing when they fall off the end of a linear sequence
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Site {
+    pub parts: Vec<Region>,
+    pub top_cut: Option<usize>,
+    pub bottom_cut: Option<usize>,
+}
+
+impl Site {
+    // NOTE: The start of the recognition site on the top strand
+    pub fn position(&self) -> usize {
+        match self.strand() {
+            Strand::Forward => self.parts[0].start(),
+            Strand::Reverse => self.parts[self.parts.len() - 1].start(),
+        }
+    }
+
+    pub fn strand(&self) -> Strand {
+        self.parts[0].strand()
+    }
+}
+
+// NOTE: Fragments start at a top-strand cut (or the start of a linear sequence), and are always
+// read along the top strand. Only an uncut circular sequence gives a circular fragment
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Fragment {
+    pub parts: Vec<Region>,
+    pub circular: bool,
+}
+
+impl Fragment {
+    pub fn start(&self) -> usize {
+        self.parts[0].start()
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.iter().map(Region::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// NOTE: The parts of `start..end` on a sequence of length `n`, where an `end` past `n` wraps around
+// the origin of a circular sequence
+fn wrapped_parts(start: usize, end: usize, n: usize, strand: Strand) -> Result<Vec<Region>, Error> {
+    let mut parts = if end <= n {
+        vec![Region::new(start..end, strand)?]
+    } else {
+        vec![
+            Region::new(start..n, strand)?,
+            Region::new(0..end - n, strand)?,
+        ]
+    };
+    if strand == Strand::Reverse {
+        parts.reverse();
+    }
+    Ok(parts)
+}
+
+impl Enzyme {
+    // ===== Constructors ==========================================================================
+
+    pub fn new(
+        name: impl Into<String>,
+        site: impl AsRef<[u8]>,
+        cut: usize,
+        complement_cut: usize,
+    ) -> Result<Self, Error> {
+        let site = Seq::dna_iupac(site)?.normalize_case(Case::Upper);
+        Ok(Self {
+            name: name.into(),
+            site: site.as_bytes().to_vec(),
+            cut,
+            complement_cut,
+        })
+    }
+
+    // NOTE: Looks up one of the built-in enzymes by name, ignoring case
+    pub fn builtin(name: &str) -> Option<Self> {
+        RESTRICTION_ENZYMES
+            .iter()
+            .find(|(n, ..)| n.eq_ignore_ascii_case(name))
+            .map(|&(name, site, cut, complement_cut)| Self {
+                name: name.to_string(),
+                site: site.to_vec(),
+                cut,
+                complement_cut,
+            })
+    }
+
+    pub fn builtins() -> Vec<Self> {
+        RESTRICTION_ENZYMES
+            .iter()
+            .filter_map(|(name, ..)| Self::builtin(name))
+            .collect()
+    }
+
+    // ===== Getters ===============================================================================
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn site(&self) -> &[u8] {
+        &self.site
+    }
+
+    pub fn cut(&self) -> usize {
+        self.cut
+    }
+
+    pub fn complement_cut(&self) -> usize {
+        self.complement_cut
+    }
+
+    pub fn is_palindromic(&self) -> bool {
+        self.site == self.reverse_site()
+    }
+
+    fn reverse_site(&self) -> Vec<u8> {
+        Seq::dna_iupac(&self.site)
+            .and_then(|s| s.reverse_complement())
+            .expect("Enzyme sites are always valid IUPAC DNA")
+            .as_bytes()
+            .to_vec()
+    }
+
+    // ===== Tools =================================================================================
+
+    // NOTE: Ambiguity codes in the site match any of the bases they stand for, but ambiguity codes
+    // in the sequence never match. Palindromic sites are only reported once, on the forward strand,
+    // and sites in circular sequences can span the origin
+    pub fn find_sites(&self, seq: &Seq, topology: Topology) -> Result<Vec<Site>, Error> {
+        if !seq.kind().is_nucleic_acid() {
+            return Err(Error::EnzymeSearch(seq.kind()));
+        }
+        let seq = seq.convert(Kind::Dna)?.normalize_case(Case::Upper);
+        let (bytes, n, len) = (seq.as_bytes(), seq.len(), self.site.len());
+        if len == 0 || n < len {
+            return Ok(Vec::new());
+        }
+
+        let starts = match topology {
+            Topology::Linear => n - len + 1,
+            Topology::Circular => n,
+        };
+        let cut = |offset: usize, p: usize| {
+            let cut = p + offset;
+            match topology {
+                Topology::Linear => (1..n).contains(&cut).then_some(cut),
+                Topology::Circular => Some(cut % n),
+            }
+        };
+        // NOTE: Reverse-strand cuts are measured back from the end of the site, so they can fall
+        // before the start of a circular sequence and wrap around to its end
+        let reverse_cut = |offset: usize, p: usize| match topology {
+            Topology::Linear => (p + len).checked_sub(offset).and_then(|c| cut(0, c)),
+            Topology::Circular => Some((p + len + n - offset % n) % n),
+        };
+        let matches = |pattern: &[u8], p: usize| {
+            pattern.iter().enumerate().all(|(i, &s)| {
+                let b = bytes[(p + i) % n];
+                s == b || IUPAC_DNA.get(&s).is_some_and(|bases| bases.contains(&b))
+            })
+        };
+
+        let reverse = (!self.is_palindromic()).then(|| self.reverse_site());
+        let mut sites = Vec::new();
+        for p in 0..starts {
+            if matches(&self.site, p) {
+                sites.push(Site {
+                    parts: wrapped_parts(p, p + len, n, Strand::Forward)?,
+                    top_cut: cut(self.cut, p),
+                    bottom_cut: cut(self.complement_cut, p),
+                });
+            }
+            if reverse.as_ref().is_some_and(|r| matches(r, p)) {
+                sites.push(Site {
+                    parts: wrapped_parts(p, p + len, n, Strand::Reverse)?,
+                    top_cut: reverse_cut(self.complement_cut, p),
+                    bottom_cut: reverse_cut(self.cut, p),
+                });
+            }
+        }
+        Ok(sites)
+    }
+}
+
+// ===== Digestion =================================================================================
+
+// NOTE: Fragment sizes only count the top strand, so sticky-end overhangs are ignored. Enzymes
+// cutting at the same place only cut once
+pub fn digest(seq: &Seq, topology: Topology, enzymes: &[Enzyme]) -> Result<Vec<Fragment>, Error> {
+    if !seq.kind().is_nucleic_acid() {
+        return Err(Error::EnzymeSearch(seq.kind()));
+    }
+    let mut cuts = Vec::new();
+    for enzyme in enzymes {
+        cuts.extend(
+            enzyme
+                .find_sites(seq, topology)?
+                .iter()
+                .filter_map(|s| s.top_cut),
+        );
+    }
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let n = seq.len();
+    let fragment = |start: usize, end: usize| -> Result<Fragment, Error> {
+        Ok(Fragment {
+            parts: wrapped_parts(start, end, n, Strand::Forward)?,
+            circular: false,
+        })
+    };
+    match (topology, cuts.first(), cuts.last()) {
+        (Topology::Circular, Some(&first), Some(&last)) => {
+            let mut fragments = cuts
+                .windows(2)
+                .map(|w| fragment(w[0], w[1]))
+                .collect::<Result<Vec<_>, _>>()?;
+            fragments.push(fragment(last, n + first)?);
+            Ok(fragments)
+        }
+        (Topology::Circular, ..) => Ok(vec![Fragment {
+            parts: vec![Region::new(0..n, Strand::Forward)?],
+            circular: true,
+        }]),
+        (Topology::Linear, ..) => [0]
+            .iter()
+            .chain(&cuts)
+            .zip(cuts.iter().chain([&n]))
+            .map(|(&start, &end)| fragment(start, end))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_enzymes() -> Result<(), Error> {
+        let eco_ri = Enzyme::builtin("ecori").unwrap();
+        assert_eq!(eco_ri.name(), "EcoRI");
+        assert_eq!(eco_ri.site(), b"GAATTC");
+        assert!(eco_ri.is_palindromic());
+        assert!(!Enzyme::builtin("BsaI").unwrap().is_palindromic());
+        assert_eq!(Enzyme::builtin("NotAnEnzyme"), None);
+        assert_eq!(Enzyme::builtins().len(), RESTRICTION_ENZYMES.len());
+        assert_eq!(Enzyme::new("Custom", "gaattc", 1, 5)?.site(), b"GAATTC");
+        assert!(Enzyme::new("Invalid", "GAAXTC", 1, 5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn find_palindromic_sites() -> Result<(), Error> {
+        let eco_ri = Enzyme::builtin("EcoRI").unwrap();
+        let dna = Seq::dna("AAGAATTCAAgaattc")?;
+        let sites = eco_ri.find_sites(&dna, Topology::Linear)?;
+        assert_eq!(
+            sites,
+            [
+                Site {
+                    parts: vec![Region::new(2..8, Strand::Forward)?],
+                    top_cut: Some(3),
+                    bottom_cut: Some(7)
+                },
+                Site {
+                    parts: vec![Region::new(10..16, Strand::Forward)?],
+                    top_cut: Some(11),
+                    bottom_cut: Some(15)
+                }
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_type_iis_sites() -> Result<(), Error> {
+        let bsa_i = Enzyme::builtin("BsaI").unwrap();
+        let dna = Seq::dna("GGTCTCAAAAAAAAAAAAAGAGACC")?;
+        let sites = bsa_i.find_sites(&dna, Topology::Linear)?;
+        assert_eq!(sites.len(), 2);
+        assert_eq!(sites[0].strand(), Strand::Forward);
+        assert_eq!((sites[0].top_cut, sites[0].bottom_cut), (Some(7), Some(11)));
+        assert_eq!(sites[1].position(), 19);
+        assert_eq!(sites[1].strand(), Strand::Reverse);
+        assert_eq!(
+            (sites[1].top_cut, sites[1].bottom_cut),
+            (Some(14), Some(18))
+        );
+
+        // NOTE: A site whose cuts fall off the end of a linear sequence
+        let sites = bsa_i.find_sites(&Seq::dna("AAGGTCTCA")?, Topology::Linear)?;
+        assert_eq!((sites[0].top_cut, sites[0].bottom_cut), (None, None));
+        Ok(())
+    }
+
+    #[test]
+    fn find_circular_sites() -> Result<(), Error> {
+        let eco_ri = Enzyme::builtin("EcoRI").unwrap();
+        let dna = Seq::dna("TTCAAAAAGAA")?;
+        assert!(eco_ri.find_sites(&dna, Topology::Linear)?.is_empty());
+        let sites = eco_ri.find_sites(&dna, Topology::Circular)?;
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].position(), 8);
+        assert_eq!(
+            sites[0].parts,
+            [
+                Region::new(8..11, Strand::Forward)?,
+                Region::new(0..3, Strand::Forward)?
+            ]
+        );
+        assert_eq!(sites[0].top_cut, Some(9));
+        assert_eq!(sites[0].bottom_cut, Some(2));
+
+        // NOTE: A reverse BsaI site just after the origin, cutting back across it
+        let bsa_i = Enzyme::builtin("BsaI").unwrap();
+        let dna = Seq::dna(format!("AAGAGACC{}", "A".repeat(32)))?;
+        let sites = bsa_i.find_sites(&dna, Topology::Circular)?;
+        assert_eq!(
+            sites,
+            [Site {
+                parts: vec![Region::new(2..8, Strand::Reverse)?],
+                top_cut: Some(37),
+                bottom_cut: Some(1)
+            }]
+        );
+        let fragments = digest(&dna, Topology::Circular, std::slice::from_ref(&bsa_i))?;
+        assert_eq!(
+            fragments,
+            [Fragment {
+                parts: vec![
+                    Region::new(37..40, Strand::Forward)?,
+                    Region::new(0..37, Strand::Forward)?
+                ],
+                circular: false
+            }]
+        );
+
+        // NOTE: A reverse site across the origin, where the part after the origin is read first
+        let dna = Seq::dna(format!("ACC{}GAG", "A".repeat(30)))?;
+        let sites = bsa_i.find_sites(&dna, Topology::Circular)?;
+        assert_eq!(sites[0].position(), 33);
+        assert_eq!(
+            sites[0].parts,
+            [
+                Region::new(0..3, Strand::Reverse)?,
+                Region::new(33..36, Strand::Reverse)?
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_ambiguous_sites() -> Result<(), Error> {
+        let ava_i = Enzyme::builtin("AvaI").unwrap();
+        let dna = Seq::dna_iupac("CTCGAGACCCGGGNCCGNG")?;
+        let sites = ava_i.find_sites(&dna, Topology::Linear)?;
+        let positions: Vec<_> = sites.iter().map(Site::position).collect();
+        assert_eq!(positions, [0, 7]);
+        let rna = Seq::rna("CUCGAG")?;
+        assert_eq!(ava_i.find_sites(&rna, Topology::Linear)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn digest_linear() -> Result<(), Error> {
+        let enzymes = [
+            Enzyme::builtin("EcoRI").unwrap(),
+            Enzyme::builtin("BamHI").unwrap(),
+        ];
+        let dna = Seq::dna("AAGAATTCAAGGATCCAAgaattc")?;
+        let sizes: Vec<_> = digest(&dna, Topology::Linear, &enzymes)?
+            .iter()
+            .map(|f| (f.start(), f.len()))
+            .collect();
+        assert_eq!(sizes, [(0, 3), (3, 8), (11, 8), (19, 5)]);
+        let uncut = digest(&Seq::dna("AAAA")?, Topology::Linear, &enzymes)?;
+        assert_eq!(
+            uncut,
+            [Fragment {
+                parts: vec![Region::new(0..4, Strand::Forward)?],
+                circular: false
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn digest_circular() -> Result<(), Error> {
+        let eco_ri = Enzyme::builtin("EcoRI").unwrap();
+        let dna = Seq::dna("TTCAAAAAGAA")?;
+        let fragments = digest(&dna, Topology::Circular, std::slice::from_ref(&eco_ri))?;
+        assert_eq!(
+            fragments,
+            [Fragment {
+                parts: vec![
+                    Region::new(9..11, Strand::Forward)?,
+                    Region::new(0..9, Strand::Forward)?
+                ],
+                circular: false
+            }]
+        );
+
+        let dna = Seq::dna("GAATTCAAAAGAATTCAA")?;
+        let fragments = digest(&dna, Topology::Circular, std::slice::from_ref(&eco_ri))?;
+        let sizes: Vec<_> = fragments.iter().map(|f| (f.start(), f.len())).collect();
+        assert_eq!(sizes, [(1, 10), (11, 8)]);
+
+        let uncut = digest(&Seq::dna("AAAA")?, Topology::Circular, &[eco_ri])?;
+        assert!(uncut[0].circular);
+        assert_eq!(uncut[0].parts, [Region::new(0..4, Strand::Forward)?]);
+        Ok(())
+    }
+
+    #[test]
+    fn find_sites_protein() -> Result<(), Error> {
+        let eco_ri = Enzyme::builtin("EcoRI").unwrap();
+        assert_eq!(
+            eco_ri.find_sites(&Seq::protein("MAMAPRTEIN")?, Topology::Linear),
+            Err(Error::EnzymeSearch(Kind::Protein))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,435 @@
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    Rng,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pwm,
+    seq::{Error, Kind, Seq},
+    types::ByteMap,
+};
+
+// ===== Mutations =================================================================================
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum Change {
+    Substitution { from: u8, to: u8 },
+    Insertion(Vec<u8>),
+    Deletion(Vec<u8>),
+}
+
+// NOTE: Positions are in the sequence as it was just before the mutation was applied, so replaying
+// the mutations in order onto the original sequence always reproduces the mutated one. Insertions
+// are made in front of their position
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Mutation {
+    pub generation: usize,
+    pub position: usize,
+    pub change: Change,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Mutated {
+    pub seq: Seq,
+    pub mutations: Vec<Mutation>,
+    pub generations: usize,
+}
+
+// ===== Mutation Simulation =======================================================================
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MutationSimulator {
+    substitution_rate: f64,
+    substitution_matrix: [[f64; 4]; 4],
+    insertion_rate: f64,
+    deletion_rate: f64,
+    max_indel_len: usize,
+}
+
+impl Default for MutationSimulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MutationSimulator {
+    // NOTE: Rates are per base, per generation. By default, substitutions happen at a rate of 1e-3
+    // with every base equally likely to replace another (the Jukes–Cantor model), and there are no
+    // indels
+    pub fn new() -> Self {
+        Self {
+            substitution_rate: 1e-3,
+            substitution_matrix: [[1.0; 4]; 4],
+            insertion_rate: 0.0,
+            deletion_rate: 0.0,
+            max_indel_len: 1,
+        }
+    }
+
+    pub fn substitution_rate(self, substitution_rate: f64) -> Self {
+        Self {
+            substitution_rate,
+            ..self
+        }
+    }
+
+    // NOTE: Rows are the base being replaced and columns its replacement, both in ACGT (or ACGU)
+    // order. Only the relative sizes of each row's off-diagonal entries matter, since they just
+    // decide which base a substituted one becomes
+    pub fn substitution_matrix(self, substitution_matrix: [[f64; 4]; 4]) -> Self {
+        Self {
+            substitution_matrix,
+            ..self
+        }
+    }
+
+    // NOTE: Weights transitions (A <-> G and C <-> T) `kappa` times more heavily than transversions,
+    // as in Kimura's two-parameter model
+    pub fn transition_bias(self, kappa: f64) -> Self {
+        let mut matrix = [[1.0; 4]; 4];
+        for (from, to) in [(0, 2), (2, 0), (1, 3), (3, 1)] {
+            matrix[from][to] = kappa;
+        }
+        self.substitution_matrix(matrix)
+    }
+
+    pub fn insertion_rate(self, insertion_rate: f64) -> Self {
+        Self {
+            insertion_rate,
+            ..self
+        }
+    }
+
+    pub fn deletion_rate(self, deletion_rate: f64) -> Self {
+        Self {
+            deletion_rate,
+            ..self
+        }
+    }
+
+    // NOTE: Indel lengths are drawn uniformly between one and this length
+    pub fn max_indel_len(self, max_indel_len: usize) -> Self {
+        Self {
+            max_indel_len: max_indel_len.max(1),
+            ..self
+        }
+    }
+
+    pub fn generations(
+        &self,
+        seq: &Seq,
+        generations: usize,
+        rng: &mut impl Rng,
+    ) -> Result<Mutated, Error> {
+        self.evolve(
+            seq,
+            rng,
+            |mutated, _| Ok(mutated.generations >= generations),
+        )
+    }
+
+    // NOTE: Divergence is measured as the number of mutations per base of the original sequence, and
+    // the simulation stops after the first generation reaching it (or once deletions have left
+    // nothing more to mutate). It's an error if the sequence ever reaches a state where no further
+    // mutation is possible, like a sequence of only ambiguous bases without any indels
+    pub fn divergence(
+        &self,
+        seq: &Seq,
+        divergence: f64,
+        rng: &mut impl Rng,
+    ) -> Result<Mutated, Error> {
+        if !divergence.is_finite() {
+            return Err(Error::InvalidDivergence);
+        }
+        if divergence > 0.0 && seq.is_empty() {
+            return Err(Error::ZeroMutationRate);
+        }
+        let len = seq.len() as f64;
+        let index = pwm::symbol_index(seq.kind());
+        self.evolve(seq, rng, |mutated, bytes| {
+            if bytes.is_empty() || mutated.mutations.len() as f64 / len >= divergence {
+                Ok(true)
+            } else if self.can_mutate(bytes, &index) {
+                Ok(false)
+            } else {
+                Err(Error::ZeroMutationRate)
+            }
+        })
+    }
+
+    fn evolve(
+        &self,
+        seq: &Seq,
+        rng: &mut impl Rng,
+        done: impl Fn(&Mutated, &[u8]) -> Result<bool, Error>,
+    ) -> Result<Mutated, Error> {
+        let kind = seq.kind();
+        if !matches!(kind, Kind::Dna | Kind::Rna) {
+            return Err(Error::MutationSimulation(kind));
+        }
+        for (name, rate) in [
+            ("substitution rate", self.substitution_rate),
+            ("insertion rate", self.insertion_rate),
+            ("deletion rate", self.deletion_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(Error::InvalidProbability(name.to_string()));
+            }
+        }
+        let symbols = pwm::symbols(kind);
+        let index = pwm::symbol_index(kind);
+
+        let mut bytes = seq.as_bytes().to_vec();
+        let mut mutated = Mutated {
+            seq: seq.clone(),
+            mutations: Vec::new(),
+            generations: 0,
+        };
+        while !done(&mutated, &bytes)? {
+            mutated.generations += 1;
+            let generation = mutated.generations;
+            // NOTE: Working from the 3' end means a mutation never moves the positions of the ones
+            // still to come in the same generation
+            for position in (0..bytes.len()).rev() {
+                if rng.gen_bool(self.deletion_rate) {
+                    let len = rng
+                        .gen_range(1..=self.max_indel_len)
+                        .min(bytes.len() - position);
+                    let deleted: Vec<_> = bytes.drain(position..position + len).collect();
+                    mutated.mutations.push(Mutation {
+                        generation,
+                        position,
+                        change: Change::Deletion(deleted),
+                    });
+                    continue;
+                }
+                let substitution = index[bytes[position]]
+                    .filter(|_| rng.gen_bool(self.substitution_rate))
+                    .and_then(|from| self.substitute(from, rng));
+                if let Some(to) = substitution {
+                    let from = bytes[position];
+                    let to = if from.is_ascii_lowercase() {
+                        symbols[to].to_ascii_lowercase()
+                    } else {
+                        symbols[to]
+                    };
+                    bytes[position] = to;
+                    mutated.mutations.push(Mutation {
+                        generation,
+                        position,
+                        change: Change::Substitution { from, to },
+                    });
+                }
+                if rng.gen_bool(self.insertion_rate) {
+                    let len = rng.gen_range(1..=self.max_indel_len);
+                    let inserted: Vec<_> = (0..len)
+                        .map(|_| symbols[rng.gen_range(0..symbols.len())])
+                        .collect();
+                    bytes.splice(position..position, inserted.iter().copied());
+                    mutated.mutations.push(Mutation {
+                        generation,
+                        position,
+                        change: Change::Insertion(inserted),
+                    });
+                }
+            }
+        }
+
+        mutated.seq = Seq::from_vec_with_kind(bytes, [kind], seq.alphabet())?;
+        Ok(mutated)
+    }
+
+    // NOTE: Picks the replacement for a base from its row of the substitution matrix, or nothing if
+    // the row doesn't allow the base to change at all
+    fn substitute(&self, from: usize, rng: &mut impl Rng) -> Option<usize> {
+        self.replacements(from).map(|d| d.sample(rng))
+    }
+
+    fn replacements(&self, from: usize) -> Option<WeightedIndex<f64>> {
+        let weights = self.substitution_matrix[from]
+            .iter()
+            .enumerate()
+            .map(|(to, &w)| if to == from { 0.0 } else { w.max(0.0) });
+        WeightedIndex::new(weights).ok()
+    }
+
+    fn can_mutate(&self, bytes: &[u8], index: &ByteMap<Option<usize>>) -> bool {
+        if self.insertion_rate > 0.0 || self.deletion_rate > 0.0 {
+            return true;
+        }
+        let substitutable: Vec<_> = (0..4).map(|i| self.replacements(i).is_some()).collect();
+        self.substitution_rate > 0.0
+            && bytes
+                .iter()
+                .any(|&b| index[b].is_some_and(|i| substitutable[i]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn ancestor() -> Result<Seq, Error> {
+        Seq::dna("ATGGCTAGCAAAGGAGAAGAACTTTTCACTGGAGTTGTCCCAATT".repeat(22))
+    }
+
+    fn replay(seq: &Seq, mutations: &[Mutation]) -> Vec<u8> {
+        let mut bytes = seq.as_bytes().to_vec();
+        for mutation in mutations {
+            let p = mutation.position;
+            match &mutation.change {
+                Change::Substitution { from, to } => {
+                    assert_eq!(bytes[p], *from);
+                    bytes[p] = *to;
+                }
+                Change::Insertion(inserted) => {
+                    bytes.splice(p..p, inserted.iter().copied());
+                }
+                Change::Deletion(deleted) => {
+                    let removed: Vec<_> = bytes.drain(p..p + deleted.len()).collect();
+                    assert_eq!(&removed, deleted);
+                }
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn mutate_over_generations() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let seq = ancestor()?;
+        let simulator = MutationSimulator::new()
+            .substitution_rate(0.01)
+            .insertion_rate(0.001)
+            .deletion_rate(0.001)
+            .max_indel_len(3);
+        let mutated = simulator.generations(&seq, 10, &mut rng)?;
+        assert_eq!(mutated.generations, 10);
+        assert!((50..150).contains(&mutated.mutations.len()));
+        assert!(mutated
+            .mutations
+            .iter()
+            .any(|m| matches!(m.change, Change::Insertion(_))));
+        assert!(mutated
+            .mutations
+            .iter()
+            .any(|m| matches!(m.change, Change::Deletion(_))));
+        assert_eq!(replay(&seq, &mutated.mutations), mutated.seq.as_bytes());
+
+        let unchanged = simulator.generations(&seq, 0, &mut rng)?;
+        assert_eq!(unchanged.seq, seq);
+        assert!(unchanged.mutations.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn mutate_with_transition_bias() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let seq = Seq::rna("acgu".repeat(250))?;
+        let mutated = MutationSimulator::new()
+            .substitution_rate(0.1)
+            .transition_bias(1000.0)
+            .generations(&seq, 1, &mut rng)?;
+        let transitions = mutated
+            .mutations
+            .iter()
+            .filter(|m| {
+                matches!(
+                    m.change,
+                    Change::Substitution { from, to }
+                        if matches!((from, to), (b'a', b'g') | (b'g', b'a') | (b'c', b'u') | (b'u', b'c'))
+                )
+            })
+            .count();
+        assert!(transitions as f64 > 0.99 * mutated.mutations.len() as f64);
+        assert!(mutated.seq.as_bytes().iter().all(u8::is_ascii_lowercase));
+
+        // NOTE: A matrix only allowing bases to become A
+        let mut matrix = [[0.0; 4]; 4];
+        matrix.iter_mut().for_each(|row| row[0] = 1.0);
+        let mutated = MutationSimulator::new()
+            .substitution_rate(0.5)
+            .substitution_matrix(matrix)
+            .generations(&seq, 5, &mut rng)?;
+        assert!(mutated
+            .mutations
+            .iter()
+            .all(|m| matches!(m.change, Change::Substitution { to: b'a', .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn mutate_to_divergence() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let seq = ancestor()?;
+        let simulator = MutationSimulator::new().substitution_rate(0.01);
+        let mutated = simulator.divergence(&seq, 0.1, &mut rng)?;
+        assert!(mutated.mutations.len() as f64 >= 0.1 * seq.len() as f64);
+        assert!((5..20).contains(&mutated.generations));
+        assert_eq!(replay(&seq, &mutated.mutations), mutated.seq.as_bytes());
+
+        assert_eq!(
+            simulator
+                .substitution_rate(0.0)
+                .divergence(&seq, 0.1, &mut rng),
+            Err(Error::ZeroMutationRate)
+        );
+        assert_eq!(
+            simulator.generations(&Seq::protein("MAMAPRTEIN")?, 1, &mut rng),
+            Err(Error::MutationSimulation(Kind::Protein))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unreachable_divergence() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let simulator = MutationSimulator::new().substitution_rate(0.5);
+        assert_eq!(
+            simulator.divergence(&Seq::dna_n("NNNNNNNN")?, 0.1, &mut rng),
+            Err(Error::ZeroMutationRate)
+        );
+        assert_eq!(
+            simulator.substitution_matrix([[0.0; 4]; 4]).divergence(
+                &Seq::dna("ACGT")?,
+                0.1,
+                &mut rng
+            ),
+            Err(Error::ZeroMutationRate)
+        );
+
+        // NOTE: A matrix only allowing bases to become A gets stuck once every base is an A
+        let mut matrix = [[0.0; 4]; 4];
+        matrix.iter_mut().for_each(|row| row[0] = 1.0);
+        assert_eq!(
+            simulator
+                .substitution_matrix(matrix)
+                .divergence(&Seq::dna("ACGT")?, 10.0, &mut rng),
+            Err(Error::ZeroMutationRate)
+        );
+
+        let empty = simulator
+            .insertion_rate(0.1)
+            .divergence(&Seq::dna("")?, 0.0, &mut rng)?;
+        assert!(empty.mutations.is_empty());
+        assert_eq!(
+            simulator.divergence(&Seq::dna("ACGT")?, f64::INFINITY, &mut rng),
+            Err(Error::InvalidDivergence)
+        );
+        assert_eq!(
+            simulator
+                .substitution_rate(f64::NAN)
+                .generations(&Seq::dna("ACGT")?, 1, &mut rng),
+            Err(Error::InvalidProbability("substitution rate".to_string()))
+        );
+        assert_eq!(
+            simulator
+                .deletion_rate(1.5)
+                .divergence(&Seq::dna("ACGT")?, 0.1, &mut rng),
+            Err(Error::InvalidProbability("deletion rate".to_string()))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enzyme::{self, Enzyme},
+    seq::{Error, Seq},
+    types::Topology,
+};
+
+// ===== Virtual Gels ==============================================================================
+
+// NOTE: The band sizes of the GeneRuler 1 kb DNA ladder
+const LADDER: [usize; 14] = [
+    10000, 8000, 6000, 5000, 4000, 3500, 3000, 2500, 2000, 1500, 1000, 750, 500, 250,
+];
+
+#[derive(Clone, Debug)]
+pub struct Digest<'a> {
+    pub label: String,
+    pub seq: &'a Seq,
+    pub topology: Topology,
+    pub enzymes: Vec<Enzyme>,
+}
+
+// NOTE: Fragments of the same size run as a single band, with `count` of them making it brighter.
+// `migration` is how far the band runs, from 0.0 (level with the largest ladder band) to 1.0 (level
+// with the smallest)
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Band {
+    pub size: usize,
+    pub count: usize,
+    pub circular: bool,
+    pub migration: f64,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Lane {
+    pub label: String,
+    pub bands: Vec<Band>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Gel {
+    pub ladder: Lane,
+    pub lanes: Vec<Lane>,
+}
+
+#[derive(Clone, Debug)]
+pub struct VirtualGel {
+    ladder: Vec<usize>,
+}
+
+impl Default for VirtualGel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VirtualGel {
+    pub fn new() -> Self {
+        Self {
+            ladder: LADDER.to_vec(),
+        }
+    }
+
+    pub fn ladder(self, ladder: impl IntoIterator<Item = usize>) -> Self {
+        Self {
+            ladder: ladder.into_iter().collect(),
+        }
+    }
+
+    // NOTE: Bands are placed with the usual log-linear model, where migration falls with the log of
+    // fragment size, fitted to the two ends of the ladder. Bands outside the ladder are pinned to
+    // its ends, and uncut circular DNA is placed at its full length, though real supercoiled DNA
+    // runs ahead of linear DNA the same size
+    pub fn run<'a>(&self, digests: impl IntoIterator<Item = Digest<'a>>) -> Result<Gel, Error> {
+        let lanes = digests
+            .into_iter()
+            .map(|d| {
+                let fragments = enzyme::digest(d.seq, d.topology, &d.enzymes)?;
+                let sizes = fragments.iter().map(|f| (f.len(), f.circular));
+                Ok(self.lane(d.label, sizes))
+            })
+            .collect::<Result<_, Error>>()?;
+        let ladder = self.lane(
+            "Ladder".to_string(),
+            self.ladder.iter().map(|&s| (s, false)),
+        );
+        Ok(Gel { ladder, lanes })
+    }
+
+    fn lane(&self, label: String, sizes: impl Iterator<Item = (usize, bool)>) -> Lane {
+        let mut bands: Vec<Band> = Vec::new();
+        for (size, circular) in sizes {
+            match bands
+                .iter_mut()
+                .find(|b| b.size == size && b.circular == circular)
+            {
+                Some(band) => band.count += 1,
+                None => bands.push(Band {
+                    size,
+                    count: 1,
+                    circular,
+                    migration: self.migration(size),
+                }),
+            }
+        }
+        bands.sort_by(|a, b| b.size.cmp(&a.size).then(a.circular.cmp(&b.circular)));
+        Lane { label, bands }
+    }
+
+    fn migration(&self, size: usize) -> f64 {
+        let max = self.ladder.iter().copied().max().unwrap_or(0) as f64;
+        let min = self.ladder.iter().copied().min().unwrap_or(0) as f64;
+        if size == 0 || max <= min {
+            return 0.0;
+        }
+        ((max.log10() - (size as f64).log10()) / (max.log10() - min.log10())).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Kind;
+
+    // NOTE: A 4 kb plasmid with EcoRI sites cutting at 100 and 1100, and a BamHI site cutting at 3101
+    fn plasmid() -> Result<Seq, Error> {
+        let seq = format!(
+            "{}GAATTC{}GAATTC{}GGATCC{}",
+            "A".repeat(99),
+            "A".repeat(994),
+            "A".repeat(1995),
+            "A".repeat(894)
+        );
+        Seq::dna(seq)
+    }
+
+    #[test]
+    fn run_gel() -> Result<(), Error> {
+        let plasmid = plasmid()?;
+        let eco_ri = Enzyme::builtin("EcoRI").unwrap();
+        let bam_hi = Enzyme::builtin("BamHI").unwrap();
+        let digest = |label: &str, topology, enzymes: &[Enzyme]| Digest {
+            label: label.to_string(),
+            seq: &plasmid,
+            topology,
+            enzymes: enzymes.to_vec(),
+        };
+        let gel = VirtualGel::new().run([
+            digest("Uncut", Topology::Circular, &[]),
+            digest("EcoRI", Topology::Circular, std::slice::from_ref(&eco_ri)),
+            digest(
+                "EcoRI + BamHI",
+                Topology::Circular,
+                &[eco_ri, bam_hi.clone()],
+            ),
+            digest("BamHI (linear)", Topology::Linear, &[bam_hi]),
+        ])?;
+
+        let sizes: Vec<Vec<_>> = gel
+            .lanes
+            .iter()
+            .map(|l| l.bands.iter().map(|b| b.size).collect())
+            .collect();
+        assert_eq!(
+            sizes,
+            [
+                vec![4000],
+                vec![3000, 1000],
+                vec![2001, 1000, 999],
+                vec![3101, 899]
+            ]
+        );
+        assert!(gel.lanes[0].bands[0].circular);
+        assert!(!gel.lanes[1].bands[0].circular);
+        assert_eq!(gel.lanes[2].label, "EcoRI + BamHI");
+
+        assert_eq!(gel.ladder.bands.len(), 14);
+        assert_eq!(gel.ladder.bands[0].migration, 0.0);
+        assert_eq!(gel.ladder.bands[13].migration, 1.0);
+        let kb = gel.lanes[1].bands[1].migration;
+        assert!((kb - 1.0 / (4.0 - 250f64.log10())).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn gel_bands() -> Result<(), Error> {
+        let gel = VirtualGel::new().ladder([1000, 100]);
+        let seq = Seq::dna("GAATTCAAAAGAATTCAAAAGAATTCAAAAGAATTCAAAA")?;
+        let gel = gel.run([Digest {
+            label: "EcoRI".to_string(),
+            seq: &seq,
+            topology: Topology::Circular,
+            enzymes: vec![Enzyme::builtin("EcoRI").unwrap()],
+        }])?;
+        let lane = &gel.lanes[0];
+        assert_eq!(lane.bands.len(), 1);
+        assert_eq!((lane.bands[0].size, lane.bands[0].count), (10, 4));
+        assert_eq!(lane.bands[0].migration, 1.0);
+        assert_eq!(gel.ladder.bands.len(), 2);
+
+        let protein = Seq::protein("MAMAPRTEIN")?;
+        let digest = Digest {
+            label: "Protein".to_string(),
+            seq: &protein,
+            topology: Topology::Linear,
+            enzymes: Vec::new(),
+        };
+        assert_eq!(
+            VirtualGel::new().run([digest]),
+            Err(Error::EnzymeSearch(Kind::Protein))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,695 @@
+use crate::{
+    annotation::{AnnotatedRecord, Feature},
+    record::SeqRecord,
+    region::Region,
+    seq::{Alphabet, Error, Kind, Seq},
+    types::{Strand, Topology},
+};
+
+// ===== GenBank Layout ============================================================================
+
+const LINE_WIDTH: usize = 79;
+const HEADER_INDENT: usize = 12;
+const FEATURE_INDENT: usize = 21;
+const DEFAULT_DIVISION: &str = "UNK";
+const DEFAULT_DATE: &str = "01-JAN-1980";
+
+// NOTE: These header fields are written indented beneath the field they belong to (like the
+// ORGANISM of a SOURCE), but are otherwise kept as ordinary metadata
+const SUBKEYWORDS: [&str; 7] = [
+    "ORGANISM", "AUTHORS", "CONSRTM", "TITLE", "JOURNAL", "PUBMED", "REMARK",
+];
+
+// ===== GenBank Writer ============================================================================
+
+impl AnnotatedRecord {
+    // NOTE: Sequences are written in their original case (rather than GenBank's usual lowercase),
+    // so soft-masking survives a round trip. Records without a division or date are given "UNK" and
+    // "01-JAN-1980", and spaces in IDs are replaced with underscores (LOCUS names can't contain them)
+    pub fn to_genbank(&self) -> String {
+        let mut out = String::new();
+        let seq = self.seq();
+        let (unit, molecule) = match seq.kind() {
+            Kind::Dna => ("bp", "DNA"),
+            Kind::Rna => ("bp", "RNA"),
+            Kind::Protein => ("aa", ""),
+            Kind::Custom(_) => ("bp", ""),
+        };
+        let topology = match self.topology() {
+            Topology::Linear => "linear",
+            Topology::Circular => "circular",
+        };
+        out.push_str(&format!(
+            "LOCUS       {:<16} {:>11} {unit}    {molecule:<6}  {topology:<8} {} {}\n",
+            self.id().replace(' ', "_"),
+            seq.len(),
+            self.division().unwrap_or(DEFAULT_DIVISION),
+            self.date().unwrap_or(DEFAULT_DATE),
+        ));
+
+        push_header(&mut out, "DEFINITION", self.desc().unwrap_or("."));
+        for (key, value) in self.metadata() {
+            if SUBKEYWORDS.contains(&key.as_str()) {
+                push_header(&mut out, &format!("  {key}"), value);
+            } else {
+                push_header(&mut out, key, value);
+            }
+        }
+
+        out.push_str("FEATURES             Location/Qualifiers\n");
+        for feature in self.features() {
+            push_feature(&mut out, feature);
+        }
+
+        out.push_str("ORIGIN\n");
+        for (i, line) in seq.as_bytes().chunks(60).enumerate() {
+            out.push_str(&format!("{:>9}", i * 60 + 1));
+            for block in line.chunks(10) {
+                out.push(' ');
+                out.push_str(&String::from_utf8_lossy(block));
+            }
+            out.push('\n');
+        }
+        out.push_str("//\n");
+        out
+    }
+}
+
+fn push_header(out: &mut String, keyword: &str, value: &str) {
+    for (i, line) in wrap_words(value, LINE_WIDTH - HEADER_INDENT)
+        .iter()
+        .enumerate()
+    {
+        let keyword = if i == 0 { keyword } else { "" };
+        out.push_str(&format!("{keyword:<HEADER_INDENT$}{line}\n"));
+    }
+}
+
+fn push_feature(out: &mut String, feature: &Feature) {
+    let location = wrap_location(&location_string(feature.parts()));
+    for (i, line) in location.iter().enumerate() {
+        let kind = if i == 0 { feature.kind() } else { "" };
+        out.push_str(&format!("     {kind:<16}{line}\n"));
+    }
+
+    for (key, value) in feature.qualifiers() {
+        let qualifier = if value.is_empty() {
+            format!("/{key}")
+        } else if value.bytes().all(|b| b.is_ascii_digit()) {
+            format!("/{key}={value}")
+        } else {
+            format!("/{key}=\"{}\"", value.replace('"', "\"\""))
+        };
+        // NOTE: Translations are the only values split mid-word (they never contain spaces), since
+        // the reader joins every other value's lines back together with spaces
+        let lines = if key == "translation" {
+            let chars: Vec<_> = qualifier.chars().collect();
+            chars
+                .chunks(LINE_WIDTH - FEATURE_INDENT)
+                .map(|c| c.iter().collect())
+                .collect()
+        } else {
+            wrap_words(&qualifier, LINE_WIDTH - FEATURE_INDENT)
+        };
+        for line in lines {
+            out.push_str(&format!("{:FEATURE_INDENT$}{line}\n", ""));
+        }
+    }
+}
+
+// NOTE: Parts are stored in the order they are read, so a feature entirely on the reverse strand is
+// written as the complement of its parts joined in ascending order
+fn location_string(parts: &[Region]) -> String {
+    let forward = |r: &Region| r.with_strand(Strand::Forward).to_string();
+    match parts {
+        [part] => part.to_string(),
+        _ if parts.iter().all(|p| p.strand() == Strand::Reverse) => {
+            let parts: Vec<_> = parts.iter().rev().map(forward).collect();
+            format!("complement(join({}))", parts.join(","))
+        }
+        _ => {
+            let parts: Vec<_> = parts.iter().map(Region::to_string).collect();
+            format!("join({})", parts.join(","))
+        }
+    }
+}
+
+fn wrap_location(location: &str) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for piece in location.split_inclusive(',') {
+        let line = lines.last_mut().unwrap();
+        if !line.is_empty() && line.len() + piece.len() > LINE_WIDTH - FEATURE_INDENT {
+            lines.push(piece.to_string());
+        } else {
+            line.push_str(piece);
+        }
+    }
+    lines
+}
+
+// NOTE: Words longer than the width are left on a line of their own rather than being split
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split(' ') {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+// ===== GenBank Reader ============================================================================
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Section {
+    Header,
+    Features,
+    Origin,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum LastField {
+    Definition,
+    Metadata,
+    Other,
+}
+
+#[derive(Default)]
+struct RawFeature {
+    line: usize,
+    kind: String,
+    location: String,
+    qualifiers: Vec<String>,
+}
+
+#[derive(Default)]
+struct RawRecord {
+    line: usize,
+    name: String,
+    len: usize,
+    unit: String,
+    molecule: String,
+    topology: Option<Topology>,
+    division: Option<String>,
+    date: Option<String>,
+    desc: Option<String>,
+    metadata: Vec<(String, String)>,
+    features: Vec<RawFeature>,
+    seq: Vec<u8>,
+}
+
+impl AnnotatedRecord {
+    // NOTE: This reads every record in a GenBank flat file. Partial locations (like `<1..>100`) are
+    // read as ordinary ones, but remote (`J00194.1:100..202`) and between-base (`4^5`) locations
+    // aren't supported. BASE COUNT lines are skipped, since they're implied by the sequence
+    pub fn from_genbank(text: &str) -> Result<Vec<Self>, Error> {
+        let mut records = Vec::new();
+        let mut record: Option<RawRecord> = None;
+        let mut section = Section::Header;
+        let mut last = LastField::Other;
+
+        for (i, line) in text.lines().enumerate() {
+            let n = i + 1;
+            let line = line.trim_end();
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some(raw) = record.as_mut() else {
+                if !line.starts_with("LOCUS") {
+                    return Err(Error::GenBank(n, "expected a LOCUS line".to_string()));
+                }
+                record = Some(parse_locus(n, line)?);
+                section = Section::Header;
+                last = LastField::Other;
+                continue;
+            };
+            if line == "//" {
+                records.push(build_record(record.take().unwrap())?);
+                continue;
+            }
+
+            let keyword = line.get(..HEADER_INDENT).unwrap_or(line).trim();
+            let value = line.get(HEADER_INDENT..).unwrap_or("").trim();
+            let top_level = !line.starts_with(' ');
+            match section {
+                _ if top_level && keyword == "ORIGIN" => section = Section::Origin,
+                _ if top_level && keyword == "FEATURES" => section = Section::Features,
+                _ if top_level && line.starts_with("BASE COUNT") => (),
+                _ if top_level && keyword == "CONTIG" => {
+                    return Err(Error::GenBank(
+                        n,
+                        "CONTIG records aren't supported".to_string(),
+                    ));
+                }
+                Section::Header if keyword.is_empty() => match last {
+                    LastField::Definition => {
+                        let desc = raw.desc.get_or_insert_with(String::new);
+                        desc.push(' ');
+                        desc.push_str(value);
+                    }
+                    LastField::Metadata => {
+                        let (_, v) = raw.metadata.last_mut().unwrap();
+                        v.push(' ');
+                        v.push_str(value);
+                    }
+                    LastField::Other => {
+                        return Err(Error::GenBank(n, "unexpected continuation".to_string()));
+                    }
+                },
+                Section::Header if keyword == "DEFINITION" => {
+                    raw.desc = (value != ".").then(|| value.to_string());
+                    last = LastField::Definition;
+                }
+                Section::Header => {
+                    raw.metadata.push((keyword.to_string(), value.to_string()));
+                    last = LastField::Metadata;
+                }
+                Section::Features => parse_feature_line(n, line, raw)?,
+                Section::Origin => raw.seq.extend(
+                    line.bytes()
+                        .filter(|b| !b.is_ascii_digit() && !b.is_ascii_whitespace()),
+                ),
+            }
+        }
+
+        match record {
+            Some(raw) => Err(Error::GenBank(
+                raw.line,
+                "record is missing its closing //".to_string(),
+            )),
+            None => Ok(records),
+        }
+    }
+}
+
+fn parse_locus(n: usize, line: &str) -> Result<RawRecord, Error> {
+    let error = || Error::GenBank(n, "malformed LOCUS line".to_string());
+    let mut tokens = line.split_whitespace().skip(1);
+    let name = tokens.next().ok_or_else(error)?.to_string();
+    let len = tokens
+        .next()
+        .and_then(|l| l.parse().ok())
+        .ok_or_else(error)?;
+    let unit = tokens.next().ok_or_else(error)?.to_string();
+
+    let mut raw = RawRecord {
+        line: n,
+        name,
+        len,
+        unit,
+        ..Default::default()
+    };
+    // NOTE: Besides the name and length, every LOCUS field is optional, so the rest are told apart
+    // by their contents rather than their positions
+    for token in tokens {
+        let is_date =
+            token.len() == 11 && token.as_bytes()[2] == b'-' && token.as_bytes()[6] == b'-';
+        match token {
+            "linear" => raw.topology = Some(Topology::Linear),
+            "circular" => raw.topology = Some(Topology::Circular),
+            _ if is_date => raw.date = Some(token.to_string()),
+            _ if token.contains("NA") => raw.molecule = token.to_string(),
+            _ => raw.division = Some(token.to_string()),
+        }
+    }
+    Ok(raw)
+}
+
+fn parse_feature_line(n: usize, line: &str, raw: &mut RawRecord) -> Result<(), Error> {
+    let indent = line.len() - line.trim_start().len();
+    let content = line.trim();
+    if (5..FEATURE_INDENT).contains(&indent) {
+        let (kind, location) = content
+            .split_once(char::is_whitespace)
+            .unwrap_or((content, ""));
+        raw.features.push(RawFeature {
+            line: n,
+            kind: kind.to_string(),
+            location: location.trim().to_string(),
+            qualifiers: Vec::new(),
+        });
+        return Ok(());
+    }
+
+    let Some(feature) = raw.features.last_mut() else {
+        return Err(Error::GenBank(n, "expected a feature key".to_string()));
+    };
+    if indent < FEATURE_INDENT {
+        return Err(Error::GenBank(
+            n,
+            "misaligned feature table line".to_string(),
+        ));
+    }
+    let open_quote = feature
+        .qualifiers
+        .last()
+        .is_some_and(|q| q.matches('"').count() % 2 == 1);
+    match feature.qualifiers.last_mut() {
+        Some(qualifier) if open_quote || !content.starts_with('/') => {
+            if !qualifier.starts_with("/translation=") {
+                qualifier.push(' ');
+            }
+            qualifier.push_str(content);
+        }
+        _ if content.starts_with('/') => feature.qualifiers.push(content.to_string()),
+        _ => feature.location.push_str(content),
+    }
+    Ok(())
+}
+
+fn build_record(raw: RawRecord) -> Result<AnnotatedRecord, Error> {
+    // NOTE: NCBI writes RNA records (like mRNAs) using their cDNA sequence, so they may be DNA.
+    // Protein records can use the extended alphabet, since nothing else could be mistaken for them
+    let (kinds, alphabet): (&[Kind], _) = if raw.unit == "aa" {
+        (&[Kind::Protein], Alphabet::Extended)
+    } else if raw.molecule.contains("RNA") {
+        (&[Kind::Rna, Kind::Dna], Alphabet::Iupac)
+    } else {
+        (&[Kind::Dna], Alphabet::Iupac)
+    };
+    let seq = Seq::from_vec_with_kind(raw.seq, kinds, alphabet)?;
+    if seq.len() != raw.len {
+        return Err(Error::GenBank(
+            raw.line,
+            format!("expected {} elements, found {}", raw.len, seq.len()),
+        ));
+    }
+
+    let record = match raw.desc {
+        Some(desc) => SeqRecord::new(raw.name, seq).with_desc(desc),
+        None => SeqRecord::new(raw.name, seq),
+    };
+    let mut annotated = AnnotatedRecord::new(record, raw.topology.unwrap_or(Topology::Linear));
+    if let Some(division) = raw.division {
+        annotated = annotated.with_division(division);
+    }
+    if let Some(date) = raw.date {
+        annotated = annotated.with_date(date);
+    }
+    for (key, value) in raw.metadata {
+        annotated = annotated.with_metadata(key, value);
+    }
+    for feature in raw.features {
+        let error = |reason: &str| Error::GenBank(feature.line, reason.to_string());
+        let parts = parse_location(&feature.location, Strand::Forward)
+            .ok_or_else(|| error("unsupported or malformed location"))?;
+        let mut parsed = Feature::from_parts(feature.kind, parts)?;
+        for qualifier in feature.qualifiers {
+            let qualifier = &qualifier[1..];
+            parsed = match qualifier.split_once('=') {
+                Some((key, value)) => {
+                    let value = match value.strip_prefix('"') {
+                        Some(quoted) => quoted
+                            .strip_suffix('"')
+                            .ok_or_else(|| error("unterminated qualifier"))?
+                            .replace("\"\"", "\""),
+                        None => value.to_string(),
+                    };
+                    parsed.with_qualifier(key, value)
+                }
+                None => parsed.with_qualifier(qualifier, ""),
+            };
+        }
+        annotated = annotated
+            .with_feature(parsed)
+            .map_err(|_| error("location is out of bounds"))?;
+    }
+    Ok(annotated)
+}
+
+// NOTE: Returns the parts in the order they are read, so the parts of complemented joins are
+// reversed
+fn parse_location(location: &str, strand: Strand) -> Option<Vec<Region>> {
+    let location = location.trim();
+    if let Some(inner) = strip_call(location, "complement") {
+        let reverse = match strand {
+            Strand::Forward => Strand::Reverse,
+            Strand::Reverse => Strand::Forward,
+        };
+        let mut parts = parse_location(inner, reverse)?;
+        parts.reverse();
+        return Some(parts);
+    }
+    if let Some(inner) = strip_call(location, "join").or_else(|| strip_call(location, "order")) {
+        let mut parts = Vec::new();
+        for piece in split_top_level(inner) {
+            parts.extend(parse_location(piece, strand)?);
+        }
+        return Some(parts);
+    }
+
+    let location: String = location.chars().filter(|&c| c != '<' && c != '>').collect();
+    let (start, end) = location.split_once("..").unwrap_or((&location, &location));
+    let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+    Some(vec![Region::from_one_based(start, end, strand).ok()?])
+}
+
+fn strip_call<'a>(location: &'a str, name: &str) -> Option<&'a str> {
+    location
+        .strip_prefix(name)?
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                pieces.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    pieces.push(&list[start..]);
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_plasmid() -> Result<AnnotatedRecord, Error> {
+        let seq =
+            Seq::dna("ATGAAACGCATTAGCACCACCATTACCACCACCATCACCATTACCACAGGTAACGGTGCGGGCTGAaaccggtt")?;
+        let record = SeqRecord::new("pTest", seq).with_desc(
+            "Synthetic construct pTest, a plasmid with a fairly long definition that has to be \
+             wrapped over more than one line.",
+        );
+        let origin = Feature::from_parts(
+            "misc_feature",
+            [
+                Region::new(68..74, Strand::Forward)?,
+                Region::new(0..3, Strand::Forward)?,
+            ],
+        )?
+        .with_qualifier("label", "origin spanning");
+        let reverse = Feature::from_parts(
+            "CDS",
+            [
+                Region::new(40..50, Strand::Reverse)?,
+                Region::new(10..20, Strand::Reverse)?,
+            ],
+        )?
+        .with_qualifier("codon_start", "1")
+        .with_qualifier("pseudo", "");
+        let cds = Feature::new("CDS", Region::new(0..66, Strand::Forward)?)
+            .with_qualifier("gene", "thrL")
+            .with_qualifier(
+                "note",
+                "a \"quoted\" note that's long enough to be wrapped onto the next line of the file",
+            )
+            .with_qualifier("translation", "MKRISTTITTTITITTGNGAG".repeat(4));
+        AnnotatedRecord::new(record, Topology::Circular)
+            .with_division("SYN")
+            .with_date("14-OCT-2026")
+            .with_metadata("KEYWORDS", ".")
+            .with_metadata("SOURCE", "synthetic DNA construct")
+            .with_metadata("ORGANISM", "synthetic DNA construct")
+            .with_feature(cds)?
+            .with_feature(origin)?
+            .with_feature(reverse)
+    }
+
+    #[test]
+    fn write_genbank() -> Result<(), Error> {
+        let genbank = test_plasmid()?.to_genbank();
+        let lines: Vec<_> = genbank.lines().collect();
+        assert_eq!(
+            lines[0],
+            "LOCUS       pTest                     74 bp    DNA     circular SYN 14-OCT-2026"
+        );
+        assert_eq!(
+            lines[1],
+            "DEFINITION  Synthetic construct pTest, a plasmid with a fairly long definition"
+        );
+        assert_eq!(lines[5], "  ORGANISM  synthetic DNA construct");
+        assert!(genbank.contains("     misc_feature    join(69..74,1..3)\n"));
+        assert!(genbank.contains("     CDS             complement(join(11..20,41..50))\n"));
+        assert!(genbank.contains("                     /codon_start=1\n"));
+        assert!(genbank.contains("                     /pseudo\n"));
+        assert!(genbank.contains("/note=\"a \"\"quoted\"\" note"));
+        assert!(genbank.contains(
+            "ORIGIN\n        1 ATGAAACGCA TTAGCACCAC CATTACCACC ACCATCACCA TTACCACAGG TAACGGTGCG\n"
+        ));
+        assert!(genbank.ends_with("       61 GGCTGAaacc ggtt\n//\n"));
+        assert!(lines.iter().all(|l| l.chars().count() <= LINE_WIDTH));
+        Ok(())
+    }
+
+    #[test]
+    fn genbank_round_trip() -> Result<(), Error> {
+        let plasmid = test_plasmid()?;
+        let records = AnnotatedRecord::from_genbank(&plasmid.to_genbank())?;
+        assert_eq!(records, [plasmid]);
+
+        let protein = AnnotatedRecord::new(
+            SeqRecord::new("MAMA", Seq::protein("MAMAPRTEIN")?),
+            Topology::Linear,
+        );
+        let records = AnnotatedRecord::from_genbank(&protein.to_genbank())?;
+        assert_eq!(records[0].seq(), protein.seq());
+        assert_eq!(records[0].desc(), None);
+        assert_eq!(records[0].division(), Some(DEFAULT_DIVISION));
+
+        for protein in [
+            Seq::protein("MAMA*")?,
+            Seq::new_with_kind("MAUOJ*", [Kind::Protein], Alphabet::Extended)?,
+        ] {
+            let record = AnnotatedRecord::new(SeqRecord::new("p", protein), Topology::Linear);
+            let records = AnnotatedRecord::from_genbank(&record.to_genbank())?;
+            assert_eq!(records[0].seq(), record.seq());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn read_ncbi_genbank() -> Result<(), Error> {
+        let genbank = "\
+LOCUS       SCU49845                  24 bp    mRNA    linear   PLN 21-JUN-1999
+DEFINITION  Saccharomyces cerevisiae TCP1-beta gene, partial cds, and Axl2p
+            (AXL2) gene.
+ACCESSION   U49845
+SOURCE      Saccharomyces cerevisiae (baker's yeast)
+  ORGANISM  Saccharomyces cerevisiae
+            Eukaryota; Fungi.
+FEATURES             Location/Qualifiers
+     source          1..24
+                     /organism=\"Saccharomyces cerevisiae\"
+     CDS             <1..>18
+                     /codon_start=3
+                     /translation=\"SSIY
+                     NGIS\"
+     gene            complement(join(3..5,
+                     10..12))
+BASE COUNT        6 a      6 c      6 g      6 t
+ORIGIN
+        1 gatcctccat atacaacggt atct
+//
+";
+        let records = AnnotatedRecord::from_genbank(genbank)?;
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.id(), "SCU49845");
+        assert_eq!(record.seq().kind(), Kind::Dna);
+        assert_eq!(record.topology(), Topology::Linear);
+        assert_eq!(record.date(), Some("21-JUN-1999"));
+        assert_eq!(
+            record.desc(),
+            Some("Saccharomyces cerevisiae TCP1-beta gene, partial cds, and Axl2p (AXL2) gene.")
+        );
+        assert_eq!(
+            record.metadata()[2],
+            (
+                "ORGANISM".to_string(),
+                "Saccharomyces cerevisiae Eukaryota; Fungi.".to_string()
+            )
+        );
+
+        let features = record.features();
+        assert_eq!(features.len(), 3);
+        assert_eq!(
+            features[0].qualifier("organism"),
+            Some("Saccharomyces cerevisiae")
+        );
+        assert_eq!(features[1].parts(), [Region::new(0..18, Strand::Forward)?]);
+        assert_eq!(features[1].qualifier("translation"), Some("SSIYNGIS"));
+        assert_eq!(
+            features[2].parts(),
+            [
+                Region::new(9..12, Strand::Reverse)?,
+                Region::new(2..5, Strand::Reverse)?
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_multiple_records() -> Result<(), Error> {
+        let a = AnnotatedRecord::new(SeqRecord::new("a", Seq::dna("ACGT")?), Topology::Linear);
+        let b = AnnotatedRecord::new(SeqRecord::new("b", Seq::rna("ACGU")?), Topology::Circular);
+        let records = AnnotatedRecord::from_genbank(&(a.to_genbank() + &b.to_genbank()))?;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].seq(), b.seq());
+        assert_eq!(records[1].topology(), Topology::Circular);
+        Ok(())
+    }
+
+    #[test]
+    fn read_invalid_genbank() {
+        let gb = |body: &str| {
+            AnnotatedRecord::from_genbank(&format!(
+                "LOCUS       bad 4 bp DNA linear\nFEATURES             Location/Qualifiers\n{body}ORIGIN\n        1 acgt\n//\n"
+            ))
+        };
+        assert!(gb("").is_ok());
+        assert_eq!(
+            gb("     gene            J00194.1:1..2\n"),
+            Err(Error::GenBank(
+                3,
+                "unsupported or malformed location".to_string()
+            ))
+        );
+        assert_eq!(
+            gb("     gene            2..8\n"),
+            Err(Error::GenBank(3, "location is out of bounds".to_string()))
+        );
+        assert_eq!(
+            AnnotatedRecord::from_genbank("ORIGIN\n"),
+            Err(Error::GenBank(1, "expected a LOCUS line".to_string()))
+        );
+        assert_eq!(
+            AnnotatedRecord::from_genbank("LOCUS       bad 8 bp DNA\nORIGIN\n 1 acgt\n//\n"),
+            Err(Error::GenBank(
+                1,
+                "expected 8 elements, found 4".to_string()
+            ))
+        );
+        assert_eq!(
+            AnnotatedRecord::from_genbank("LOCUS       bad 4 bp DNA\nORIGIN\n 1 acgt\n"),
+            Err(Error::GenBank(
+                1,
+                "record is missing its closing //".to_string()
+            ))
+        );
+
+        // NOTE: Continuations never carry over into the next record
+        for field in ["KEYWORDS    .", "DEFINITION  first"] {
+            assert_eq!(
+                AnnotatedRecord::from_genbank(&format!(
+                    "LOCUS       a 4 bp DNA\n{field}\nORIGIN\n 1 acgt\n//\n\
+                     LOCUS       b 4 bp DNA\n            stray\nORIGIN\n 1 acgt\n//\n"
+                )),
+                Err(Error::GenBank(7, "unexpected continuation".to_string()))
+            );
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    seq::{Error, Kind, Seq},
+    types::Case,
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct KmerSpectrum {
+    k: usize,
+    counts: HashMap<Vec<u8>, usize>,
+}
+
+impl KmerSpectrum {
+    pub fn new<'a>(seqs: impl IntoIterator<Item = &'a Seq>, k: usize) -> Result<Self, Error> {
+        if k == 0 {
+            return Err(Error::KmerLength(k));
+        }
+
+        let mut counts = HashMap::new();
+        for seq in seqs {
+            for (kmer, n) in seq.count_kmers(k)? {
+                *counts.entry(kmer).or_insert(0) += n;
+            }
+        }
+        Ok(Self { k, counts })
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn count(&self, kmer: impl AsRef<[u8]>) -> usize {
+        let kmer = kmer.as_ref().to_ascii_uppercase();
+        self.counts.get(&kmer).copied().unwrap_or(0)
+    }
+
+    // NOTE: Index `i` of the histogram holds the number of distinct k-mers seen exactly `i` times
+    pub fn histogram(&self) -> Vec<usize> {
+        let max = self.counts.values().copied().max().unwrap_or(0);
+        let mut histogram = vec![0; max + 1];
+        for &n in self.counts.values() {
+            histogram[n] += 1;
+        }
+        histogram
+    }
+
+    // NOTE: Erroneous k-mers are rare, so they pile up at the low end of the histogram. The first
+    // valley separates them from the peak of "solid" k-mers coming from real sequence. When there
+    // is no valley, everything is treated as solid so that nothing gets "corrected" blindly
+    pub fn solid_threshold(&self) -> usize {
+        let histogram = self.histogram();
+        (1..histogram.len().saturating_sub(1))
+            .find(|&i| histogram[i] <= histogram[i + 1])
+            .unwrap_or(1)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Correction {
+    pub position: usize,
+    pub from: u8,
+    pub to: u8,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ErrorCorrector<'a> {
+    spectrum: &'a KmerSpectrum,
+    threshold: usize,
+}
+
+impl<'a> ErrorCorrector<'a> {
+    pub fn new(spectrum: &'a KmerSpectrum, threshold: usize) -> Self {
+        Self {
+            spectrum,
+            threshold,
+        }
+    }
+
+    pub fn correct(&self, read: &Seq) -> Result<(Seq, Vec<Correction>), Error> {
+        let bases = match read.kind() {
+            Kind::Dna => b"ACGT",
+            Kind::Rna => b"ACGU",
+            kind => return Err(Error::ErrorCorrection(kind)),
+        };
+
+        let k = self.spectrum.k();
+        let mut bytes = read.normalize_case(Case::Upper).as_bytes().to_vec();
+        let mut corrections = Vec::new();
+        if bytes.len() < k {
+            return Ok((read.clone(), corrections));
+        }
+
+        // NOTE: A base is only suspect if every k-mer covering it is weak, and it is only
+        // corrected if a single substitution makes every one of those k-mers solid. Ties are
+        // broken by the weakest covering k-mer, so the best-supported substitution wins
+        for i in 0..bytes.len() {
+            let windows = i.saturating_sub(k - 1)..=i.min(bytes.len() - k);
+            let weakest = |bytes: &[u8]| {
+                windows
+                    .clone()
+                    .map(|s| self.spectrum.count(&bytes[s..s + k]))
+                    .min()
+                    .unwrap_or(0)
+            };
+            if windows
+                .clone()
+                .any(|s| self.spectrum.count(&bytes[s..s + k]) >= self.threshold)
+            {
+                continue;
+            }
+
+            let from = bytes[i];
+            let best = bases
+                .iter()
+                .filter(|&&b| b != from)
+                .map(|&b| {
+                    bytes[i] = b;
+                    (weakest(&bytes), b)
+                })
+                .filter(|&(n, _)| n >= self.threshold)
+                .max();
+            bytes[i] = from;
+
+            if let Some((_, to)) = best {
+                bytes[i] = to;
+                // NOTE: Corrected bases keep the case of the base they replaced
+                let from = read.as_bytes()[i];
+                let to = if from.is_ascii_lowercase() {
+                    to.to_ascii_lowercase()
+                } else {
+                    to
+                };
+                corrections.push(Correction {
+                    position: i,
+                    from,
+                    to,
+                });
+            }
+        }
+
+        let mut bytes = read.as_bytes().to_vec();
+        for c in &corrections {
+            bytes[c.position] = c.to;
+        }
+        Ok((
+            Seq::from_vec_with_kind(bytes, [read.kind()], read.alphabet())?,
+            corrections,
+        ))
+    }
+}
+
+const MAX_COMPOSITION_K: usize = 12;
+
+// NOTE: Bases are packed two bits at a time in lexicographic order (A < C < G < T / U), so the
+// integer value of a packed k-mer is its rank among all k-mers of the same length
+pub(crate) fn encode_base(base: u8) -> Option<usize> {
+    match base {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' | b'U' | b'u' => Some(3),
+        _ => None,
+    }
+}
+
+impl Seq {
+    // NOTE: Index `i` holds the count of the `i`th k-mer in lexicographic order, so the vector
+    // always has 4^k elements (which is why `k` is capped at 12, or 16 million counts). Any k-mer
+    // containing an ambiguity code is skipped
+    pub fn kmer_composition(&self, k: usize) -> Result<Vec<usize>, Error> {
+        if !self.kind().is_nucleic_acid() {
+            return Err(Error::KmerComposition(self.kind()));
+        }
+        if !(1..=MAX_COMPOSITION_K).contains(&k) {
+            return Err(Error::KmerLength(k));
+        }
+        let len = 4usize.pow(k as u32);
+
+        let mask = len - 1;
+        let mut composition = vec![0; len];
+        let (mut kmer, mut valid) = (0, 0);
+        for &b in self {
+            match encode_base(b) {
+                Some(code) => {
+                    kmer = (kmer << 2 | code) & mask;
+                    valid += 1;
+                    if valid >= k {
+                        composition[kmer] += 1;
+                    }
+                }
+                None => valid = 0,
+            }
+        }
+        Ok(composition)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum SimilarityMetric {
+    Jaccard,
+    Cosine,
+    Euclidean,
+}
+
+impl Seq {
+    // NOTE: Jaccard is the weighted (Ruzicka) form — the sum of the smaller counts over the sum of
+    // the larger — which reduces to the usual set Jaccard when every k-mer occurs at most once.
+    // Jaccard and cosine are similarities (1.0 for identical k-mer profiles), but Euclidean is a
+    // distance (0.0 for identical k-mer profiles). Jaccard and cosine are NaN if either sequence
+    // is shorter than `k`
+    pub fn kmer_similarity(
+        &self,
+        other: &Self,
+        k: usize,
+        metric: SimilarityMetric,
+    ) -> Result<f64, Error> {
+        if self.kind() != other.kind() {
+            return Err(Error::DistanceKindMismatch(self.kind(), other.kind()));
+        }
+
+        let (a, b) = (self.count_kmers(k)?, other.count_kmers(k)?);
+        let pairs = a
+            .iter()
+            .map(|(kmer, &n)| (n as f64, b.get(kmer).copied().unwrap_or(0) as f64))
+            .chain(
+                b.iter()
+                    .filter(|(kmer, _)| !a.contains_key(*kmer))
+                    .map(|(_, &n)| (0.0, n as f64)),
+            );
+        Ok(match metric {
+            SimilarityMetric::Jaccard => {
+                let (min, max) = pairs.fold((0.0, 0.0), |(min, max), (x, y): (f64, f64)| {
+                    (min + x.min(y), max + x.max(y))
+                });
+                min / max
+            }
+            SimilarityMetric::Cosine => {
+                let dot: f64 = pairs.map(|(x, y)| x * y).sum();
+                let norm = |counts: &HashMap<Vec<u8>, usize>| {
+                    counts.values().map(|&n| (n * n) as f64).sum::<f64>().sqrt()
+                };
+                dot / (norm(&a) * norm(&b))
+            }
+            SimilarityMetric::Euclidean => pairs.map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "ATGCGTACGTTAGCCTAGGCATCGATCGGA";
+
+    fn reads() -> Result<Vec<Seq>, Error> {
+        let mut reads = vec![Seq::dna(TEMPLATE)?; 10];
+        reads.push(Seq::dna("ATGCGTACGTTAGCCAAGGCATCGATCGGA")?);
+        Ok(reads)
+    }
+
+    #[test]
+    fn spectrum_counts() -> Result<(), Error> {
+        let spectrum = KmerSpectrum::new(&reads()?, 7)?;
+        assert_eq!(spectrum.k(), 7);
+        assert_eq!(spectrum.count("ATGCGTA"), 11);
+        assert_eq!(spectrum.count("atgcgta"), 11);
+        assert_eq!(spectrum.count("GCCAAGG"), 1);
+        assert_eq!(spectrum.count("AAAAAAA"), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn spectrum_histogram() -> Result<(), Error> {
+        let spectrum = KmerSpectrum::new(&reads()?, 7)?;
+        let histogram = spectrum.histogram();
+        assert_eq!(histogram.len(), 12);
+        assert_eq!(histogram[1], 7);
+        assert_eq!(histogram[10], 7);
+        assert_eq!(histogram[11], 17);
+        assert_eq!(spectrum.solid_threshold(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn spectrum_zero_k() {
+        assert_eq!(KmerSpectrum::new(&[], 0), Err(Error::KmerLength(0)));
+    }
+
+    #[test]
+    fn correct_substitution() -> Result<(), Error> {
+        let reads = reads()?;
+        let spectrum = KmerSpectrum::new(&reads, 7)?;
+        let corrector = ErrorCorrector::new(&spectrum, spectrum.solid_threshold());
+        let (read, corrections) = corrector.correct(&reads[10])?;
+        assert_eq!(read.to_string(), TEMPLATE);
+        assert_eq!(
+            corrections,
+            vec![Correction {
+                position: 15,
+                from: b'A',
+                to: b'T'
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn correct_keeps_case() -> Result<(), Error> {
+        let spectrum = KmerSpectrum::new(&reads()?, 7)?;
+        let corrector = ErrorCorrector::new(&spectrum, 2);
+        let (read, corrections) =
+            corrector.correct(&Seq::dna("atgcgtacgttagccaaggcatcgatcgga")?)?;
+        assert_eq!(read.to_string(), TEMPLATE.to_ascii_lowercase());
+        assert_eq!(corrections[0].to, b't');
+        Ok(())
+    }
+
+    #[test]
+    fn correct_solid_read() -> Result<(), Error> {
+        let reads = reads()?;
+        let spectrum = KmerSpectrum::new(&reads, 7)?;
+        let corrector = ErrorCorrector::new(&spectrum, 2);
+        let (read, corrections) = corrector.correct(&reads[0])?;
+        assert_eq!(read, reads[0]);
+        assert!(corrections.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn correct_protein() -> Result<(), Error> {
+        let spectrum = KmerSpectrum::new(&reads()?, 7)?;
+        let corrector = ErrorCorrector::new(&spectrum, 2);
+        assert_eq!(
+            corrector.correct(&Seq::protein("MAMAPRTEINSTRING")?),
+            Err(Error::ErrorCorrection(Kind::Protein))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn kmer_similarities() -> Result<(), Error> {
+        use SimilarityMetric::*;
+        let a = Seq::dna("ACGTACGT")?;
+        let b = Seq::dna("acgtacgt")?;
+        let c = Seq::dna("AAAATTTT")?;
+        let d = Seq::dna("ACGTTTTT")?;
+        assert_eq!(a.kmer_similarity(&b, 2, Jaccard)?, 1.0);
+        assert!((a.kmer_similarity(&b, 2, Cosine)? - 1.0).abs() < 1e-12);
+        assert_eq!(a.kmer_similarity(&b, 2, Euclidean)?, 0.0);
+        assert_eq!(a.kmer_similarity(&c, 2, Jaccard)?, 0.0);
+        assert_eq!(a.kmer_similarity(&c, 2, Cosine)?, 0.0);
+
+        // NOTE: a = {AC: 2, CG: 2, GT: 2, TA: 1} and d = {AC: 1, CG: 1, GT: 1, TT: 4}
+        assert_eq!(a.kmer_similarity(&d, 2, Jaccard)?, 3.0 / 11.0);
+        assert!(
+            (a.kmer_similarity(&d, 2, Cosine)? - 6.0 / (13f64.sqrt() * 19f64.sqrt())).abs() < 1e-12
+        );
+        assert_eq!(a.kmer_similarity(&d, 2, Euclidean)?, 20f64.sqrt());
+        Ok(())
+    }
+
+    #[test]
+    fn kmer_similarity_errors() -> Result<(), Error> {
+        let dna = Seq::dna("ACGT")?;
+        assert_eq!(
+            dna.kmer_similarity(&Seq::rna("ACGU")?, 2, SimilarityMetric::Cosine),
+            Err(Error::DistanceKindMismatch(Kind::Dna, Kind::Rna))
+        );
+        assert_eq!(
+            dna.kmer_similarity(&dna, 0, SimilarityMetric::Jaccard),
+            Err(Error::KmerLength(0))
+        );
+        assert!(dna
+            .kmer_similarity(&dna, 5, SimilarityMetric::Jaccard)?
+            .is_nan());
+        Ok(())
+    }
+
+    #[test]
+    fn kmer_compositions() -> Result<(), Error> {
+        let composition = Seq::dna("ACGTacgtAA")?.kmer_composition(2)?;
+        assert_eq!(composition.len(), 16);
+        // NOTE: AA, AC, CG, GT, and TA appear in that order (as 0, 1, 6, 11, and 12)
+        let mut expected = vec![0; 16];
+        expected[0] = 1;
+        expected[1] = 2;
+        expected[6] = 2;
+        expected[11] = 2;
+        expected[12] = 2;
+        assert_eq!(composition, expected);
+        assert_eq!(Seq::rna("UUU")?.kmer_composition(1)?, [0, 0, 0, 3]);
+        assert_eq!(
+            Seq::dna_n("ACNGT")?
+                .kmer_composition(2)?
+                .iter()
+                .sum::<usize>(),
+            2
+        );
+        assert!(Seq::dna("AC")?.kmer_composition(3)?.iter().all(|&n| n == 0));
+        Ok(())
+    }
+
+    #[test]
+    fn kmer_composition_errors() -> Result<(), Error> {
+        let dna = Seq::dna("ACGT")?;
+        assert_eq!(dna.kmer_composition(0), Err(Error::KmerLength(0)));
+        assert_eq!(dna.kmer_composition(64), Err(Error::KmerLength(64)));
+        assert_eq!(dna.kmer_composition(13), Err(Error::KmerLength(13)));
+        assert_eq!(dna.kmer_composition(12)?.len(), 1 << 24);
+        assert_eq!(
+            Seq::protein("MAMAPRTEIN")?.kmer_composition(2),
+            Err(Error::KmerComposition(Kind::Protein))
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,35 @@
+pub mod abif;
+pub mod alphabet;
+pub mod annotation;
+pub mod biobrick;
+pub mod checksum;
+pub mod codon;
+pub mod crispr;
 pub mod data;
+pub mod diff;
+pub mod domestication;
+pub mod encoding;
+pub mod enzyme;
+pub mod evolution;
+pub mod gel;
+pub mod genbank;
+pub mod kmer;
+pub mod logo;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod plasmid;
+pub mod promoter;
+pub mod protein;
+pub mod provenance;
+pub mod pwm;
+pub mod rbs;
+pub mod reads;
+pub mod record;
+pub mod region;
 pub mod seq;
+pub mod snapgene;
+pub mod stats;
+pub mod terminator;
+pub mod translation;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pwm::{self, Background, Pwm},
+    seq::{Error, Kind, Seq},
+};
+
+// ===== Sequence Logos ============================================================================
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct LogoLetter {
+    pub symbol: char,
+    pub height: f64,
+}
+
+// NOTE: Letters are sorted from shortest to tallest, which is the order they are stacked in (from
+// the bottom up), and symbols that never occur at a position are left out. The letter heights always
+// add up to the column's information content (in bits)
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct LogoColumn {
+    pub position: usize,
+    pub information: f64,
+    pub letters: Vec<LogoLetter>,
+}
+
+impl Pwm {
+    pub fn logo(&self) -> Vec<LogoColumn> {
+        let information = self.position_information();
+        logo_columns(self.kind(), self.probs(), &information)
+    }
+}
+
+// NOTE: Builds a logo straight from a gapless alignment (or any set of equal-length sites) against a
+// uniform background. With only a handful of sequences, the observed information content is biased
+// upwards, so the small-sample correction from Schneider et al. (1986) is subtracted from every
+// column (clamping at zero)
+pub fn alignment_logo<T: AsRef<Seq>>(
+    kind: Kind,
+    seqs: impl IntoIterator<Item = T>,
+) -> Result<Vec<LogoColumn>, Error> {
+    let seqs: Vec<_> = seqs.into_iter().collect();
+    let pwm = Pwm::from_sites(&seqs, &Background::uniform(kind), 0.0)?;
+    let symbols = pwm::symbols(kind).len() as f64;
+    let correction = (symbols - 1.0) / (2.0 * std::f64::consts::LN_2 * seqs.len() as f64);
+    let information: Vec<_> = pwm
+        .position_information()
+        .iter()
+        .map(|i| (i - correction).max(0.0))
+        .collect();
+    Ok(logo_columns(kind, pwm.probs(), &information))
+}
+
+fn logo_columns(kind: Kind, probs: &[Vec<f64>], information: &[f64]) -> Vec<LogoColumn> {
+    let symbols = pwm::symbols(kind);
+    probs
+        .iter()
+        .zip(information)
+        .enumerate()
+        .map(|(position, (row, &information))| {
+            let mut letters: Vec<_> = symbols
+                .iter()
+                .zip(row)
+                .filter(|(_, &p)| p > 0.0)
+                .map(|(&s, p)| LogoLetter {
+                    symbol: char::from(s),
+                    height: p * information,
+                })
+                .collect();
+            letters.sort_by(|a, b| a.height.total_cmp(&b.height));
+            LogoColumn {
+                position,
+                information,
+                letters,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sites() -> Result<Vec<Seq>, Error> {
+        ["TATAAT", "TATAAT", "TATGAT", "TACAAT"]
+            .into_iter()
+            .map(Seq::dna)
+            .collect()
+    }
+
+    #[test]
+    fn pwm_logo() -> Result<(), Error> {
+        let pwm = Pwm::from_sites(sites()?, &Background::uniform(Kind::Dna), 0.0)?;
+        let logo = pwm.logo();
+        assert_eq!(logo.len(), 6);
+        assert_eq!(
+            logo[0].letters,
+            [LogoLetter {
+                symbol: 'T',
+                height: 2.0
+            }]
+        );
+
+        let column = &logo[2];
+        assert_eq!(column.position, 2);
+        let symbols: Vec<_> = column.letters.iter().map(|l| l.symbol).collect();
+        assert_eq!(symbols, ['C', 'T']);
+        assert!((column.letters[0].height - 0.297_180_6).abs() < 1e-6);
+        assert!((column.letters[1].height - 0.891_541_9).abs() < 1e-6);
+        let total: f64 = column.letters.iter().map(|l| l.height).sum();
+        assert!((total - column.information).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn pwm_logo_background() -> Result<(), Error> {
+        // NOTE: Against an AT-rich background, a conserved A carries less information than a G
+        let background = Background::gc(Kind::Dna, 0.2)?;
+        let pwm = Pwm::from_sites([Seq::dna("AG")?], &background, 0.0)?;
+        let logo = pwm.logo();
+        assert!((logo[0].information - (1.0f64 / 0.4).log2()).abs() < 1e-12);
+        assert!((logo[1].information - (1.0f64 / 0.1).log2()).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn alignment_logos() -> Result<(), Error> {
+        let logo = alignment_logo(Kind::Dna, sites()?)?;
+        let correction = 3.0 / (8.0 * std::f64::consts::LN_2);
+        assert!((logo[0].information - (2.0 - correction)).abs() < 1e-12);
+        assert!((logo[0].letters[0].height - (2.0 - correction)).abs() < 1e-12);
+
+        // NOTE: A single sequence is so small a sample that it carries no information at all
+        let logo = alignment_logo(Kind::Dna, [Seq::dna("TATAAT")?])?;
+        assert!(logo.iter().all(|c| c.information == 0.0));
+
+        let protein = alignment_logo(Kind::Protein, vec![Seq::protein("MAMA")?; 20])?;
+        assert_eq!(protein[0].letters[0].symbol, 'M');
+        assert_eq!(
+            alignment_logo(Kind::Dna, Vec::<Seq>::new()),
+            Err(Error::EmptySeqSet)
+        );
+        Ok(())
+    }
+}
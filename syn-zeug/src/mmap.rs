@@ -0,0 +1,213 @@
+use memmap2::Mmap;
+use std::{borrow::Cow, fs::File, io, ops::Range, path::Path};
+
+use crate::{
+    record::SeqRecord,
+    seq::{Error, Seq},
+};
+
+// ===== Memory-Mapped FASTA =======================================================================
+
+// NOTE: Only the record boundaries are found when a file is opened, so records are never copied or
+// validated until they are converted into a `Seq` or `SeqRecord`
+pub struct MappedFasta {
+    map: Mmap,
+    spans: Vec<RecordSpan>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct RecordSpan {
+    header: Range<usize>,
+    seq: Range<usize>,
+}
+
+impl MappedFasta {
+    // NOTE: Mapping a file is unsafe because the map is undefined if the file is changed (by this
+    // or any other process) while it is mapped, so files must be left alone while they are open
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let map = unsafe { Mmap::map(&file)? };
+        let spans = index(&map)?;
+        Ok(Self { map, spans })
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> Option<FastaView<'_>> {
+        self.spans.get(i).map(|span| self.view(span))
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = FastaView<'_>> {
+        self.spans.iter().map(|span| self.view(span))
+    }
+
+    fn view(&self, span: &RecordSpan) -> FastaView<'_> {
+        FastaView {
+            header: &self.map[span.header.clone()],
+            raw_seq: &self.map[span.seq.clone()],
+        }
+    }
+}
+
+fn index(bytes: &[u8]) -> io::Result<Vec<RecordSpan>> {
+    let mut pos = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    if pos < bytes.len() && bytes[pos] != b'>' {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Expected a FASTA header at byte {pos}"),
+        ));
+    }
+
+    let line_end = |from: usize| {
+        bytes[from..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |i| from + i)
+    };
+    let mut spans = Vec::new();
+    while pos < bytes.len() {
+        let header_end = line_end(pos);
+        let seq_start = (header_end + 1).min(bytes.len());
+        let mut seq_end = seq_start;
+        while seq_end < bytes.len() && bytes[seq_end] != b'>' {
+            seq_end = (line_end(seq_end) + 1).min(bytes.len());
+        }
+        spans.push(RecordSpan {
+            header: pos + 1..header_end,
+            seq: seq_start..seq_end,
+        });
+        pos = seq_end;
+    }
+    Ok(spans)
+}
+
+// ===== Record Views ==============================================================================
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct FastaView<'a> {
+    header: &'a [u8],
+    raw_seq: &'a [u8],
+}
+
+impl<'a> FastaView<'a> {
+    // NOTE: Headers that aren't valid UTF-8 have the offending bytes replaced
+    pub fn id(&self) -> Cow<'a, str> {
+        let header = trim_line_end(self.header);
+        let end = header
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(header.len());
+        String::from_utf8_lossy(&header[..end])
+    }
+
+    pub fn desc(&self) -> Option<Cow<'a, str>> {
+        let header = trim_line_end(self.header);
+        let start = header.iter().position(u8::is_ascii_whitespace)?;
+        let desc = header[start..].trim_ascii_start();
+        (!desc.is_empty()).then(|| String::from_utf8_lossy(desc))
+    }
+
+    // NOTE: This is the sequence exactly as it appears in the file, line breaks and all
+    pub fn raw_seq(&self) -> &'a [u8] {
+        self.raw_seq
+    }
+
+    pub fn seq_len(&self) -> usize {
+        self.seq_bytes().count()
+    }
+
+    pub fn to_seq(&self) -> Result<Seq, Error> {
+        Seq::from_vec(self.seq_bytes().collect())
+    }
+
+    pub fn to_record(&self) -> Result<SeqRecord, Error> {
+        let record = SeqRecord::new(self.id(), self.to_seq()?);
+        Ok(match self.desc() {
+            Some(desc) => record.with_desc(desc),
+            None => record,
+        })
+    }
+
+    fn seq_bytes(&self) -> impl Iterator<Item = u8> + 'a {
+        self.raw_seq
+            .iter()
+            .copied()
+            .filter(|&b| b != b'\n' && b != b'\r')
+    }
+}
+
+fn trim_line_end(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Kind;
+    use std::{env, fs};
+
+    fn mapped(name: &str, contents: &str) -> io::Result<MappedFasta> {
+        let path = env::temp_dir().join(format!("syn-zeug-{}-{name}.fa", std::process::id()));
+        fs::write(&path, contents)?;
+        let fasta = MappedFasta::open(&path);
+        fs::remove_file(&path)?;
+        fasta
+    }
+
+    #[test]
+    fn map_records() -> Result<(), Box<dyn std::error::Error>> {
+        let fasta = mapped(
+            "records",
+            ">chr1 the first one\nACGT\nacgt\n\n>chr2\r\nMAMAPRTEIN\r\n>empty\n",
+        )?;
+        assert_eq!(fasta.len(), 3);
+
+        let chr1 = fasta.get(0).unwrap();
+        assert_eq!(chr1.id(), "chr1");
+        assert_eq!(chr1.desc().as_deref(), Some("the first one"));
+        assert_eq!(chr1.raw_seq(), b"ACGT\nacgt\n\n");
+        assert_eq!(chr1.seq_len(), 8);
+        assert_eq!(chr1.to_seq()?, Seq::dna("ACGTacgt")?);
+
+        let chr2 = fasta.get(1).unwrap().to_record()?;
+        assert_eq!(chr2.id(), "chr2");
+        assert_eq!(chr2.desc(), None);
+        assert_eq!(chr2.seq().kind(), Kind::Protein);
+
+        let empty = fasta.get(2).unwrap();
+        assert_eq!(empty.id(), "empty");
+        assert_eq!(empty.seq_len(), 0);
+        assert!(fasta.get(3).is_none());
+
+        let ids: Vec<_> = fasta.records().map(|r| r.id().into_owned()).collect();
+        assert_eq!(ids, ["chr1", "chr2", "empty"]);
+        Ok(())
+    }
+
+    #[test]
+    fn map_lazy_validation() -> Result<(), Box<dyn std::error::Error>> {
+        let fasta = mapped("lazy", ">good\nACGT\n>bad\nAC-GT\n")?;
+        assert_eq!(fasta.len(), 2);
+        assert!(fasta.get(0).unwrap().to_seq().is_ok());
+        assert!(fasta.get(1).unwrap().to_seq().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn map_invalid_files() -> io::Result<()> {
+        assert!(mapped("empty", "")?.is_empty());
+        assert!(mapped("blank", "\n\n")?.is_empty());
+        let error = mapped("headless", "ACGT\n>seq\nACGT\n").err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        Ok(())
+    }
+}
@@ -0,0 +1,405 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    annotation::AnnotatedRecord,
+    enzyme::Enzyme,
+    region::Region,
+    seq::{Error, Seq},
+    types::{Strand, Topology},
+};
+
+// ===== Plasmid Map Data ==========================================================================
+
+// NOTE: Angles are in degrees, measured clockwise from the top of the map (where the origin is), so
+// renderers never need to know the sequence length. An arc's end angle is always larger than its
+// start angle, since arcs never cross the origin
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Arc {
+    pub start: usize,
+    pub end: usize,
+    pub start_angle: f64,
+    pub end_angle: f64,
+}
+
+// NOTE: Features are given the lowest track (ring) where they don't overlap any feature already on
+// it, so a renderer can draw each track at its own radius without any collisions
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MapFeature {
+    pub kind: String,
+    pub label: Option<String>,
+    pub strand: Strand,
+    pub arcs: Vec<Arc>,
+    pub mid_angle: f64,
+    pub track: usize,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MapSite {
+    pub enzyme: String,
+    pub position: usize,
+    pub angle: f64,
+    pub cuts: usize,
+}
+
+// NOTE: ORFs of circular sequences can run past the origin, in which case they're split into two
+// arcs just like a feature would be
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MapOrf {
+    pub strand: Strand,
+    pub arcs: Vec<Arc>,
+    pub codons: usize,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PlasmidMap {
+    pub name: String,
+    pub len: usize,
+    pub topology: Topology,
+    pub features: Vec<MapFeature>,
+    pub sites: Vec<MapSite>,
+    pub orfs: Vec<MapOrf>,
+    pub tracks: usize,
+}
+
+// ===== Plasmid Map Export ========================================================================
+
+pub struct PlasmidMapper {
+    enzymes: Vec<Enzyme>,
+    max_cuts: usize,
+    min_orf_len: usize,
+}
+
+impl Default for PlasmidMapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlasmidMapper {
+    // NOTE: By default, only the built-in enzymes that cut once (the most useful for cloning) are
+    // shown, along with any ORFs of at least 100 codons
+    pub fn new() -> Self {
+        Self {
+            enzymes: Enzyme::builtins(),
+            max_cuts: 1,
+            min_orf_len: 100,
+        }
+    }
+
+    pub fn enzymes(self, enzymes: impl IntoIterator<Item = Enzyme>) -> Self {
+        Self {
+            enzymes: enzymes.into_iter().collect(),
+            ..self
+        }
+    }
+
+    pub fn max_cuts(self, max_cuts: usize) -> Self {
+        Self { max_cuts, ..self }
+    }
+
+    pub fn min_orf_len(self, min_orf_len: usize) -> Self {
+        Self {
+            min_orf_len,
+            ..self
+        }
+    }
+
+    // NOTE: Sites are placed at the enzyme's top-strand cut (or the start of its site, if it cuts
+    // off the end of a linear sequence)
+    pub fn export(&self, record: &AnnotatedRecord) -> Result<PlasmidMap, Error> {
+        let len = record.len();
+        let arc = |region: &Region| Arc {
+            start: region.start(),
+            end: region.end(),
+            start_angle: angle(region.start(), len),
+            end_angle: angle(region.end(), len),
+        };
+
+        let mut tracks: Vec<Vec<Region>> = Vec::new();
+        let mut features = Vec::new();
+        for feature in record.features() {
+            let parts = feature.parts();
+            let track = tracks
+                .iter()
+                .position(|t| !t.iter().any(|r| parts.iter().any(|p| p.overlaps(r))))
+                .unwrap_or(tracks.len());
+            if track == tracks.len() {
+                tracks.push(Vec::new());
+            }
+            tracks[track].extend_from_slice(parts);
+
+            let arcs: Vec<_> = parts.iter().map(arc).collect();
+            features.push(MapFeature {
+                kind: feature.kind().to_string(),
+                label: feature.label().map(str::to_string),
+                strand: feature.strand(),
+                mid_angle: mid_angle(&arcs, len),
+                arcs,
+                track,
+            });
+        }
+
+        let mut sites = Vec::new();
+        for enzyme in &self.enzymes {
+            let found = enzyme.find_sites(record.seq(), record.topology())?;
+            if found.is_empty() || found.len() > self.max_cuts {
+                continue;
+            }
+            sites.extend(found.iter().map(|site| {
+                let position = site.top_cut.unwrap_or(site.position());
+                MapSite {
+                    enzyme: enzyme.name().to_string(),
+                    position,
+                    angle: angle(position, len),
+                    cuts: found.len(),
+                }
+            }));
+        }
+        sites.sort_by_key(|s| s.position);
+
+        let mut orfs = Vec::new();
+        if record.seq().kind().is_nucleic_acid() {
+            let strands = [
+                (Strand::Forward, record.seq().clone()),
+                (Strand::Reverse, record.seq().reverse_complement()?),
+            ];
+            for (strand, seq) in strands {
+                for (start, orf_len) in find_orfs(&seq, record.topology(), self.min_orf_len)? {
+                    // NOTE: Reverse-strand ORFs are moved back onto forward-strand coordinates
+                    let start = match strand {
+                        Strand::Forward => start,
+                        Strand::Reverse => (2 * len - start - orf_len) % len,
+                    };
+                    let regions = if start + orf_len <= len {
+                        vec![Region::new(start..start + orf_len, strand)?]
+                    } else {
+                        vec![
+                            Region::new(start..len, strand)?,
+                            Region::new(0..start + orf_len - len, strand)?,
+                        ]
+                    };
+                    orfs.push(MapOrf {
+                        strand,
+                        arcs: regions.iter().map(arc).collect(),
+                        codons: orf_len / 3,
+                    });
+                }
+            }
+        }
+        orfs.sort_by_key(|o| o.arcs[0].start);
+
+        Ok(PlasmidMap {
+            name: record.id().to_string(),
+            len,
+            topology: record.topology(),
+            features,
+            sites,
+            orfs,
+            tracks: tracks.len(),
+        })
+    }
+}
+
+// NOTE: The start and length of every ORF in `seq`, read along its forward strand. Circular
+// sequences are searched twice over, so that ORFs running past the origin are found too
+fn find_orfs(seq: &Seq, topology: Topology, min_len: usize) -> Result<Vec<(usize, usize)>, Error> {
+    let len = seq.len();
+    let orfs = match topology {
+        Topology::Linear => seq.find_orfs(min_len)?,
+        Topology::Circular => {
+            let doubled = [seq.as_bytes(), seq.as_bytes()].concat();
+            Seq::from_vec_with_kind(doubled, [seq.kind()], seq.alphabet())?.find_orfs(min_len)?
+        }
+    };
+    Ok(orfs
+        .iter()
+        .map(|(orf, _)| (orf.start(), orf.len()))
+        .filter(|&(start, orf_len)| start < len && orf_len <= len)
+        .collect())
+}
+
+fn angle(position: usize, len: usize) -> f64 {
+    if len == 0 {
+        0.0
+    } else {
+        position as f64 * 360.0 / len as f64
+    }
+}
+
+// NOTE: The angle halfway along a feature (for placing its label), following its parts around the
+// map even when they span the origin
+fn mid_angle(arcs: &[Arc], len: usize) -> f64 {
+    let total: usize = arcs.iter().map(|a| a.end - a.start).sum();
+    let mut remaining = total / 2;
+    for arc in arcs {
+        let span = arc.end - arc.start;
+        if remaining <= span {
+            return angle(arc.start + remaining, len);
+        }
+        remaining -= span;
+    }
+    arcs.first().map_or(0.0, |a| a.start_angle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        annotation::Feature,
+        record::SeqRecord,
+        seq::{Kind, Seq},
+    };
+
+    // NOTE: A 360 bp plasmid, so positions and angles are the same
+    fn test_plasmid() -> Result<AnnotatedRecord, Error> {
+        let orf = format!("ATG{}TAA", "GCT".repeat(50));
+        let seq = format!(
+            "{}GAATTC{orf}{}GGATCC{}GGATCC{}",
+            "T".repeat(20),
+            "T".repeat(40),
+            "T".repeat(20),
+            "T".repeat(106)
+        );
+        let record = SeqRecord::new("pMap", Seq::dna(seq)?);
+        let cds = Feature::new("CDS", Region::new(26..182, Strand::Forward)?)
+            .with_qualifier("label", "gene");
+        let promoter = Feature::new("promoter", Region::new(0..30, Strand::Forward)?);
+        let origin = Feature::from_parts(
+            "rep_origin",
+            [
+                Region::new(340..360, Strand::Forward)?,
+                Region::new(0..28, Strand::Forward)?,
+            ],
+        )?;
+        let terminator = Feature::new("terminator", Region::new(200..240, Strand::Reverse)?);
+        AnnotatedRecord::new(record, Topology::Circular)
+            .with_feature(cds)?
+            .with_feature(promoter)?
+            .with_feature(origin)?
+            .with_feature(terminator)
+    }
+
+    #[test]
+    fn map_features() -> Result<(), Error> {
+        let map = PlasmidMapper::new().export(&test_plasmid()?)?;
+        assert_eq!(map.name, "pMap");
+        assert_eq!(map.len, 360);
+        assert_eq!(map.topology, Topology::Circular);
+        let tracks: Vec<_> = map.features.iter().map(|f| f.track).collect();
+        assert_eq!(tracks, [0, 1, 2, 0]);
+        assert_eq!(map.tracks, 3);
+
+        let cds = &map.features[0];
+        assert_eq!(cds.label.as_deref(), Some("gene"));
+        assert_eq!(cds.arcs[0].start_angle, 26.0);
+        assert_eq!(cds.arcs[0].end_angle, 182.0);
+        assert_eq!(cds.mid_angle, 104.0);
+
+        let origin = &map.features[2];
+        assert_eq!(origin.arcs.len(), 2);
+        assert_eq!(origin.mid_angle, 4.0);
+        Ok(())
+    }
+
+    #[test]
+    fn map_sites_and_orfs() -> Result<(), Error> {
+        let record = test_plasmid()?;
+        let map = PlasmidMapper::new().export(&record)?;
+        let sites: Vec<_> = map
+            .sites
+            .iter()
+            .map(|s| (s.enzyme.as_str(), s.position))
+            .collect();
+        assert_eq!(sites, [("EcoRI", 21)]);
+
+        let map = PlasmidMapper::new()
+            .max_cuts(2)
+            .min_orf_len(50)
+            .export(&record)?;
+        let bam_hi: Vec<_> = map.sites.iter().filter(|s| s.enzyme == "BamHI").collect();
+        assert_eq!(bam_hi.len(), 2);
+        assert_eq!(bam_hi[0].cuts, 2);
+
+        // NOTE: The reverse strand has an ATG opposite the CDS's start codon, which reads back across
+        // the origin and through the poly(A) runs until it stops inside the CDS
+        assert_eq!(map.orfs.len(), 2);
+        assert_eq!(map.orfs[0].strand, Strand::Forward);
+        assert_eq!(map.orfs[0].arcs.len(), 1);
+        assert_eq!(
+            (map.orfs[0].arcs[0].start, map.orfs[0].arcs[0].end),
+            (26, 182)
+        );
+        assert_eq!(map.orfs[0].codons, 52);
+        assert_eq!(map.orfs[1].strand, Strand::Reverse);
+        let arcs: Vec<_> = map.orfs[1].arcs.iter().map(|a| (a.start, a.end)).collect();
+        assert_eq!(arcs, [(178, 360), (0, 28)]);
+        assert_eq!(map.orfs[1].codons, 70);
+        let map = PlasmidMapper::new().min_orf_len(75).export(&record)?;
+        assert!(map.orfs.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn map_orfs_across_the_origin() -> Result<(), Error> {
+        // NOTE: A 60 codon ORF starting 30 bp before the origin, on each strand in turn
+        let orf = format!("ATG{}TAA", "GCT".repeat(58));
+        let (head, tail) = orf.split_at(30);
+        let seq = Seq::dna(format!("{tail}{}{head}", "T".repeat(120)))?;
+        for (strand, seq) in [
+            (Strand::Forward, seq.clone()),
+            (Strand::Reverse, seq.reverse_complement()?),
+        ] {
+            let record = AnnotatedRecord::new(SeqRecord::new("pOri", seq), Topology::Circular);
+            let map = PlasmidMapper::new().min_orf_len(50).export(&record)?;
+            assert_eq!(map.orfs.len(), 1);
+            assert_eq!(map.orfs[0].strand, strand);
+            assert_eq!(map.orfs[0].codons, 60);
+            let arcs: Vec<_> = map.orfs[0].arcs.iter().map(|a| (a.start, a.end)).collect();
+            match strand {
+                Strand::Forward => assert_eq!(arcs, [(270, 300), (0, 150)]),
+                Strand::Reverse => assert_eq!(arcs, [(150, 300), (0, 30)]),
+            }
+
+            let linear = AnnotatedRecord::new(record.record().clone(), Topology::Linear);
+            let map = PlasmidMapper::new().min_orf_len(50).export(&linear)?;
+            assert!(map.orfs.is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn map_custom_enzymes() -> Result<(), Error> {
+        let record = test_plasmid()?;
+        let enzymes = [Enzyme::new("Custom", "GCTGCT", 3, 3)?];
+        let map = PlasmidMapper::new()
+            .enzymes(enzymes)
+            .max_cuts(100)
+            .export(&record)?;
+        assert!(map.sites.iter().all(|s| s.enzyme == "Custom"));
+        assert_eq!(map.sites.len(), 49);
+        Ok(())
+    }
+
+    #[test]
+    fn map_to_json() -> Result<(), Error> {
+        let map = PlasmidMapper::new().export(&test_plasmid()?)?;
+        let json = serde_json::to_value(&map).unwrap();
+        assert_eq!(json["features"][0]["arcs"][0]["start_angle"], 26.0);
+        assert_eq!(json["sites"][0]["enzyme"], "EcoRI");
+        assert_eq!(serde_json::from_value::<PlasmidMap>(json).unwrap(), map);
+        Ok(())
+    }
+
+    #[test]
+    fn map_protein() -> Result<(), Error> {
+        let record = AnnotatedRecord::new(
+            SeqRecord::new("protein", Seq::protein("MAMAPRTEIN")?),
+            Topology::Linear,
+        );
+        assert_eq!(
+            PlasmidMapper::new().export(&record),
+            Err(Error::EnzymeSearch(Kind::Protein))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::{
+    data::{KNOWN_PROMOTERS, SIGMA70_MINUS_10, SIGMA70_MINUS_35},
+    pwm::{Background, Pwm},
+    region::Region,
+    seq::{Error, Kind, Seq},
+    types::Strand,
+};
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum PromoterKind {
+    Sigma70 {
+        minus_35: usize,
+        minus_10: usize,
+        spacing: usize,
+    },
+    Known(String),
+}
+
+// NOTE: Scores are relative (between 0.0 and 1.0), with σ70 promoters scoring the mean of their
+// two boxes and exact matches to known promoters always scoring 1.0
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Promoter {
+    pub kind: PromoterKind,
+    pub region: Region,
+    pub score: f64,
+}
+
+// NOTE: Each box position is given the consensus base's frequency, with the rest split evenly
+// between the other three bases
+fn consensus_pwm(consensus: &[(u8, f64)]) -> Pwm {
+    let counts: Vec<Vec<f64>> = consensus
+        .iter()
+        .map(|&(base, percent)| {
+            b"ACGT"
+                .iter()
+                .map(|&b| {
+                    if b == base {
+                        percent
+                    } else {
+                        (100.0 - percent) / 3.0
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    Pwm::from_counts(&counts, &Background::uniform(Kind::Dna), 0.0)
+        .expect("Every row should have one count per base")
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PromoterFinder {
+    minus_35: Pwm,
+    minus_10: Pwm,
+    min_score: f64,
+    spacing: Range<usize>,
+}
+
+impl Default for PromoterFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PromoterFinder {
+    // NOTE: By default both boxes need a relative score of at least 0.8, and must be separated by
+    // a 15–19 bp spacer (17 bp being optimal)
+    pub fn new() -> Self {
+        Self {
+            minus_35: consensus_pwm(&SIGMA70_MINUS_35),
+            minus_10: consensus_pwm(&SIGMA70_MINUS_10),
+            min_score: 0.8,
+            spacing: 15..20,
+        }
+    }
+
+    pub fn min_score(self, min_score: f64) -> Self {
+        Self { min_score, ..self }
+    }
+
+    pub fn spacing(self, spacing: Range<usize>) -> Self {
+        Self { spacing, ..self }
+    }
+
+    pub fn find(&self, seq: &Seq) -> Result<Vec<Promoter>, Error> {
+        if seq.kind() != Kind::Dna {
+            return Err(Error::PromoterSearch(seq.kind()));
+        }
+
+        let mut promoters = self.find_known(seq)?;
+        promoters.extend(self.find_sigma70(seq)?);
+        promoters.sort_by_key(|p| (p.region.start(), p.region.strand()));
+        Ok(promoters)
+    }
+
+    fn find_known(&self, seq: &Seq) -> Result<Vec<Promoter>, Error> {
+        let mut promoters = Vec::new();
+        for (name, site) in KNOWN_PROMOTERS {
+            let site = Seq::dna(site)?;
+            for (strand, site) in [
+                (Strand::Forward, site.clone()),
+                (Strand::Reverse, site.reverse_complement()?),
+            ] {
+                let len = site.len();
+                let matches = seq
+                    .as_bytes()
+                    .windows(len)
+                    .enumerate()
+                    .filter(|(_, w)| w.eq_ignore_ascii_case(site.as_bytes()));
+                for (i, _) in matches {
+                    promoters.push(Promoter {
+                        kind: PromoterKind::Known(name.to_string()),
+                        region: Region::new(i..i + len, strand)?,
+                        score: 1.0,
+                    });
+                }
+            }
+        }
+        Ok(promoters)
+    }
+
+    fn find_sigma70(&self, seq: &Seq) -> Result<Vec<Promoter>, Error> {
+        let threshold = |pwm: &Pwm| {
+            let (min, max) = (pwm.min_score(), pwm.max_score());
+            min + self.min_score * (max - min)
+        };
+        let hits_35 = self.minus_35.scan(seq, threshold(&self.minus_35))?;
+        let hits_10 = self.minus_10.scan(seq, threshold(&self.minus_10))?;
+        let (len_35, len_10) = (self.minus_35.len(), self.minus_10.len());
+
+        let mut candidates = Vec::new();
+        for h35 in &hits_35 {
+            for h10 in hits_10.iter().filter(|h| h.strand == h35.strand) {
+                // NOTE: On the reverse strand, the −35 box sits downstream of the −10 box
+                let (upstream, upstream_len, downstream, downstream_len) = match h35.strand {
+                    Strand::Forward => (h35.position, len_35, h10.position, len_10),
+                    Strand::Reverse => (h10.position, len_10, h35.position, len_35),
+                };
+                let Some(spacing) = downstream.checked_sub(upstream + upstream_len) else {
+                    continue;
+                };
+                if !self.spacing.contains(&spacing) {
+                    continue;
+                }
+                let score = (self.minus_35.relative_score(h35.score)
+                    + self.minus_10.relative_score(h10.score))
+                    / 2.0;
+                candidates.push(Promoter {
+                    kind: PromoterKind::Sigma70 {
+                        minus_35: h35.position,
+                        minus_10: h10.position,
+                        spacing,
+                    },
+                    region: Region::new(upstream..downstream + downstream_len, h35.strand)?,
+                    score,
+                });
+            }
+        }
+
+        // NOTE: Weak boxes near a strong pair produce a pile of overlapping candidates, so only the
+        // best of any overlapping candidates on the same strand are kept
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        let mut promoters: Vec<Promoter> = Vec::new();
+        for candidate in candidates {
+            if promoters.iter().all(|p| {
+                p.region.strand() != candidate.region.strand()
+                    || !p.region.overlaps(&candidate.region)
+            }) {
+                promoters.push(candidate);
+            }
+        }
+        Ok(promoters)
+    }
+}
+
+impl Seq {
+    pub fn find_promoters(&self) -> Result<Vec<Promoter>, Error> {
+        PromoterFinder::new().find(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const J23119: &str = "TTGACAGCTAGCTCAGTCCTAGGTATAATGCTAGC";
+
+    #[test]
+    fn find_anderson_promoter() -> Result<(), Error> {
+        let promoters = Seq::dna(format!("GGGG{J23119}GGGG"))?.find_promoters()?;
+        assert_eq!(
+            promoters,
+            [
+                Promoter {
+                    kind: PromoterKind::Known("J23119".to_string()),
+                    region: Region::new(4..39, Strand::Forward)?,
+                    score: 1.0,
+                },
+                Promoter {
+                    kind: PromoterKind::Sigma70 {
+                        minus_35: 4,
+                        minus_10: 27,
+                        spacing: 17,
+                    },
+                    region: Region::new(4..33, Strand::Forward)?,
+                    score: 1.0,
+                }
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_reverse_promoters() -> Result<(), Error> {
+        let seq = Seq::dna(format!("GGGG{J23119}GGGG"))?.reverse_complement()?;
+        let promoters = seq.find_promoters()?;
+        assert_eq!(promoters.len(), 2);
+        assert!(promoters
+            .iter()
+            .all(|p| p.region.strand() == Strand::Reverse));
+        assert_eq!(promoters[0].region.range(), 4..39);
+        assert_eq!(promoters[1].region.range(), 10..39);
+        assert_eq!(
+            promoters[1].kind,
+            PromoterKind::Sigma70 {
+                minus_35: 33,
+                minus_10: 10,
+                spacing: 17
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_phage_promoters() -> Result<(), Error> {
+        let seq = Seq::dna("ccTAATACGACTCACTATAGggATTTAGGTGACACTATAGcc")?;
+        let promoters = PromoterFinder::new().find(&seq)?;
+        let names: Vec<_> = promoters
+            .iter()
+            .map(|p| (&p.kind, p.region.range()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                (&PromoterKind::Known("T7".to_string()), 2..20),
+                (&PromoterKind::Known("SP6".to_string()), 22..40)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sigma70_spacing() -> Result<(), Error> {
+        let seq = Seq::dna("TTGACAGCTAGCTCAGTCCTATATAATGC")?;
+        let sigma70 = |finder: PromoterFinder| -> Result<Vec<_>, Error> {
+            Ok(finder
+                .find(&seq)?
+                .into_iter()
+                .filter_map(|p| match p.kind {
+                    PromoterKind::Sigma70 { spacing, .. } => Some(spacing),
+                    _ => None,
+                })
+                .collect())
+        };
+        assert_eq!(sigma70(PromoterFinder::new())?, [15]);
+        assert!(sigma70(PromoterFinder::new().spacing(16..20))?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn promoter_non_dna() -> Result<(), Error> {
+        assert_eq!(
+            Seq::rna("UUGACA")?.find_promoters(),
+            Err(Error::PromoterSearch(Kind::Rna))
+        );
+        Ok(())
+    }
+}
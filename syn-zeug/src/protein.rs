@@ -0,0 +1,385 @@
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+use crate::{
+    data::{
+        CHOU_FASMAN, KYTE_DOOLITTLE, PKA_C_TERMINUS, PKA_NEGATIVE, PKA_N_TERMINUS, PKA_POSITIVE,
+    },
+    region::Region,
+    seq::{Error, Kind, Seq},
+    types::Strand,
+};
+
+// ===== Hydropathy ================================================================================
+
+impl Seq {
+    // NOTE: Element `i` of the profile is the mean hydropathy of the window starting at residue `i`.
+    // Residues without a hydropathy index (like X) are left out of their windows' means, and
+    // windows with none of them at all have no mean
+    pub fn hydropathy_profile(&self, window: usize) -> Result<Vec<Option<f64>>, Error> {
+        if self.kind() != Kind::Protein {
+            return Err(Error::Hydropathy(self.kind()));
+        }
+        if window == 0 {
+            return Err(Error::WindowSize(window));
+        }
+
+        let scores: Vec<_> = self
+            .as_bytes()
+            .iter()
+            .map(|b| KYTE_DOOLITTLE.get(&b.to_ascii_uppercase()).copied())
+            .collect();
+        Ok(scores
+            .windows(window)
+            .map(|w| {
+                let known: Vec<_> = w.iter().flatten().collect();
+                (!known.is_empty()).then(|| known.iter().copied().sum::<f64>() / known.len() as f64)
+            })
+            .collect())
+    }
+}
+
+// ===== Charge ====================================================================================
+
+// NOTE: The fraction of a group with the given pKa that has lost its proton at the given pH
+fn deprotonated(pka: f64, ph: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(pka - ph))
+}
+
+impl Seq {
+    // NOTE: Charges are estimated with the Henderson–Hasselbalch equation, treating every ionisable
+    // group as independent of the others
+    pub fn net_charge(&self, ph: f64) -> Result<f64, Error> {
+        if self.kind() != Kind::Protein {
+            return Err(Error::Charge(self.kind()));
+        }
+        if self.is_empty() {
+            return Ok(0.0);
+        }
+
+        let side_chains: f64 = self
+            .as_bytes()
+            .iter()
+            .map(|b| {
+                let b = b.to_ascii_uppercase();
+                if let Some(&pka) = PKA_POSITIVE.get(&b) {
+                    1.0 - deprotonated(pka, ph)
+                } else if let Some(&pka) = PKA_NEGATIVE.get(&b) {
+                    -deprotonated(pka, ph)
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+        Ok(side_chains + 1.0 - deprotonated(PKA_N_TERMINUS, ph) - deprotonated(PKA_C_TERMINUS, ph))
+    }
+
+    // NOTE: Net charge only ever falls as the pH rises, so the isoelectric point can be found by
+    // bisecting the 0–14 pH range
+    pub fn isoelectric_point(&self) -> Result<f64, Error> {
+        let (mut lo, mut hi) = (0.0, 14.0);
+        while hi - lo > 1e-4 {
+            let mid = (lo + hi) / 2.0;
+            if self.net_charge(mid)? > 0.0 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Ok((lo + hi) / 2.0)
+    }
+
+    // NOTE: Returns `points` evenly spaced (pH, charge) pairs, including both ends of the range
+    pub fn charge_curve(
+        &self,
+        ph: RangeInclusive<f64>,
+        points: usize,
+    ) -> Result<Vec<(f64, f64)>, Error> {
+        let (start, end) = ph.into_inner();
+        let step = if points > 1 {
+            (end - start) / (points - 1) as f64
+        } else {
+            0.0
+        };
+        (0..points)
+            .map(|i| {
+                let ph = start + step * i as f64;
+                Ok((ph, self.net_charge(ph)?))
+            })
+            .collect()
+    }
+}
+
+// ===== Transmembrane Helix Prediction ============================================================
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TmSegment {
+    pub region: Region,
+    pub peak_hydropathy: f64,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TmHelixFinder {
+    window: usize,
+    threshold: f64,
+}
+
+impl Default for TmHelixFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TmHelixFinder {
+    // NOTE: The defaults are those suggested by Kyte & Doolittle (1982): a 19 residue window (about
+    // the length of a helix spanning the membrane) with a mean hydropathy above 1.6
+    pub fn new() -> Self {
+        Self {
+            window: 19,
+            threshold: 1.6,
+        }
+    }
+
+    pub fn window(self, window: usize) -> Self {
+        Self { window, ..self }
+    }
+
+    pub fn threshold(self, threshold: f64) -> Self {
+        Self { threshold, ..self }
+    }
+
+    // NOTE: Overlapping windows above the threshold are merged into a single segment
+    pub fn find(&self, protein: &Seq) -> Result<Vec<TmSegment>, Error> {
+        let profile = protein.hydropathy_profile(self.window)?;
+        let mut segments: Vec<TmSegment> = Vec::new();
+        for (i, &h) in profile.iter().enumerate() {
+            let Some(h) = h.filter(|&h| h > self.threshold) else {
+                continue;
+            };
+            let end = i + self.window;
+            match segments.last_mut() {
+                Some(last) if last.region.end() > i => {
+                    last.region = Region::new(last.region.start()..end, Strand::Forward)?;
+                    last.peak_hydropathy = last.peak_hydropathy.max(h);
+                }
+                _ => segments.push(TmSegment {
+                    region: Region::new(i..end, Strand::Forward)?,
+                    peak_hydropathy: h,
+                }),
+            }
+        }
+        Ok(segments)
+    }
+}
+
+// ===== Secondary Structure Propensity ============================================================
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum SecondaryStructure {
+    Helix,
+    Sheet,
+    Coil,
+}
+
+// NOTE: Each vector holds one (window-averaged) propensity per residue, with values above 1.0
+// favouring that conformation. Chou & Fasman's turn parameters stand in for the coil propensity
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SecondaryStructureProfile {
+    pub helix: Vec<f64>,
+    pub sheet: Vec<f64>,
+    pub coil: Vec<f64>,
+}
+
+impl SecondaryStructureProfile {
+    // NOTE: A residue is called as helix or sheet using Chou & Fasman's nucleation thresholds
+    // (1.03 and 1.05), with ties going to the helix, otherwise it's called as coil. This skips the
+    // original method's nucleation and extension rules, so is only a rough first pass
+    pub fn predict(&self) -> Vec<SecondaryStructure> {
+        self.helix
+            .iter()
+            .zip(&self.sheet)
+            .map(|(&helix, &sheet)| {
+                if helix >= 1.03 && helix >= sheet {
+                    SecondaryStructure::Helix
+                } else if sheet >= 1.05 {
+                    SecondaryStructure::Sheet
+                } else {
+                    SecondaryStructure::Coil
+                }
+            })
+            .collect()
+    }
+}
+
+impl Seq {
+    // NOTE: Propensities are averaged over a window centred on each residue (truncated at the ends
+    // of the sequence), and residues without Chou–Fasman parameters (like X) are left out of the
+    // averages. A window of 1 gives the raw, per-residue parameters
+    pub fn secondary_structure_profile(
+        &self,
+        window: usize,
+    ) -> Result<SecondaryStructureProfile, Error> {
+        if self.kind() != Kind::Protein {
+            return Err(Error::StructurePrediction(self.kind()));
+        }
+        if window == 0 {
+            return Err(Error::WindowSize(window));
+        }
+
+        let params: Vec<_> = self
+            .as_bytes()
+            .iter()
+            .map(|b| CHOU_FASMAN.get(&b.to_ascii_uppercase()).copied())
+            .collect();
+        let mut profile = SecondaryStructureProfile {
+            helix: Vec::with_capacity(params.len()),
+            sheet: Vec::with_capacity(params.len()),
+            coil: Vec::with_capacity(params.len()),
+        };
+        for i in 0..params.len() {
+            let start = i.saturating_sub(window / 2);
+            let end = (i + window - window / 2).min(params.len());
+            let known: Vec<_> = params[start..end].iter().flatten().collect();
+            let mean = |f: fn(&(f64, f64, f64)) -> f64| {
+                known.iter().map(|p| f(p)).sum::<f64>() / known.len() as f64
+            };
+            profile.helix.push(mean(|p| p.0));
+            profile.sheet.push(mean(|p| p.1));
+            profile.coil.push(mean(|p| p.2));
+        }
+        Ok(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hydropathy_profiles() -> Result<(), Error> {
+        let protein = Seq::protein("IIRR")?;
+        assert_eq!(
+            protein.hydropathy_profile(2)?,
+            [Some(4.5), Some(0.0), Some(-4.5)]
+        );
+        assert_eq!(
+            Seq::protein_iupac("IXL")?.hydropathy_profile(2)?,
+            [Some(4.5), Some(3.8)]
+        );
+        assert_eq!(
+            Seq::protein_iupac("IXXL")?.hydropathy_profile(2)?,
+            [Some(4.5), None, Some(3.8)]
+        );
+        assert!(protein.hydropathy_profile(5)?.is_empty());
+        assert_eq!(protein.hydropathy_profile(0), Err(Error::WindowSize(0)));
+        assert_eq!(
+            Seq::dna("ACGT")?.hydropathy_profile(2),
+            Err(Error::Hydropathy(Kind::Dna))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn net_charges() -> Result<(), Error> {
+        let lysine = Seq::protein("K")?;
+        assert!((lysine.net_charge(7.0)? - 0.9758).abs() < 1e-4);
+        assert!((lysine.net_charge(0.0)? - 2.0).abs() < 1e-3);
+        assert!((lysine.net_charge(14.0)? + 1.0).abs() < 1e-3);
+        assert!(Seq::protein("DEDE")?.net_charge(7.0)? < -3.9);
+        assert_eq!(Seq::protein("")?.net_charge(7.0)?, 0.0);
+        assert_eq!(
+            Seq::dna("ACGT")?.net_charge(7.0),
+            Err(Error::Charge(Kind::Dna))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn isoelectric_points() -> Result<(), Error> {
+        let acidic = Seq::protein("MDDEEDAS")?;
+        let basic = Seq::protein("MKKRRKAS")?;
+        let pi = acidic.isoelectric_point()?;
+        assert!(pi < 4.0);
+        assert!(acidic.net_charge(pi)?.abs() < 1e-3);
+        assert!(basic.isoelectric_point()? > 11.0);
+        assert!((Seq::protein("GG")?.isoelectric_point()? - 6.1).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn charge_curves() -> Result<(), Error> {
+        let protein = Seq::protein("MKHDECYR")?;
+        let curve = protein.charge_curve(2.0..=12.0, 11)?;
+        assert_eq!(curve.len(), 11);
+        assert_eq!(curve[0].0, 2.0);
+        assert_eq!(curve[10].0, 12.0);
+        assert_eq!(curve[3], (5.0, protein.net_charge(5.0)?));
+        assert!(curve.windows(2).all(|w| w[0].1 > w[1].1));
+        assert_eq!(
+            protein.charge_curve(7.0..=7.0, 1)?,
+            [(7.0, protein.net_charge(7.0)?)]
+        );
+        assert!(protein.charge_curve(2.0..=12.0, 0)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn find_tm_helices() -> Result<(), Error> {
+        let soluble = "MKKRDEDEKRSTNQ";
+        let helix = "LLIVALLFAVIGLLAVLIV";
+        let protein = Seq::protein(format!("{soluble}{helix}{soluble}{helix}KRDE"))?;
+        let segments = TmHelixFinder::new().find(&protein)?;
+        assert_eq!(segments.len(), 2);
+        assert!(segments[0].region.contains(14) && segments[0].region.contains(32));
+        assert!(segments[1].region.contains(47) && segments[1].region.contains(65));
+        assert!(segments.iter().all(|s| s.peak_hydropathy > 3.0));
+        let unknown = Seq::protein_iupac("X".repeat(30))?;
+        assert!(TmHelixFinder::new().find(&unknown)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn find_no_tm_helices() -> Result<(), Error> {
+        let protein = Seq::protein("MKKRDEDEKRSTNQGSGSGSMKKRDEDEKRSTNQ")?;
+        assert!(TmHelixFinder::new().find(&protein)?.is_empty());
+        let short = Seq::protein("MKLLIVALLFAVKR")?;
+        assert!(TmHelixFinder::new().find(&short)?.is_empty());
+        assert_eq!(TmHelixFinder::new().window(5).find(&short)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn secondary_structure_profiles() -> Result<(), Error> {
+        let raw = Seq::protein("AVG")?.secondary_structure_profile(1)?;
+        assert_eq!(raw.helix, [1.42, 1.06, 0.57]);
+        assert_eq!(raw.sheet, [0.83, 1.70, 0.75]);
+        assert_eq!(raw.coil, [0.66, 0.50, 1.56]);
+
+        let smoothed = Seq::protein_iupac("AXE")?.secondary_structure_profile(3)?;
+        assert_eq!(smoothed.helix.len(), 3);
+        assert!((smoothed.helix[1] - (1.42 + 1.51) / 2.0).abs() < 1e-12);
+        assert!((smoothed.helix[0] - 1.42).abs() < 1e-12);
+
+        assert_eq!(
+            Seq::protein("AVG")?.secondary_structure_profile(0),
+            Err(Error::WindowSize(0))
+        );
+        assert_eq!(
+            Seq::rna("ACGU")?.secondary_structure_profile(5),
+            Err(Error::StructurePrediction(Kind::Rna))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn predict_secondary_structure() -> Result<(), Error> {
+        use SecondaryStructure::*;
+        let protein = Seq::protein("EEAMLKAEEGPNGSGVIVYVIFT")?;
+        let predicted = protein.secondary_structure_profile(5)?.predict();
+        assert_eq!(predicted.len(), protein.len());
+        assert!(predicted[..6].iter().all(|&s| s == Helix));
+        assert!(predicted[10..12].iter().all(|&s| s == Coil));
+        assert!(predicted[17..].iter().all(|&s| s == Sheet));
+        Ok(())
+    }
+}
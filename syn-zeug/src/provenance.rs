@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops::Range};
+
+use crate::{
+    region::Region,
+    seq::{Error, Kind, Seq},
+    translation::TranslationOptions,
+    types::Case,
+};
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Operation {
+    Subseq(Range<usize>),
+    Extract(Region),
+    Rev,
+    ReverseComplement,
+    NormalizeCase(Case),
+    SoftMask(Vec<Range<usize>>),
+    HardMask(Vec<Range<usize>>),
+    Convert(Kind),
+    Translate(TranslationOptions),
+    Custom(String),
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranges = |ranges: &[Range<usize>]| {
+            let ranges: Vec<_> = ranges.iter().map(|r| format!("{r:?}")).collect();
+            ranges.join(", ")
+        };
+        match self {
+            Operation::Subseq(range) => write!(f, "subseq({range:?})")?,
+            Operation::Extract(region) => write!(f, "extract({region})")?,
+            Operation::Rev => write!(f, "rev")?,
+            Operation::ReverseComplement => write!(f, "revcomp")?,
+            Operation::NormalizeCase(Case::Upper) => write!(f, "uppercase")?,
+            Operation::NormalizeCase(Case::Lower) => write!(f, "lowercase")?,
+            Operation::SoftMask(r) => write!(f, "soft_mask({})", ranges(r))?,
+            Operation::HardMask(r) => write!(f, "hard_mask({})", ranges(r))?,
+            Operation::Convert(kind) => write!(f, "convert({kind})")?,
+            Operation::Translate(options) => write!(f, "translate({options})")?,
+            Operation::Custom(name) => write!(f, "{name}")?,
+        }
+        Ok(())
+    }
+}
+
+// NOTE: This is an opt-in wrapper, so plain `Seq`s don't pay for the history. Every tool here
+// mirrors a chainable tool on `Seq`, and anything else can be recorded using `apply`
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct TrackedSeq {
+    seq: Seq,
+    operations: Vec<Operation>,
+}
+
+impl TrackedSeq {
+    pub fn new(seq: Seq) -> Self {
+        Self {
+            seq,
+            operations: Vec::new(),
+        }
+    }
+
+    // ===== Getters ===============================================================================
+
+    pub fn seq(&self) -> &Seq {
+        &self.seq
+    }
+
+    pub fn into_seq(self) -> Seq {
+        self.seq
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    // NOTE: A human-readable summary like "revcomp → convert(RNA) → convert(Protein)"
+    pub fn history(&self) -> String {
+        let steps: Vec<_> = self.operations.iter().map(|op| op.to_string()).collect();
+        steps.join(" → ")
+    }
+
+    // ===== Chainable Tools =======================================================================
+
+    pub fn apply(
+        &self,
+        operation: Operation,
+        f: impl FnOnce(&Seq) -> Result<Seq, Error>,
+    ) -> Result<Self, Error> {
+        let seq = f(&self.seq)?;
+        let mut operations = self.operations.clone();
+        operations.push(operation);
+        Ok(Self { seq, operations })
+    }
+
+    fn record(&self, operation: Operation, seq: Seq) -> Self {
+        self.apply(operation, |_| Ok(seq))
+            .expect("Recording an infallible operation should never fail")
+    }
+
+    pub fn subseq(&self, range: Range<usize>) -> Self {
+        self.record(Operation::Subseq(range.clone()), self.seq.subseq(range))
+    }
+
+    pub fn extract(&self, region: &Region) -> Result<Self, Error> {
+        self.apply(Operation::Extract(*region), |s| s.extract(region))
+    }
+
+    pub fn rev(&self) -> Self {
+        self.record(Operation::Rev, self.seq.rev())
+    }
+
+    pub fn reverse_complement(&self) -> Result<Self, Error> {
+        self.apply(Operation::ReverseComplement, Seq::reverse_complement)
+    }
+
+    pub fn normalize_case(&self, case: Case) -> Self {
+        self.record(
+            Operation::NormalizeCase(case),
+            self.seq.normalize_case(case),
+        )
+    }
+
+    pub fn soft_mask(&self, ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        let ranges: Vec<_> = ranges.into_iter().collect();
+        let seq = self.seq.soft_mask(ranges.clone());
+        self.record(Operation::SoftMask(ranges), seq)
+    }
+
+    pub fn hard_mask(&self, ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        let ranges: Vec<_> = ranges.into_iter().collect();
+        let seq = self.seq.hard_mask(ranges.clone());
+        self.record(Operation::HardMask(ranges), seq)
+    }
+
+    pub fn convert(&self, kind: Kind) -> Result<Self, Error> {
+        self.apply(Operation::Convert(kind), |s| s.convert(kind))
+    }
+
+    pub fn translate(&self, options: &TranslationOptions) -> Result<Self, Error> {
+        self.apply(Operation::Translate(*options), |s| s.translate(options))
+    }
+}
+
+impl From<Seq> for TrackedSeq {
+    fn from(seq: Seq) -> Self {
+        Self::new(seq)
+    }
+}
+
+impl AsRef<Seq> for TrackedSeq {
+    fn as_ref(&self) -> &Seq {
+        &self.seq
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Strand;
+
+    #[test]
+    fn track_operations() -> Result<(), Error> {
+        let dna = Seq::dna("CATGGCCATTTA")?;
+        let tracked = TrackedSeq::new(dna.clone())
+            .reverse_complement()?
+            .convert(Kind::Rna)?
+            .convert(Kind::Protein)?;
+        assert_eq!(
+            tracked.seq(),
+            &dna.reverse_complement()?.convert(Kind::Protein)?
+        );
+        assert_eq!(
+            tracked.operations(),
+            [
+                Operation::ReverseComplement,
+                Operation::Convert(Kind::Rna),
+                Operation::Convert(Kind::Protein)
+            ]
+        );
+        assert_eq!(
+            tracked.history(),
+            "revcomp → convert(RNA) → convert(Protein)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn track_translation() -> Result<(), Error> {
+        use crate::translation::StopCodons;
+
+        let options = TranslationOptions::new().stop_codons(StopCodons::ToFirstStop);
+        let tracked = TrackedSeq::new(Seq::dna("ATGGCCTAAGGG")?).translate(&options)?;
+        assert_eq!(tracked.seq(), &Seq::protein("MA")?);
+        assert_eq!(tracked.operations(), [Operation::Translate(options)]);
+        assert_eq!(
+            tracked.history(),
+            "translate(to_first_stop, resolve_ambiguous, ignore_partial)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn track_infallible_operations() -> Result<(), Error> {
+        let tracked = TrackedSeq::from(Seq::dna("acgtACGTacgt")?)
+            .normalize_case(Case::Upper)
+            .soft_mask([0..2, 6..8])
+            .hard_mask(Some(10..12))
+            .subseq(1..11)
+            .rev();
+        assert_eq!(tracked.seq().to_string(), "NCAtgCATGc");
+        assert_eq!(
+            tracked.history(),
+            "uppercase → soft_mask(0..2, 6..8) → hard_mask(10..12) → subseq(1..11) → rev"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn track_custom_operations() -> Result<(), Error> {
+        let region = Region::new(0..4, Strand::Reverse)?;
+        let tracked = TrackedSeq::new(Seq::dna("AACCGGTT")?)
+            .extract(&region)?
+            .apply(Operation::Custom("double".to_string()), |s| {
+                Seq::dna(format!("{s}{s}"))
+            })?;
+        assert_eq!(tracked.seq().to_string(), "GGTTGGTT");
+        assert_eq!(tracked.history(), "extract(complement(1..4)) → double");
+        Ok(())
+    }
+
+    #[test]
+    fn failed_operations_are_not_tracked() -> Result<(), Error> {
+        let tracked = TrackedSeq::new(Seq::protein("MAMAPRTEIN")?);
+        assert_eq!(
+            tracked.reverse_complement(),
+            Err(Error::ReverseComplement(Kind::Protein))
+        );
+        assert!(tracked.operations().is_empty());
+        assert_eq!(tracked.history(), "");
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_provenance() -> Result<(), Error> {
+        let tracked = TrackedSeq::new(Seq::dna("ACGT")?).convert(Kind::Rna)?;
+        let json = serde_json::to_string(&tracked).unwrap();
+        assert_eq!(serde_json::from_str::<TrackedSeq>(&json).unwrap(), tracked);
+        Ok(())
+    }
+}
@@ -0,0 +1,518 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alphabet,
+    seq::{Error, Kind, Seq},
+    types::{ByteMap, Strand},
+};
+
+// ===== Symbols ===================================================================================
+
+// NOTE: Matrices only have columns for unambiguous symbols, so ambiguity codes in sites are skipped
+// when counting, and any window containing one is never scored
+pub(crate) fn symbols(kind: Kind) -> &'static [u8] {
+    match kind {
+        Kind::Dna => b"ACGT",
+        Kind::Rna => b"ACGU",
+        Kind::Protein => b"ACDEFGHIKLMNPQRSTVWY",
+        Kind::Custom(id) => alphabet::custom_symbols(id).unwrap_or_default(),
+    }
+}
+
+pub(crate) fn symbol_index(kind: Kind) -> ByteMap<Option<usize>> {
+    let mut index = ByteMap::default();
+    for (i, &s) in symbols(kind).iter().enumerate() {
+        index[s] = Some(i);
+        index[s.to_ascii_lowercase()] = Some(i);
+    }
+    index
+}
+
+// ===== Background Models =========================================================================
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Background {
+    kind: Kind,
+    probs: Vec<f64>,
+}
+
+impl Background {
+    pub fn uniform(kind: Kind) -> Self {
+        let n = symbols(kind).len();
+        Self {
+            kind,
+            probs: vec![1.0 / n as f64; n],
+        }
+    }
+
+    // NOTE: Probabilities are normalised (so they can be given as counts), and symbols that aren't
+    // given are assumed to never occur
+    pub fn new(kind: Kind, probs: impl IntoIterator<Item = (u8, f64)>) -> Result<Self, Error> {
+        let index = symbol_index(kind);
+        let mut background = Self {
+            kind,
+            probs: vec![0.0; symbols(kind).len()],
+        };
+        for (symbol, p) in probs {
+            if !p.is_finite() || p < 0.0 {
+                return Err(Error::InvalidProbability(format!(
+                    "background probability of {}",
+                    symbol as char
+                )));
+            }
+            if let Some(i) = index[symbol] {
+                background.probs[i] += p;
+            }
+        }
+        background.normalize();
+        Ok(background)
+    }
+
+    pub fn gc(kind: Kind, gc_content: f64) -> Result<Self, Error> {
+        let at = if kind == Kind::Rna { b'U' } else { b'T' };
+        match kind {
+            Kind::Protein | Kind::Custom(_) => Err(Error::GcContent(kind)),
+            _ if !(0.0..=1.0).contains(&gc_content) => {
+                Err(Error::InvalidProbability("GC content".to_string()))
+            }
+            _ => Self::new(
+                kind,
+                [
+                    (b'A', 1.0 - gc_content),
+                    (at, 1.0 - gc_content),
+                    (b'C', gc_content),
+                    (b'G', gc_content),
+                ],
+            ),
+        }
+    }
+
+    pub fn from_seqs<T: AsRef<Seq>>(
+        kind: Kind,
+        seqs: impl IntoIterator<Item = T>,
+    ) -> Result<Self, Error> {
+        let index = symbol_index(kind);
+        let mut background = Self {
+            kind,
+            probs: vec![0.0; symbols(kind).len()],
+        };
+        for seq in seqs {
+            let seq = seq.as_ref();
+            if seq.kind() != kind {
+                return Err(Error::PwmKindMismatch(kind, seq.kind()));
+            }
+            for &b in seq {
+                if let Some(i) = index[b] {
+                    background.probs[i] += 1.0;
+                }
+            }
+        }
+        background.normalize();
+        Ok(background)
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    pub fn prob(&self, symbol: u8) -> f64 {
+        symbol_index(self.kind)[symbol].map_or(0.0, |i| self.probs[i])
+    }
+
+    fn normalize(&mut self) {
+        let total: f64 = self.probs.iter().sum();
+        if total > 0.0 {
+            self.probs.iter_mut().for_each(|p| *p /= total);
+        }
+    }
+}
+
+// ===== Position Weight Matrices ==================================================================
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PwmHit {
+    pub position: usize,
+    pub strand: Strand,
+    pub score: f64,
+}
+
+// NOTE: Each row of `probs` holds the probability of every symbol at one position of the motif, and
+// `weights` holds the matching log-odds scores (in bits) against the background
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Pwm {
+    background: Background,
+    probs: Vec<Vec<f64>>,
+    weights: Vec<Vec<f64>>,
+}
+
+impl Pwm {
+    // NOTE: The pseudocount is shared out between symbols in proportion to the background, so
+    // symbols never seen at a position still get a finite (if very negative) weight. Every symbol
+    // needs a background probability above zero, and every position needs some counts (or a
+    // pseudocount), since the weights would be undefined otherwise
+    pub fn from_counts(
+        counts: &[Vec<f64>],
+        background: &Background,
+        pseudocount: f64,
+    ) -> Result<Self, Error> {
+        let symbols = symbols(background.kind);
+        let width = symbols.len();
+        if let Some(i) = background
+            .probs
+            .iter()
+            .position(|&p| p.is_nan() || p <= 0.0)
+        {
+            return Err(Error::ZeroBackground(symbols[i]));
+        }
+        let mut probs = Vec::with_capacity(counts.len());
+        for (i, row) in counts.iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::ProfileWidth(width, row.len()));
+            }
+            let total = row.iter().sum::<f64>() + pseudocount;
+            if total.is_nan() || total <= 0.0 {
+                return Err(Error::EmptyProfileRow(i));
+            }
+            probs.push(
+                row.iter()
+                    .zip(&background.probs)
+                    .map(|(c, bg)| (c + pseudocount * bg) / total)
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        let weights = probs
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&background.probs)
+                    .map(|(p, bg)| (p / bg).log2())
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            background: background.clone(),
+            probs,
+            weights,
+        })
+    }
+
+    pub fn from_sites<T: AsRef<Seq>>(
+        sites: impl IntoIterator<Item = T>,
+        background: &Background,
+        pseudocount: f64,
+    ) -> Result<Self, Error> {
+        let kind = background.kind;
+        let index = symbol_index(kind);
+        let mut counts: Option<Vec<Vec<f64>>> = None;
+        for site in sites {
+            let site = site.as_ref();
+            if site.kind() != kind {
+                return Err(Error::PwmKindMismatch(kind, site.kind()));
+            }
+            let counts =
+                counts.get_or_insert_with(|| vec![vec![0.0; symbols(kind).len()]; site.len()]);
+            if site.len() != counts.len() {
+                return Err(Error::SiteLength(counts.len(), site.len()));
+            }
+            for (row, &b) in counts.iter_mut().zip(site) {
+                if let Some(i) = index[b] {
+                    row[i] += 1.0;
+                }
+            }
+        }
+
+        let counts = counts.ok_or(Error::EmptySeqSet)?;
+        Self::from_counts(&counts, background, pseudocount)
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.background.kind
+    }
+
+    pub fn background(&self) -> &Background {
+        &self.background
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    pub fn probs(&self) -> &[Vec<f64>] {
+        &self.probs
+    }
+
+    pub fn weights(&self) -> &[Vec<f64>] {
+        &self.weights
+    }
+
+    // NOTE: This is the relative entropy (in bits) of each position against the background, which
+    // reduces to the familiar `log2(4) - H` for DNA with a uniform background
+    pub fn position_information(&self) -> Vec<f64> {
+        self.probs
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&self.background.probs)
+                    .filter(|(&p, _)| p > 0.0)
+                    .map(|(p, bg)| p * (p / bg).log2())
+                    .sum()
+            })
+            .collect()
+    }
+
+    pub fn information_content(&self) -> f64 {
+        self.position_information().iter().sum()
+    }
+
+    pub fn max_score(&self) -> f64 {
+        self.weights
+            .iter()
+            .map(|row| row.iter().copied().fold(f64::NEG_INFINITY, f64::max))
+            .sum()
+    }
+
+    pub fn min_score(&self) -> f64 {
+        self.weights
+            .iter()
+            .map(|row| row.iter().copied().fold(f64::INFINITY, f64::min))
+            .sum()
+    }
+
+    // NOTE: Rescales a score so that the worst possible match is 0.0 and the best is 1.0
+    pub fn relative_score(&self, score: f64) -> f64 {
+        let (min, max) = (self.min_score(), self.max_score());
+        (score - min) / (max - min)
+    }
+
+    pub fn score(&self, window: &[u8]) -> Option<f64> {
+        if window.len() != self.len() {
+            return None;
+        }
+        let index = symbol_index(self.kind());
+        self.score_with(&index, window)
+    }
+
+    fn score_with(&self, index: &ByteMap<Option<usize>>, window: &[u8]) -> Option<f64> {
+        window
+            .iter()
+            .zip(&self.weights)
+            .map(|(&b, row)| Some(row[index[b]?]))
+            .sum()
+    }
+
+    // NOTE: Nucleic acids are scanned on both strands. Hits on the reverse strand are reported at
+    // the forward-strand position of their window, so `seq[position..position + len]` is always
+    // the matched region
+    pub fn scan(&self, seq: &Seq, min_score: f64) -> Result<Vec<PwmHit>, Error> {
+        if seq.kind() != self.kind() {
+            return Err(Error::PwmKindMismatch(self.kind(), seq.kind()));
+        }
+
+        let index = symbol_index(self.kind());
+        let mut strands = vec![(Strand::Forward, seq.clone())];
+        if self.kind().is_nucleic_acid() {
+            strands.push((Strand::Reverse, seq.reverse_complement()?));
+        }
+
+        let len = self.len();
+        let mut hits = Vec::new();
+        for (strand, seq) in strands {
+            if len == 0 || seq.len() < len {
+                break;
+            }
+            for (i, window) in seq.as_bytes().windows(len).enumerate() {
+                let Some(score) = self.score_with(&index, window) else {
+                    continue;
+                };
+                if score >= min_score {
+                    let position = match strand {
+                        Strand::Forward => i,
+                        Strand::Reverse => seq.len() - len - i,
+                    };
+                    hits.push(PwmHit {
+                        position,
+                        strand,
+                        score,
+                    });
+                }
+            }
+        }
+        hits.sort_by_key(|h| (h.position, h.strand));
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Alphabet;
+
+    fn sites() -> Result<Vec<Seq>, Error> {
+        ["TATAAT", "TATAAT", "TATGAT", "TACAAT"]
+            .into_iter()
+            .map(Seq::dna)
+            .collect()
+    }
+
+    // ===== Background Model Tests ================================================================
+
+    #[test]
+    fn background_models() -> Result<(), Error> {
+        assert_eq!(Background::uniform(Kind::Dna).prob(b'g'), 0.25);
+        assert_eq!(Background::uniform(Kind::Protein).prob(b'W'), 0.05);
+        let gc = Background::gc(Kind::Rna, 0.6)?;
+        assert_eq!(gc.prob(b'G'), 0.3);
+        assert!((gc.prob(b'U') - 0.2).abs() < 1e-12);
+        assert_eq!(gc.prob(b'N'), 0.0);
+        assert_eq!(
+            Background::gc(Kind::Protein, 0.6),
+            Err(Error::GcContent(Kind::Protein))
+        );
+        for gc_content in [1.5, -0.1, f64::NAN] {
+            assert_eq!(
+                Background::gc(Kind::Dna, gc_content),
+                Err(Error::InvalidProbability("GC content".to_string()))
+            );
+        }
+        let weighted = Background::new(Kind::Dna, [(b'A', 3.0), (b'C', 1.0)])?;
+        assert_eq!(weighted.prob(b'A'), 0.75);
+        for p in [-1.0, f64::INFINITY, f64::NAN] {
+            assert_eq!(
+                Background::new(Kind::Dna, [(b'A', p), (b'C', 1.0)]),
+                Err(Error::InvalidProbability(
+                    "background probability of A".to_string()
+                ))
+            );
+        }
+        let counted = Background::from_seqs(Kind::Dna, [Seq::dna_n("AACGNT")?])?;
+        assert_eq!(counted.prob(b'A'), 0.4);
+        assert_eq!(counted.prob(b'N'), 0.0);
+        Ok(())
+    }
+
+    // ===== Construction Tests ====================================================================
+
+    #[test]
+    fn pwm_from_sites() -> Result<(), Error> {
+        let pwm = Pwm::from_sites(sites()?, &Background::uniform(Kind::Dna), 0.0)?;
+        assert_eq!(pwm.len(), 6);
+        assert_eq!(pwm.probs()[0], [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(pwm.probs()[3], [0.75, 0.0, 0.25, 0.0]);
+        assert_eq!(pwm.weights()[0][3], 2.0);
+        assert_eq!(pwm.weights()[0][0], f64::NEG_INFINITY);
+        Ok(())
+    }
+
+    #[test]
+    fn pwm_with_pseudocounts() -> Result<(), Error> {
+        let pwm = Pwm::from_sites(sites()?, &Background::uniform(Kind::Dna), 4.0)?;
+        assert_eq!(pwm.probs()[0], [0.125, 0.125, 0.125, 0.625]);
+        assert!(pwm.weights().iter().flatten().all(|w| w.is_finite()));
+        Ok(())
+    }
+
+    #[test]
+    fn pwm_from_counts() -> Result<(), Error> {
+        let background = Background::uniform(Kind::Dna);
+        let pwm = Pwm::from_counts(&[vec![1.0, 1.0, 1.0, 1.0]], &background, 0.0)?;
+        assert_eq!(pwm.information_content(), 0.0);
+        assert_eq!(
+            Pwm::from_counts(&[vec![1.0, 1.0, 1.0]], &background, 0.0),
+            Err(Error::ProfileWidth(4, 3))
+        );
+        let empty = [vec![1.0, 1.0, 1.0, 1.0], vec![0.0; 4]];
+        assert_eq!(
+            Pwm::from_counts(&empty, &background, 0.0),
+            Err(Error::EmptyProfileRow(1))
+        );
+        assert!(Pwm::from_counts(&empty, &background, 1.0).is_ok());
+        let no_gc = Background::new(Kind::Dna, [(b'A', 0.5), (b'T', 0.5)])?;
+        assert_eq!(
+            Pwm::from_counts(&[vec![1.0, 1.0, 1.0, 1.0]], &no_gc, 1.0),
+            Err(Error::ZeroBackground(b'C'))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pwm_invalid_sites() -> Result<(), Error> {
+        let background = Background::uniform(Kind::Dna);
+        assert_eq!(
+            Pwm::from_sites([Seq::dna("TATAAT")?, Seq::dna("TATA")?], &background, 0.0),
+            Err(Error::SiteLength(6, 4))
+        );
+        assert_eq!(
+            Pwm::from_sites([Seq::rna("UAUAAU")?], &background, 0.0),
+            Err(Error::PwmKindMismatch(Kind::Dna, Kind::Rna))
+        );
+        assert_eq!(
+            Pwm::from_sites(Vec::<Seq>::new(), &background, 0.0),
+            Err(Error::EmptySeqSet)
+        );
+        Ok(())
+    }
+
+    // ===== Information Content Tests =============================================================
+
+    #[test]
+    fn pwm_information_content() -> Result<(), Error> {
+        let pwm = Pwm::from_sites(sites()?, &Background::uniform(Kind::Dna), 0.0)?;
+        let information = pwm.position_information();
+        assert_eq!(information[0], 2.0);
+        assert!((information[2] - 1.188_722_5).abs() < 1e-6);
+        let total = 4.0 * 2.0 + 2.0 * information[2];
+        assert!((pwm.information_content() - total).abs() < 1e-12);
+        Ok(())
+    }
+
+    // ===== Scanning Tests ========================================================================
+
+    #[test]
+    fn pwm_scores() -> Result<(), Error> {
+        let pwm = Pwm::from_sites(sites()?, &Background::uniform(Kind::Dna), 1.0)?;
+        let best = pwm.score(b"TATAAT").unwrap();
+        assert_eq!(best, pwm.max_score());
+        assert_eq!(pwm.score(b"tataat"), Some(best));
+        assert_eq!(pwm.relative_score(best), 1.0);
+        assert!(pwm.score(b"TATGAT").unwrap() < best);
+        assert_eq!(pwm.score(b"TATNAT"), None);
+        assert_eq!(pwm.score(b"TATA"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn pwm_scan_both_strands() -> Result<(), Error> {
+        let pwm = Pwm::from_sites(sites()?, &Background::uniform(Kind::Dna), 1.0)?;
+        let seq = Seq::dna("GGTATAATGGGGATTATAGG")?;
+        let hits = pwm.scan(&seq, pwm.max_score() - 0.1)?;
+        assert_eq!(hits.len(), 2);
+        assert_eq!((hits[0].position, hits[0].strand), (2, Strand::Forward));
+        assert_eq!((hits[1].position, hits[1].strand), (12, Strand::Reverse));
+        assert!(pwm.scan(&seq, pwm.max_score() + 0.1)?.is_empty());
+        assert_eq!(
+            pwm.scan(&Seq::protein("MAMAPRTEIN")?, 0.0),
+            Err(Error::PwmKindMismatch(Kind::Dna, Kind::Protein))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pwm_scan_custom_alphabet() -> Result<(), Error> {
+        let kind = alphabet::register_alphabet("Methylated PWM DNA", "ACGMT")?;
+        let site = |s| Seq::new_with_kind(s, [kind], Alphabet::Base);
+        let pwm = Pwm::from_sites(
+            [site("CMG")?, site("CMG")?],
+            &Background::uniform(kind),
+            1.0,
+        )?;
+        let hits = pwm.scan(&site("ACMGTCGAC")?, pwm.max_score() - 0.1)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].position, hits[0].strand), (1, Strand::Forward));
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::{
+    data::ANTI_SHINE_DALGARNO,
+    region::Region,
+    seq::{Error, Seq},
+    types::{Case, Strand},
+    utils::pair_score,
+};
+
+// NOTE: `sd` is the stretch of sequence found pairing with the anti-Shine–Dalgarno, and `spacing`
+// counts the bases between its 3' end and the start codon
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Rbs {
+    pub start_codon: usize,
+    pub sd: Region,
+    pub spacing: usize,
+    pub pairing: f64,
+    pub strength: f64,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RbsFinder {
+    spacing: Range<usize>,
+    optimal_spacing: usize,
+    min_pairing: f64,
+}
+
+impl Default for RbsFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RbsFinder {
+    // NOTE: By default, Shine–Dalgarno sites must end 5–10 bases upstream of the start codon (with
+    // 7 being optimal) and pair at least as well as a GGA core
+    pub fn new() -> Self {
+        Self {
+            spacing: 5..11,
+            optimal_spacing: 7,
+            min_pairing: 8.0,
+        }
+    }
+
+    // NOTE: The spacing range can't be empty, since there would be nowhere left for a site to end
+    pub fn spacing(self, spacing: Range<usize>, optimal_spacing: usize) -> Result<Self, Error> {
+        if spacing.is_empty() {
+            return Err(Error::RbsSpacing(spacing.start, spacing.end));
+        }
+        Ok(Self {
+            spacing,
+            optimal_spacing,
+            ..self
+        })
+    }
+
+    pub fn min_pairing(self, min_pairing: f64) -> Self {
+        Self {
+            min_pairing,
+            ..self
+        }
+    }
+
+    // NOTE: Every AUG, GUG, and UUG on the forward strand is treated as a potential start codon
+    pub fn find(&self, seq: &Seq) -> Result<Vec<Rbs>, Error> {
+        let bytes = rna_bytes(seq)?;
+        Ok((0..bytes.len().saturating_sub(2))
+            .filter(|&i| matches!(&bytes[i..i + 3], b"AUG" | b"GUG" | b"UUG"))
+            .filter_map(|i| self.score_bytes(&bytes, i))
+            .collect())
+    }
+
+    pub fn score(&self, seq: &Seq, start_codon: usize) -> Result<Option<Rbs>, Error> {
+        Ok(self.score_bytes(&rna_bytes(seq)?, start_codon))
+    }
+
+    // NOTE: This is a simplified model of ribosome recruitment: the strongest contiguous stretch of
+    // pairing (allowing G–U wobble pairs) with the anti-Shine–Dalgarno is found, then its strength
+    // is scaled down by 15% for every base its spacing is away from the optimum. A perfect, 9 bp
+    // UAAGGAGGU site at the optimal spacing has a strength of 1.0
+    fn score_bytes(&self, bytes: &[u8], start_codon: usize) -> Option<Rbs> {
+        let target: Vec<_> = ANTI_SHINE_DALGARNO.iter().rev().copied().collect();
+        let max_pairing: f64 = target
+            .iter()
+            .map(|&t| {
+                b"ACGU"
+                    .iter()
+                    .filter_map(|&b| pair_score(b, t))
+                    .fold(0.0, f64::max)
+            })
+            .sum();
+
+        let first_end = start_codon.checked_sub(self.spacing.end - 1)?;
+        let last_end = start_codon.checked_sub(self.spacing.start)?;
+        let lo = first_end.saturating_sub(target.len());
+
+        let mut best: Option<Rbs> = None;
+        let mut runs = vec![(0.0, 0); target.len()];
+        for (i, &base) in bytes.iter().enumerate().take(last_end).skip(lo) {
+            let prev = runs.clone();
+            for (j, &t) in target.iter().enumerate() {
+                runs[j] = match pair_score(base, t) {
+                    Some(s) if j > 0 => (prev[j - 1].0 + s, prev[j - 1].1 + 1),
+                    Some(s) => (s, 1),
+                    None => (0.0, 0),
+                };
+            }
+
+            let sd_end = i + 1;
+            if sd_end < first_end {
+                continue;
+            }
+            let spacing = start_codon - sd_end;
+            let penalty = 0.15 * spacing.abs_diff(self.optimal_spacing) as f64;
+            for &(pairing, len) in &runs {
+                let strength = pairing / max_pairing * (1.0 - penalty).max(0.0);
+                if pairing >= self.min_pairing
+                    && best.as_ref().is_none_or(|b| strength > b.strength)
+                {
+                    best = Some(Rbs {
+                        start_codon,
+                        sd: Region::new(sd_end - len..sd_end, Strand::Forward)
+                            .expect("A run never extends past the base it ends at"),
+                        spacing,
+                        pairing,
+                        strength,
+                    });
+                }
+            }
+        }
+        best
+    }
+}
+
+fn rna_bytes(seq: &Seq) -> Result<Vec<u8>, Error> {
+    if !seq.kind().is_nucleic_acid() {
+        return Err(Error::RbsSearch(seq.kind()));
+    }
+    Ok(seq
+        .normalize_case(Case::Upper)
+        .as_bytes()
+        .iter()
+        .map(|&b| if b == b'T' { b'U' } else { b })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Kind;
+
+    #[test]
+    fn score_consensus_rbs() -> Result<(), Error> {
+        let seq = Seq::dna("GGGTAAGGAGGTCCCCCCCATGAAA")?;
+        let rbs = RbsFinder::new().score(&seq, 19)?.unwrap();
+        assert_eq!(rbs.sd.range(), 3..12);
+        assert_eq!(rbs.spacing, 7);
+        assert_eq!(rbs.pairing, 22.0);
+        assert_eq!(rbs.strength, 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn score_spaced_rbs() -> Result<(), Error> {
+        let seq = Seq::rna("aaggaggaaaaaaaug")?;
+        let rbs = RbsFinder::new().score(&seq, 13)?.unwrap();
+        assert_eq!(rbs.sd.range(), 0..7);
+        assert_eq!(rbs.spacing, 6);
+        assert_eq!(rbs.pairing, 18.0);
+        assert!((rbs.strength - 18.0 / 22.0 * 0.85).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn find_rbs() -> Result<(), Error> {
+        let seq = Seq::dna("AAGGAGGAAAAAAATGCCCCCCCCCCCCCCCTTGCCC")?;
+        let sites = RbsFinder::new().find(&seq)?;
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].start_codon, 13);
+        let sites = RbsFinder::new().min_pairing(25.0).find(&seq)?;
+        assert!(sites.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn rbs_spacing() -> Result<(), Error> {
+        let seq = Seq::dna("AAGGAGGAAAAAAATG")?;
+        assert!(RbsFinder::new()
+            .spacing(11..14, 12)?
+            .score(&seq, 13)?
+            .is_none());
+        let seq = Seq::dna("ATG")?;
+        assert!(RbsFinder::new().score(&seq, 0)?.is_none());
+        assert_eq!(
+            RbsFinder::new().spacing(Range { start: 0, end: 0 }, 0),
+            Err(Error::RbsSpacing(0, 0))
+        );
+        assert_eq!(
+            RbsFinder::new().spacing(Range { start: 8, end: 4 }, 6),
+            Err(Error::RbsSpacing(8, 4))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rbs_protein() -> Result<(), Error> {
+        assert_eq!(
+            RbsFinder::new().find(&Seq::protein("MAMAPRTEIN")?),
+            Err(Error::RbsSearch(Kind::Protein))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,1362 @@
+use bio::alignment::{
+    pairwise::{Aligner, Scoring, MIN_SCORE},
+    AlignmentOperation,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    pwm,
+    record::SeqRecord,
+    region::Region,
+    seq::{Alphabet, Error, Kind, Seq},
+    types::{Case, Strand},
+};
+
+// ===== Overlap Detection =========================================================================
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Overlap {
+    pub start: usize,
+    pub len: usize,
+    pub mismatches: usize,
+}
+
+// NOTE: This is an ungapped, end-anchored alignment of `query` against `target`: `query` starts at
+// `start` in `target` and either lies wholly within it or runs off its end. The best overlap has
+// the most matching bases, with ties going to the leftmost start. Comparisons are case-insensitive
+pub fn find_overlap(
+    target: &[u8],
+    query: &[u8],
+    max_error_rate: f64,
+    min_overlap: usize,
+) -> Option<Overlap> {
+    let min_overlap = min_overlap.max(1);
+    if query.is_empty() || target.len() < min_overlap {
+        return None;
+    }
+
+    (0..=target.len() - min_overlap)
+        .filter_map(|start| {
+            let len = query.len().min(target.len() - start);
+            if len < min_overlap {
+                return None;
+            }
+            let mismatches = target[start..start + len]
+                .iter()
+                .zip(query)
+                .filter(|(t, q)| !t.eq_ignore_ascii_case(q))
+                .count();
+            (mismatches as f64 <= len as f64 * max_error_rate).then_some(Overlap {
+                start,
+                len,
+                mismatches,
+            })
+        })
+        .min_by_key(|o| (o.mismatches as isize - o.len as isize, o.start))
+}
+
+// NOTE: This is the gapped counterpart of `find_overlap`, as a semi-global alignment of `query`
+// against the 3' end of `target`. The whole of `query` is aligned within `target`, or a prefix of
+// it is aligned to the end of `target`, and `len` counts the bases of `target` covered. Here,
+// `mismatches` counts every edit (substitutions and indels), and the error rate and minimum overlap
+// apply to the bases of `query` that were aligned
+pub fn find_gapped_overlap(
+    target: &[u8],
+    query: &[u8],
+    max_error_rate: f64,
+    min_overlap: usize,
+) -> Option<Overlap> {
+    let min_overlap = min_overlap.max(1);
+    if query.is_empty() || target.len() < min_overlap {
+        return None;
+    }
+
+    fn score(a: u8, b: u8) -> i32 {
+        if a.eq_ignore_ascii_case(&b) {
+            1
+        } else {
+            -1
+        }
+    }
+    let scoring = || {
+        Scoring::new(-1, -1, score)
+            .xclip_prefix(0)
+            .yclip_prefix(MIN_SCORE)
+    };
+    let contained = scoring().xclip_suffix(0).yclip_suffix(MIN_SCORE);
+    let partial = scoring().xclip_suffix(MIN_SCORE).yclip_suffix(0);
+
+    [contained, partial]
+        .into_iter()
+        .filter_map(|scoring| {
+            let alignment = Aligner::with_scoring(scoring).custom(target, query);
+            let aligned = alignment.yend - alignment.ystart;
+            let (mut matches, mut mismatches) = (0, 0);
+            for op in &alignment.operations {
+                match op {
+                    AlignmentOperation::Match => matches += 1,
+                    AlignmentOperation::Subst
+                    | AlignmentOperation::Del
+                    | AlignmentOperation::Ins => mismatches += 1,
+                    _ => (),
+                }
+            }
+            (aligned >= min_overlap && mismatches as f64 <= aligned as f64 * max_error_rate)
+                .then_some((
+                    matches,
+                    Overlap {
+                        start: alignment.xstart,
+                        len: alignment.xend - alignment.xstart,
+                        mismatches,
+                    },
+                ))
+        })
+        .min_by_key(|(matches, o)| (-(*matches as isize), o.start))
+        .map(|(_, o)| o)
+}
+
+// ===== Adapter Trimming ==========================================================================
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum ReadEnd {
+    Five,
+    Three,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Adapter {
+    pub name: String,
+    pub seq: Seq,
+    pub end: ReadEnd,
+}
+
+impl Adapter {
+    pub fn new(name: impl Into<String>, seq: Seq, end: ReadEnd) -> Self {
+        Self {
+            name: name.into(),
+            seq,
+            end,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct AdapterMatch {
+    pub adapter: usize,
+    pub overlap: Overlap,
+    pub trimmed: usize,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct AdapterStats {
+    pub name: String,
+    pub reads: usize,
+    pub bases: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AdapterTrimmer {
+    adapters: Vec<Adapter>,
+    max_error_rate: f64,
+    min_overlap: usize,
+}
+
+impl AdapterTrimmer {
+    // NOTE: The defaults (10% errors, a 3 base minimum overlap) are the same as cutadapt's
+    pub fn new(adapters: impl Into<Vec<Adapter>>) -> Self {
+        Self {
+            adapters: adapters.into(),
+            max_error_rate: 0.1,
+            min_overlap: 3,
+        }
+    }
+
+    pub fn max_error_rate(self, max_error_rate: f64) -> Self {
+        Self {
+            max_error_rate,
+            ..self
+        }
+    }
+
+    pub fn min_overlap(self, min_overlap: usize) -> Self {
+        Self {
+            min_overlap,
+            ..self
+        }
+    }
+
+    // NOTE: Each adapter is searched for once, in order, with later adapters seeing the read
+    // already trimmed by earlier ones. Adapters are found with a gapped alignment, so they can
+    // contain indels as well as mismatches
+    pub fn trim(&self, record: &SeqRecord) -> (SeqRecord, Vec<AdapterMatch>) {
+        let mut record = record.clone();
+        let mut matches = Vec::new();
+
+        for (i, adapter) in self.adapters.iter().enumerate() {
+            let read = record.seq().normalize_case(Case::Upper);
+            let adapter_seq = adapter.seq.normalize_case(Case::Upper);
+            let (read, adapter_seq) = match adapter.end {
+                ReadEnd::Three => (read, adapter_seq),
+                ReadEnd::Five => (read.rev(), adapter_seq.rev()),
+            };
+
+            let Some(overlap) = find_gapped_overlap(
+                read.as_bytes(),
+                adapter_seq.as_bytes(),
+                self.max_error_rate,
+                self.min_overlap,
+            ) else {
+                continue;
+            };
+
+            let len = record.len();
+            let trimmed = len - overlap.start;
+            record = match adapter.end {
+                ReadEnd::Three => record.subrecord(0..overlap.start),
+                ReadEnd::Five => record.subrecord(trimmed..len),
+            };
+            matches.push(AdapterMatch {
+                adapter: i,
+                overlap,
+                trimmed,
+            });
+        }
+
+        (record, matches)
+    }
+
+    pub fn trim_reads(
+        &self,
+        records: impl IntoIterator<Item = SeqRecord>,
+    ) -> (Vec<SeqRecord>, Vec<AdapterStats>) {
+        let mut stats: Vec<_> = self
+            .adapters
+            .iter()
+            .map(|a| AdapterStats {
+                name: a.name.clone(),
+                reads: 0,
+                bases: 0,
+            })
+            .collect();
+
+        let records = records
+            .into_iter()
+            .map(|r| {
+                let (r, matches) = self.trim(&r);
+                for m in matches {
+                    stats[m.adapter].reads += 1;
+                    stats[m.adapter].bases += m.trimmed;
+                }
+                r
+            })
+            .collect();
+
+        (records, stats)
+    }
+}
+
+// ===== Paired-End Merging ========================================================================
+
+// NOTE: Agreeing bases have their qualities summed (as both reads are independent evidence for
+// them), but are capped at the highest score Illumina instruments report
+const MAX_MERGED_QUALITY: u8 = 41;
+
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct MergedPairs {
+    pub merged: Vec<SeqRecord>,
+    pub unmerged: Vec<(SeqRecord, SeqRecord)>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PairMerger {
+    max_error_rate: f64,
+    min_overlap: usize,
+}
+
+impl Default for PairMerger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PairMerger {
+    pub fn new() -> Self {
+        Self {
+            max_error_rate: 0.1,
+            min_overlap: 10,
+        }
+    }
+
+    pub fn max_error_rate(self, max_error_rate: f64) -> Self {
+        Self {
+            max_error_rate,
+            ..self
+        }
+    }
+
+    pub fn min_overlap(self, min_overlap: usize) -> Self {
+        Self {
+            min_overlap,
+            ..self
+        }
+    }
+
+    // NOTE: R2 is reverse complemented and must then start within R1. Pairs whose inserts are
+    // shorter than the reads (so R1 runs into the adapter) should be adapter-trimmed first. When
+    // the reads disagree, the base with the higher quality wins (R1 on ties) and the merged quality
+    // is the difference between the two. The merged record takes R1's ID and description
+    pub fn merge(&self, r1: &SeqRecord, r2: &SeqRecord) -> Result<Option<SeqRecord>, Error> {
+        let (k1, k2) = (r1.seq().kind(), r2.seq().kind());
+        if k1 != k2 {
+            return Err(Error::MergeKindMismatch(k1, k2));
+        }
+        let q1 = r1.qual().ok_or(Error::MissingQuality)?;
+        let q2: Vec<_> = r2
+            .qual()
+            .ok_or(Error::MissingQuality)?
+            .iter()
+            .rev()
+            .copied()
+            .collect();
+        let rc = r2.seq().reverse_complement()?;
+        let (s1, s2) = (r1.seq().as_bytes(), rc.as_bytes());
+
+        let Some(overlap) = find_overlap(s1, s2, self.max_error_rate, self.min_overlap) else {
+            return Ok(None);
+        };
+
+        let mut bytes = s1[..overlap.start].to_vec();
+        let mut qual = q1[..overlap.start].to_vec();
+        for i in 0..overlap.len {
+            let (b1, b2) = (s1[overlap.start + i], s2[i]);
+            let (q1, q2) = (q1[overlap.start + i], q2[i]);
+            let (b, q) = if b1.eq_ignore_ascii_case(&b2) {
+                (b1, q1.saturating_add(q2).min(MAX_MERGED_QUALITY))
+            } else if q1 >= q2 {
+                (b1, q1 - q2)
+            } else {
+                (b2, q2 - q1)
+            };
+            bytes.push(b);
+            qual.push(q);
+        }
+        // NOTE: Only one of these tails can be non-empty
+        let end = overlap.start + overlap.len;
+        bytes.extend_from_slice(&s1[end..]);
+        qual.extend_from_slice(&q1[end..]);
+        bytes.extend_from_slice(&s2[overlap.len..]);
+        qual.extend_from_slice(&q2[overlap.len..]);
+
+        let seq = Seq::from_vec_with_kind(bytes, [k1], Alphabet::Iupac)?;
+        let merged = SeqRecord::with_qual(r1.id(), seq, qual)?;
+        Ok(Some(match r1.desc() {
+            Some(desc) => merged.with_desc(desc),
+            None => merged,
+        }))
+    }
+
+    pub fn merge_pairs(
+        &self,
+        pairs: impl IntoIterator<Item = (SeqRecord, SeqRecord)>,
+    ) -> Result<MergedPairs, Error> {
+        let mut merged = MergedPairs::default();
+        for (r1, r2) in pairs {
+            match self.merge(&r1, &r2)? {
+                Some(record) => merged.merged.push(record),
+                None => merged.unmerged.push((r1, r2)),
+            }
+        }
+        Ok(merged)
+    }
+}
+
+// ===== Demultiplexing ============================================================================
+
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Demultiplexed {
+    pub samples: HashMap<String, Vec<SeqRecord>>,
+    pub unassigned: Vec<SeqRecord>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Demultiplexer {
+    barcodes: Vec<(String, Seq)>,
+    max_mismatches: usize,
+    trim_barcodes: bool,
+}
+
+impl Demultiplexer {
+    pub fn new<S: Into<String>>(barcodes: impl IntoIterator<Item = (S, Seq)>) -> Self {
+        Self {
+            barcodes: barcodes
+                .into_iter()
+                .map(|(s, b)| (s.into(), b.normalize_case(Case::Upper)))
+                .collect(),
+            max_mismatches: 0,
+            trim_barcodes: true,
+        }
+    }
+
+    pub fn max_mismatches(self, max_mismatches: usize) -> Self {
+        Self {
+            max_mismatches,
+            ..self
+        }
+    }
+
+    pub fn trim_barcodes(self, trim_barcodes: bool) -> Self {
+        Self {
+            trim_barcodes,
+            ..self
+        }
+    }
+
+    // NOTE: Barcodes are expected at the 5' end of the read. If the closest barcodes of two
+    // samples are equally good matches, the read can't be assigned with any confidence
+    pub fn assign(&self, record: &SeqRecord) -> Option<usize> {
+        let read = record.seq().as_bytes();
+        let mut hits: Vec<_> = self
+            .barcodes
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, b))| b.len() <= read.len())
+            .map(|(i, (_, b))| {
+                let mismatches = b
+                    .as_bytes()
+                    .iter()
+                    .zip(read)
+                    .filter(|(b, r)| !b.eq_ignore_ascii_case(r))
+                    .count();
+                (mismatches, i)
+            })
+            .filter(|&(m, _)| m <= self.max_mismatches)
+            .collect();
+        hits.sort_unstable();
+
+        match hits[..] {
+            [(m1, _), (m2, _), ..] if m1 == m2 => None,
+            [(_, i), ..] => Some(i),
+            [] => None,
+        }
+    }
+
+    // NOTE: Yields each record (trimmed, if barcodes are being trimmed) with the sample it was
+    // assigned to, or `None` if it couldn't be assigned, so reads can be streamed straight to
+    // per-sample files without every record being held in memory
+    pub fn demultiplex_iter<'a>(
+        &'a self,
+        records: impl IntoIterator<Item = SeqRecord> + 'a,
+    ) -> impl Iterator<Item = (Option<&'a str>, SeqRecord)> + 'a {
+        records
+            .into_iter()
+            .map(move |record| match self.assign(&record) {
+                Some(i) => {
+                    let (sample, barcode) = &self.barcodes[i];
+                    let record = if self.trim_barcodes {
+                        trim_ends(&record, barcode.len(), 0)
+                    } else {
+                        record
+                    };
+                    (Some(sample.as_str()), record)
+                }
+                None => (None, record),
+            })
+    }
+
+    pub fn demultiplex(&self, records: impl IntoIterator<Item = SeqRecord>) -> Demultiplexed {
+        let mut demuxed = Demultiplexed {
+            samples: self
+                .barcodes
+                .iter()
+                .map(|(s, _)| (s.clone(), Vec::new()))
+                .collect(),
+            unassigned: Vec::new(),
+        };
+
+        for (sample, record) in self.demultiplex_iter(records) {
+            match sample {
+                Some(sample) => demuxed
+                    .samples
+                    .get_mut(sample)
+                    .expect("Every sample should have a record list")
+                    .push(record),
+                None => demuxed.unassigned.push(record),
+            }
+        }
+
+        demuxed
+    }
+}
+
+// ===== Deduplication =============================================================================
+
+// NOTE: Sequences are compared case-insensitively, and near-duplicates are those sharing the same
+// `prefix_len` leading elements. The first occurrence of each sequence is the one that is kept
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Deduplicator {
+    prefix_len: Option<usize>,
+    seen: HashSet<(Kind, Vec<u8>)>,
+    duplicates: usize,
+}
+
+impl Deduplicator {
+    pub fn exact() -> Self {
+        Self::default()
+    }
+
+    pub fn prefix(prefix_len: usize) -> Self {
+        Self {
+            prefix_len: Some(prefix_len),
+            ..Self::default()
+        }
+    }
+
+    pub fn insert(&mut self, seq: &Seq) -> bool {
+        let bytes = seq.as_bytes();
+        let key = match self.prefix_len {
+            Some(len) => &bytes[..len.min(bytes.len())],
+            None => bytes,
+        };
+        let new = self.seen.insert((seq.kind(), key.to_ascii_uppercase()));
+        if !new {
+            self.duplicates += 1;
+        }
+        new
+    }
+
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    pub fn dedup<T: AsRef<Seq>>(mut self, records: impl IntoIterator<Item = T>) -> (Vec<T>, usize) {
+        let records = records
+            .into_iter()
+            .filter(|r| self.insert(r.as_ref()))
+            .collect();
+        (records, self.duplicates)
+    }
+}
+
+// ===== Subsampling ===============================================================================
+
+// NOTE: This is reservoir sampling (Algorithm R), so only `n` records are ever held in memory. The
+// sampled records are returned in the order they were read
+pub fn subsample<T>(records: impl IntoIterator<Item = T>, n: usize, rng: &mut impl Rng) -> Vec<T> {
+    let mut reservoir = Vec::with_capacity(n);
+    for (i, record) in records.into_iter().enumerate() {
+        if i < n {
+            reservoir.push((i, record));
+        } else {
+            let j = rng.gen_range(0..=i);
+            if j < n {
+                reservoir[j] = (i, record);
+            }
+        }
+    }
+
+    reservoir.sort_unstable_by_key(|&(i, _)| i);
+    reservoir.into_iter().map(|(_, r)| r).collect()
+}
+
+pub fn sample_fraction<'a, T: 'a>(
+    records: impl IntoIterator<Item = T> + 'a,
+    fraction: f64,
+    rng: &'a mut impl Rng,
+) -> Result<impl Iterator<Item = T> + 'a, Error> {
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(Error::InvalidProbability("sampling fraction".to_string()));
+    }
+    Ok(records.into_iter().filter(move |_| rng.gen_bool(fraction)))
+}
+
+// ===== Trimming ==================================================================================
+
+pub fn trim_ends(record: &SeqRecord, head: usize, tail: usize) -> SeqRecord {
+    let start = head.min(record.len());
+    let end = record.len().saturating_sub(tail).max(start);
+    record.subrecord(start..end)
+}
+
+// NOTE: Like Trimmomatic's `SLIDINGWINDOW`, this scans from the 5' end and cuts the read at the
+// start of the first window whose mean quality drops below `min_quality`
+pub fn trim_quality_window(
+    record: &SeqRecord,
+    window: usize,
+    min_quality: f64,
+) -> Result<SeqRecord, Error> {
+    if window == 0 {
+        return Err(Error::WindowSize(window));
+    }
+    let qual = record.qual().ok_or(Error::MissingQuality)?;
+    let window = window.min(qual.len());
+    if window == 0 {
+        return Ok(record.clone());
+    }
+
+    // OPTIMISATION: Keeping a running sum avoids re-summing every window from scratch
+    let min_sum = min_quality * window as f64;
+    let mut sum: u32 = qual[..window].iter().map(|&q| q as u32).sum();
+    let mut end = qual.len();
+    for start in 0..=qual.len() - window {
+        if start > 0 {
+            sum = sum + qual[start + window - 1] as u32 - qual[start - 1] as u32;
+        }
+        if (sum as f64) < min_sum {
+            end = start;
+            break;
+        }
+    }
+
+    Ok(record.subrecord(0..end))
+}
+
+// ===== Filtering =================================================================================
+
+// NOTE: When a quality threshold is given, records without qualities are always dropped
+pub fn filter_reads(
+    records: impl IntoIterator<Item = SeqRecord>,
+    min_len: usize,
+    min_mean_quality: Option<f64>,
+) -> impl Iterator<Item = SeqRecord> {
+    records.into_iter().filter(move |r| {
+        r.len() >= min_len
+            && min_mean_quality.is_none_or(|min| r.mean_quality().is_some_and(|q| q >= min))
+    })
+}
+
+// ===== Read Simulation ===========================================================================
+
+// NOTE: Read qualities either stay constant or change linearly from the first base to the last
+// (usually decaying, as they do in Illumina reads)
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum QualityModel {
+    Constant(u8),
+    Linear { start: u8, end: u8 },
+}
+
+impl QualityModel {
+    fn quality(&self, i: usize, len: usize) -> u8 {
+        match *self {
+            QualityModel::Constant(q) => q,
+            QualityModel::Linear { start, end } => {
+                let t = i as f64 / len.saturating_sub(1).max(1) as f64;
+                (start as f64 + (end as f64 - start as f64) * t).round() as u8
+            }
+        }
+    }
+}
+
+// NOTE: `origin` is the region of the template that a read was sequenced from (including any
+// deleted bases), on the strand it was read along
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct SimulatedRead {
+    pub record: SeqRecord,
+    pub origin: Region,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ReadSimulator {
+    read_len: usize,
+    coverage: f64,
+    qualities: QualityModel,
+    insertion_rate: f64,
+    deletion_rate: f64,
+    both_strands: bool,
+}
+
+impl Default for ReadSimulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadSimulator {
+    // NOTE: By default, 150 bp reads are sampled from both strands at 30x coverage, with qualities
+    // falling from Q40 to Q20 and rare indels
+    pub fn new() -> Self {
+        Self {
+            read_len: 150,
+            coverage: 30.0,
+            qualities: QualityModel::Linear { start: 40, end: 20 },
+            insertion_rate: 0.0001,
+            deletion_rate: 0.0001,
+            both_strands: true,
+        }
+    }
+
+    pub fn read_len(self, read_len: usize) -> Self {
+        Self { read_len, ..self }
+    }
+
+    pub fn coverage(self, coverage: f64) -> Self {
+        Self { coverage, ..self }
+    }
+
+    pub fn qualities(self, qualities: QualityModel) -> Self {
+        Self { qualities, ..self }
+    }
+
+    pub fn insertion_rate(self, insertion_rate: f64) -> Self {
+        Self {
+            insertion_rate,
+            ..self
+        }
+    }
+
+    pub fn deletion_rate(self, deletion_rate: f64) -> Self {
+        Self {
+            deletion_rate,
+            ..self
+        }
+    }
+
+    pub fn both_strands(self, both_strands: bool) -> Self {
+        Self {
+            both_strands,
+            ..self
+        }
+    }
+
+    // NOTE: Qualities are honest: each base is substituted with the error probability of its Phred
+    // score. Indels are drawn independently of the qualities, and reads keep reading past deletions
+    // until they reach `read_len` (or the end of the template). Enough reads are sampled, uniformly
+    // along the template, to reach the requested mean coverage
+    pub fn simulate(
+        &self,
+        template: &Seq,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<SimulatedRead>, Error> {
+        let kind = template.kind();
+        if !matches!(kind, Kind::Dna | Kind::Rna) {
+            return Err(Error::ReadSimulation(kind));
+        }
+        for (name, rate) in [
+            ("insertion rate", self.insertion_rate),
+            ("deletion rate", self.deletion_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(Error::InvalidProbability(name.to_string()));
+            }
+        }
+        if !self.coverage.is_finite() || self.coverage < 0.0 {
+            return Err(Error::InvalidCoverage);
+        }
+        let len = template.len();
+        let read_len = self.read_len.min(len);
+        if read_len == 0 {
+            return Ok(Vec::new());
+        }
+
+        let symbols = pwm::symbols(kind);
+        let strands = [
+            template.normalize_case(Case::Upper).into_bytes(),
+            template
+                .reverse_complement()?
+                .normalize_case(Case::Upper)
+                .into_bytes(),
+        ];
+        let n = (self.coverage * len as f64 / read_len as f64).ceil() as usize;
+        let mut reads = Vec::with_capacity(n);
+        for i in 0..n {
+            let strand = if self.both_strands && rng.gen_bool(0.5) {
+                Strand::Reverse
+            } else {
+                Strand::Forward
+            };
+            let bytes = &strands[(strand == Strand::Reverse) as usize];
+            let start = rng.gen_range(0..=len - read_len);
+
+            let (mut read, mut qual) = (Vec::with_capacity(read_len), Vec::with_capacity(read_len));
+            let mut end = start;
+            while read.len() < read_len && end < len {
+                let q = self.qualities.quality(read.len(), read_len);
+                if rng.gen_bool(self.insertion_rate) {
+                    read.push(symbols[rng.gen_range(0..symbols.len())]);
+                    qual.push(q);
+                    continue;
+                }
+                let base = bytes[end];
+                end += 1;
+                if rng.gen_bool(self.deletion_rate) {
+                    continue;
+                }
+                let error = 10f64.powf(-(q as f64) / 10.0).min(1.0);
+                let base = if rng.gen_bool(error) {
+                    let others: Vec<_> = symbols.iter().filter(|&&s| s != base).collect();
+                    *others[rng.gen_range(0..others.len())]
+                } else {
+                    base
+                };
+                read.push(base);
+                qual.push(q);
+            }
+
+            let range = match strand {
+                Strand::Forward => start..end,
+                Strand::Reverse => len - end..len - start,
+            };
+            let seq = Seq::from_vec_with_kind(read, [kind], template.alphabet())?;
+            reads.push(SimulatedRead {
+                record: SeqRecord::with_qual(format!("read_{}", i + 1), seq, qual)?,
+                origin: Region::new(range, strand)?,
+            });
+        }
+        Ok(reads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn record(seq: &str, qual: &[u8]) -> Result<SeqRecord, Error> {
+        SeqRecord::with_qual("read", Seq::dna(seq)?, qual.to_vec())
+    }
+
+    // ===== Overlap Detection Tests ===============================================================
+
+    #[test]
+    fn overlap_contained() {
+        let overlap = find_overlap(b"ACGTACGTTTGGCC", b"ttggcc", 0.0, 3);
+        assert_eq!(
+            overlap,
+            Some(Overlap {
+                start: 8,
+                len: 6,
+                mismatches: 0
+            })
+        );
+    }
+
+    #[test]
+    fn overlap_partial_with_mismatch() {
+        let overlap = find_overlap(b"ACGTACGTAGATCGTA", b"AGATCGGAAGAGC", 0.2, 3);
+        assert_eq!(
+            overlap,
+            Some(Overlap {
+                start: 8,
+                len: 8,
+                mismatches: 1
+            })
+        );
+        assert_eq!(
+            find_overlap(b"ACGTACGTAGATCGTA", b"AGATCGGAAGAGC", 0.0, 3),
+            None
+        );
+    }
+
+    #[test]
+    fn overlap_too_short() {
+        assert_eq!(find_overlap(b"ACGTACAG", b"AGATCGG", 0.0, 3), None);
+        assert!(find_overlap(b"ACGTACAG", b"AGATCGG", 0.0, 2).is_some());
+        assert_eq!(find_overlap(b"AC", b"ACGT", 0.0, 3), None);
+    }
+
+    #[test]
+    fn gapped_overlaps() {
+        let overlap = find_gapped_overlap(b"ACGTACGTAGATCGAAGAGCAC", b"AGATCGGAAGAGC", 0.1, 3);
+        assert_eq!(
+            overlap,
+            Some(Overlap {
+                start: 8,
+                len: 12,
+                mismatches: 1
+            })
+        );
+        let overlap = find_gapped_overlap(b"ACGTACGTAGATTCGGA", b"AGATCGGAAGAGC", 0.2, 3);
+        assert_eq!(
+            overlap,
+            Some(Overlap {
+                start: 8,
+                len: 9,
+                mismatches: 1
+            })
+        );
+        assert_eq!(
+            find_gapped_overlap(b"ACGTACGTAGATTCGGA", b"AGATCGGAAGAGC", 0.0, 3),
+            None
+        );
+        assert_eq!(find_gapped_overlap(b"AC", b"ACGT", 0.0, 3), None);
+    }
+
+    // ===== Adapter Trimming Tests ================================================================
+
+    fn illumina() -> Result<Adapter, Error> {
+        Ok(Adapter::new(
+            "TruSeq",
+            Seq::dna("AGATCGGAAGAGC")?,
+            ReadEnd::Three,
+        ))
+    }
+
+    #[test]
+    fn trim_three_prime_adapter() -> Result<(), Error> {
+        let trimmer = AdapterTrimmer::new([illumina()?]);
+        let read = record(
+            "ACGTTGCAACGTAGATCGGAAGAGCACAC",
+            &(0..29).collect::<Vec<_>>(),
+        )?;
+        let (trimmed, matches) = trimmer.trim(&read);
+        assert_eq!(trimmed.seq().to_string(), "ACGTTGCAACGT");
+        assert_eq!(trimmed.qual(), Some(&(0..12).collect::<Vec<_>>()[..]));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].trimmed, 17);
+
+        // NOTE: An adapter with a deleted base
+        let read = record("ACGTTGCAACGTAGATCGAAGAGCACAC", &[30; 28])?;
+        let (trimmed, matches) = trimmer.trim(&read);
+        assert_eq!(trimmed.seq().to_string(), "ACGTTGCAACGT");
+        assert_eq!(matches[0].overlap.mismatches, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn trim_partial_adapter() -> Result<(), Error> {
+        let trimmer = AdapterTrimmer::new([illumina()?]);
+        let read = record("ACGTTGCAACGTAGATC", &[30; 17])?;
+        let (trimmed, _) = trimmer.trim(&read);
+        assert_eq!(trimmed.seq().to_string(), "ACGTTGCAACGT");
+        let trimmer = trimmer.min_overlap(6);
+        let (trimmed, matches) = trimmer.trim(&read);
+        assert_eq!(trimmed, read);
+        assert!(matches.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn trim_five_prime_adapter() -> Result<(), Error> {
+        let adapter = Adapter::new("Primer", Seq::dna("GGTTCCAA")?, ReadEnd::Five);
+        let trimmer = AdapterTrimmer::new([adapter]);
+        let read = record("ttccaaACGTACGTACGT", &[30; 18])?;
+        let (trimmed, matches) = trimmer.trim(&read);
+        assert_eq!(trimmed.seq().to_string(), "ACGTACGTACGT");
+        assert_eq!(matches[0].trimmed, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn trim_adapter_stats() -> Result<(), Error> {
+        let primer = Adapter::new("Primer", Seq::dna("GGTTCCAA")?, ReadEnd::Five);
+        let trimmer = AdapterTrimmer::new([illumina()?, primer]);
+        let reads = vec![
+            record("GGTTCCAAACGTACGTAGATCGG", &[30; 23])?,
+            record("ACGTACGTAGATCGGAAG", &[30; 18])?,
+            record("TTTTTTTTTTTTTTTT", &[30; 16])?,
+        ];
+        let (reads, stats) = trimmer.trim_reads(reads);
+        assert_eq!(reads[0].seq().to_string(), "ACGTACGT");
+        assert_eq!(reads[1].seq().to_string(), "ACGTACGT");
+        assert_eq!(reads[2].seq().to_string(), "TTTTTTTTTTTTTTTT");
+        assert_eq!(
+            stats,
+            vec![
+                AdapterStats {
+                    name: "TruSeq".to_string(),
+                    reads: 2,
+                    bases: 17
+                },
+                AdapterStats {
+                    name: "Primer".to_string(),
+                    reads: 1,
+                    bases: 8
+                }
+            ]
+        );
+        Ok(())
+    }
+
+    // ===== Paired-End Merging Tests ==============================================================
+
+    const INSERT: &str = "ATGCGTACGTTAGCCTAGGCATCGATCGGA";
+
+    fn read_pair(r1: &str, r2: &str) -> Result<(SeqRecord, SeqRecord), Error> {
+        let rc = Seq::dna(r2)?.reverse_complement()?;
+        Ok((
+            SeqRecord::with_qual("pair_1", Seq::dna(r1)?, vec![30; r1.len()])?,
+            SeqRecord::with_qual("pair_1", rc, vec![20; r2.len()])?,
+        ))
+    }
+
+    #[test]
+    fn merge_overlapping_pair() -> Result<(), Error> {
+        let (r1, r2) = read_pair(&INSERT[..20], &INSERT[10..])?;
+        let merged = PairMerger::new().merge(&r1, &r2)?.unwrap();
+        assert_eq!(merged.id(), "pair_1");
+        assert_eq!(merged.seq().to_string(), INSERT);
+        let mut qual = vec![30; 10];
+        qual.extend([41; 10]);
+        qual.extend([20; 10]);
+        assert_eq!(merged.qual(), Some(qual.as_slice()));
+        Ok(())
+    }
+
+    #[test]
+    fn merge_resolves_by_quality() -> Result<(), Error> {
+        let r1 = SeqRecord::with_qual("pair_1", Seq::dna("ATGCGTACGTTAGCCAAGGC")?, {
+            let mut qual = vec![30; 20];
+            qual[15] = 5;
+            qual
+        })?;
+        let (_, r2) = read_pair(&INSERT[..20], &INSERT[10..])?;
+        let merged = PairMerger::new().merge(&r1, &r2)?.unwrap();
+        assert_eq!(merged.seq().to_string(), INSERT);
+        assert_eq!(merged.qual().unwrap()[15], 15);
+
+        let r2 = SeqRecord::with_qual("pair_1", r2.seq().clone(), vec![2; 20])?;
+        let merged = PairMerger::new().merge(&r1, &r2)?.unwrap();
+        assert_eq!(&merged.seq().to_string()[14..17], "CAA");
+        assert_eq!(merged.qual().unwrap()[15], 3);
+        Ok(())
+    }
+
+    #[test]
+    fn merge_contained_pair() -> Result<(), Error> {
+        let (r1, r2) = read_pair(INSERT, &INSERT[5..25])?;
+        let merged = PairMerger::new().merge(&r1, &r2)?.unwrap();
+        assert_eq!(merged.seq().to_string(), INSERT);
+        assert_eq!(merged.qual().unwrap()[..6], [30, 30, 30, 30, 30, 41]);
+        assert_eq!(merged.qual().unwrap()[24..], [41, 30, 30, 30, 30, 30]);
+        Ok(())
+    }
+
+    #[test]
+    fn merge_unmergeable_pairs() -> Result<(), Error> {
+        let merger = PairMerger::new();
+        let pairs = [
+            read_pair(&INSERT[..20], &INSERT[10..])?,
+            read_pair(&INSERT[..15], &INSERT[11..])?,
+        ];
+        let merged = merger.merge_pairs(pairs.clone())?;
+        assert_eq!(merged.merged.len(), 1);
+        assert_eq!(merged.unmerged, [pairs[1].clone()]);
+        let merged = merger.min_overlap(4).merge_pairs(pairs)?;
+        assert_eq!(merged.merged[1].seq().to_string(), INSERT);
+        assert!(merged.unmerged.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn merge_invalid_pairs() -> Result<(), Error> {
+        let merger = PairMerger::new();
+        let (r1, r2) = read_pair(&INSERT[..20], &INSERT[10..])?;
+        let unqualified = SeqRecord::new("pair_1", r2.seq().clone());
+        assert_eq!(merger.merge(&r1, &unqualified), Err(Error::MissingQuality));
+        let rna = SeqRecord::with_qual("pair_1", r2.seq().convert(Kind::Rna)?, vec![20; 20])?;
+        assert_eq!(
+            merger.merge(&r1, &rna),
+            Err(Error::MergeKindMismatch(Kind::Dna, Kind::Rna))
+        );
+        Ok(())
+    }
+
+    // ===== Demultiplexing Tests ==================================================================
+
+    fn demultiplexer() -> Result<Demultiplexer, Error> {
+        Ok(Demultiplexer::new([
+            ("sample_1", Seq::dna("ACGTAC")?),
+            ("sample_2", Seq::dna("TGCATG")?),
+            ("sample_3", Seq::dna("ACGTTG")?),
+        ]))
+    }
+
+    #[test]
+    fn assign_exact_barcodes() -> Result<(), Error> {
+        let demux = demultiplexer()?;
+        assert_eq!(demux.assign(&record("ACGTACGGGG", &[30; 10])?), Some(0));
+        assert_eq!(demux.assign(&record("tgcatgGGGG", &[30; 10])?), Some(1));
+        assert_eq!(demux.assign(&record("ACGTAAGGGG", &[30; 10])?), None);
+        assert_eq!(demux.assign(&record("ACGT", &[30; 4])?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn assign_with_mismatches() -> Result<(), Error> {
+        let demux = demultiplexer()?.max_mismatches(1);
+        assert_eq!(demux.assign(&record("TGCTTGGGGG", &[30; 10])?), Some(1));
+        // NOTE: One mismatch away from both `sample_1` and `sample_3`
+        assert_eq!(demux.assign(&record("ACGTAGGGGG", &[30; 10])?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn demultiplex_reads() -> Result<(), Error> {
+        let demux = demultiplexer()?;
+        let reads = vec![
+            record("ACGTACGGGG", &[30; 10])?,
+            record("TGCATGCCCC", &[30; 10])?,
+            record("ACGTACAAAA", &[30; 10])?,
+            record("TTTTTTTTTT", &[30; 10])?,
+        ];
+        let demuxed = demux.demultiplex(reads.clone());
+        assert_eq!(demuxed.samples.len(), 3);
+        let sample_1: Vec<_> = demuxed.samples["sample_1"]
+            .iter()
+            .map(|r| r.seq().to_string())
+            .collect();
+        assert_eq!(sample_1, ["GGGG", "AAAA"]);
+        assert_eq!(demuxed.samples["sample_2"].len(), 1);
+        assert!(demuxed.samples["sample_3"].is_empty());
+        assert_eq!(demuxed.unassigned, [reads[3].clone()]);
+
+        let streamed: Vec<_> = demux
+            .demultiplex_iter(reads.clone())
+            .map(|(sample, r)| (sample, r.seq().to_string()))
+            .collect();
+        assert_eq!(
+            streamed,
+            [
+                (Some("sample_1"), "GGGG".to_string()),
+                (Some("sample_2"), "CCCC".to_string()),
+                (Some("sample_1"), "AAAA".to_string()),
+                (None, "TTTTTTTTTT".to_string())
+            ]
+        );
+
+        let demuxed = demux.trim_barcodes(false).demultiplex(reads.clone());
+        assert_eq!(demuxed.samples["sample_2"], [reads[1].clone()]);
+        Ok(())
+    }
+
+    // ===== Deduplication Tests ===================================================================
+
+    #[test]
+    fn dedup_exact_reads() -> Result<(), Error> {
+        let reads = vec![
+            record("ACGTACGT", &[30; 8])?,
+            record("acgtacgt", &[20; 8])?,
+            record("ACGTACGA", &[30; 8])?,
+            record("ACGTACGT", &[10; 8])?,
+        ];
+        let (reads, duplicates) = Deduplicator::exact().dedup(reads);
+        assert_eq!(duplicates, 2);
+        assert_eq!(reads.len(), 2);
+        assert_eq!(reads[0].qual(), Some([30; 8].as_slice()));
+        Ok(())
+    }
+
+    #[test]
+    fn dedup_prefix_parts() -> Result<(), Error> {
+        let parts = vec![
+            Seq::dna("ACGTACGT")?,
+            Seq::dna("ACGTAGGG")?,
+            Seq::dna("ACG")?,
+            Seq::dna("TTGTACGT")?,
+        ];
+        let (kept, duplicates) = Deduplicator::prefix(5).dedup(parts.clone());
+        assert_eq!(duplicates, 1);
+        assert_eq!(kept, [parts[0].clone(), parts[2].clone(), parts[3].clone()]);
+        Ok(())
+    }
+
+    #[test]
+    fn dedup_respects_kind() -> Result<(), Error> {
+        let mut dedup = Deduplicator::exact();
+        assert!(dedup.insert(&Seq::dna("ACGA")?));
+        assert!(dedup.insert(&Seq::protein("ACGA")?));
+        assert!(!dedup.insert(&Seq::dna("ACGA")?));
+        assert_eq!(dedup.duplicates(), 1);
+        Ok(())
+    }
+
+    // ===== Subsampling Tests =====================================================================
+
+    #[test]
+    fn subsample_reservoir() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sample = subsample(0..1000, 10, &mut rng);
+        assert_eq!(sample.len(), 10);
+        assert!(sample.windows(2).all(|w| w[0] < w[1]));
+        assert!(sample.iter().any(|&i| i >= 10));
+    }
+
+    #[test]
+    fn subsample_small_input() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(subsample(0..5, 10, &mut rng), [0, 1, 2, 3, 4]);
+        assert!(subsample(0..5, 0, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn subsample_fraction() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sample: Vec<_> = sample_fraction(0..10_000, 0.1, &mut rng)?.collect();
+        assert!((800..1200).contains(&sample.len()));
+        assert_eq!(sample_fraction(0..100, 1.0, &mut rng)?.count(), 100);
+        assert_eq!(sample_fraction(0..100, 0.0, &mut rng)?.count(), 0);
+        for fraction in [1.5, -0.1, f64::NAN] {
+            assert!(matches!(
+                sample_fraction(0..100, fraction, &mut rng),
+                Err(Error::InvalidProbability(_))
+            ));
+        }
+        Ok(())
+    }
+
+    // ===== Trimming Tests ========================================================================
+
+    #[test]
+    fn trim_fixed_ends() -> Result<(), Error> {
+        let read = record("ACGTACGT", &[30; 8])?;
+        assert_eq!(trim_ends(&read, 2, 1).seq().to_string(), "GTACG");
+        assert_eq!(trim_ends(&read, 0, 0), read);
+        assert!(trim_ends(&read, 5, 5).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn trim_low_quality_tail() -> Result<(), Error> {
+        let read = record("ACGTACGTAC", &[40, 40, 40, 40, 40, 40, 10, 10, 2, 2])?;
+        let trimmed = trim_quality_window(&read, 4, 20.0)?;
+        assert_eq!(trimmed.seq().to_string(), "ACGTA");
+        assert_eq!(trimmed.qual(), Some([40; 5].as_slice()));
+        Ok(())
+    }
+
+    #[test]
+    fn trim_high_quality_read() -> Result<(), Error> {
+        let read = record("ACGTACGTAC", &[35; 10])?;
+        assert_eq!(trim_quality_window(&read, 4, 20.0)?, read);
+        assert_eq!(trim_quality_window(&read, 40, 20.0)?, read);
+        Ok(())
+    }
+
+    #[test]
+    fn trim_quality_errors() -> Result<(), Error> {
+        let read = record("ACGT", &[35; 4])?;
+        assert_eq!(
+            trim_quality_window(&read, 0, 20.0),
+            Err(Error::WindowSize(0))
+        );
+        let read = SeqRecord::new("read", Seq::dna("ACGT")?);
+        assert_eq!(
+            trim_quality_window(&read, 4, 20.0),
+            Err(Error::MissingQuality)
+        );
+        Ok(())
+    }
+
+    // ===== Filtering Tests =======================================================================
+
+    #[test]
+    fn filter_by_length_and_quality() -> Result<(), Error> {
+        let reads = vec![
+            record("ACGTACGT", &[30; 8])?,
+            record("ACG", &[30; 3])?,
+            record("ACGTACGT", &[10; 8])?,
+            SeqRecord::new("read", Seq::dna("ACGTACGT")?),
+        ];
+        assert_eq!(filter_reads(reads.clone(), 4, None).count(), 3);
+        let kept: Vec<_> = filter_reads(reads, 4, Some(20.0)).collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].qual(), Some([30; 8].as_slice()));
+        Ok(())
+    }
+
+    // ===== Read Simulation Tests =================================================================
+
+    fn template(rng: &mut StdRng) -> Result<Seq, Error> {
+        let bytes: Vec<_> = (0..1000).map(|_| b"ACGT"[rng.gen_range(0..4)]).collect();
+        Seq::dna(bytes)
+    }
+
+    #[test]
+    fn simulate_error_free_reads() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let template = template(&mut rng)?;
+        let simulator = ReadSimulator::new()
+            .read_len(100)
+            .coverage(5.0)
+            .qualities(QualityModel::Constant(90))
+            .insertion_rate(0.0)
+            .deletion_rate(0.0);
+        let reads = simulator.simulate(&template, &mut rng)?;
+        assert_eq!(reads.len(), 50);
+        for read in &reads {
+            assert_eq!(read.record.len(), 100);
+            assert_eq!(read.origin.len(), 100);
+            assert_eq!(read.record.seq(), &template.extract(&read.origin)?);
+            assert_eq!(read.record.qual(), Some(&[90; 100][..]));
+        }
+        assert!(reads.iter().any(|r| r.origin.strand() == Strand::Reverse));
+        assert_eq!(reads[0].record.id(), "read_1");
+        Ok(())
+    }
+
+    #[test]
+    fn simulate_sequencing_errors() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let template = template(&mut rng)?;
+        let reads = ReadSimulator::new()
+            .read_len(100)
+            .qualities(QualityModel::Constant(10))
+            .insertion_rate(0.0)
+            .deletion_rate(0.0)
+            .both_strands(false)
+            .simulate(&template, &mut rng)?;
+        assert_eq!(reads.len(), 300);
+        let errors: usize = reads
+            .iter()
+            .map(|r| {
+                r.record
+                    .seq()
+                    .hamming_distance(&template.extract(&r.origin)?)
+            })
+            .sum::<Result<_, Error>>()?;
+        let rate = errors as f64 / 30_000.0;
+        assert!((0.09..0.11).contains(&rate));
+
+        // NOTE: Deletions make reads consume more of the template, and insertions less
+        let deletions = ReadSimulator::new().read_len(100).deletion_rate(0.1);
+        let reads = deletions.simulate(&template, &mut rng)?;
+        let mean: f64 = reads.iter().map(|r| r.origin.len() as f64).sum::<f64>() / 300.0;
+        assert!(mean > 105.0);
+        Ok(())
+    }
+
+    #[test]
+    fn simulate_quality_model() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let reads = ReadSimulator::new()
+            .read_len(5)
+            .coverage(1.0)
+            .qualities(QualityModel::Linear { start: 40, end: 20 })
+            .simulate(&Seq::rna("ACGUACGUAC")?, &mut rng)?;
+        assert_eq!(reads.len(), 2);
+        assert_eq!(reads[0].record.qual(), Some(&[40, 35, 30, 25, 20][..]));
+        assert_eq!(reads[0].record.seq().kind(), Kind::Rna);
+
+        let short = ReadSimulator::new().simulate(&Seq::dna("ACGT")?, &mut rng)?;
+        assert!(short.iter().all(|r| r.record.len() <= 5));
+        assert!(ReadSimulator::new()
+            .simulate(&Seq::dna("")?, &mut rng)?
+            .is_empty());
+        assert_eq!(
+            ReadSimulator::new().simulate(&Seq::protein("MAMAPRTEIN")?, &mut rng),
+            Err(Error::ReadSimulation(Kind::Protein))
+        );
+        assert_eq!(
+            ReadSimulator::new()
+                .deletion_rate(1.5)
+                .simulate(&Seq::dna("ACGT")?, &mut rng),
+            Err(Error::InvalidProbability("deletion rate".to_string()))
+        );
+        assert_eq!(
+            ReadSimulator::new()
+                .insertion_rate(f64::NAN)
+                .simulate(&Seq::dna("ACGT")?, &mut rng),
+            Err(Error::InvalidProbability("insertion rate".to_string()))
+        );
+        for coverage in [f64::INFINITY, f64::NAN, -1.0] {
+            assert_eq!(
+                ReadSimulator::new()
+                    .coverage(coverage)
+                    .simulate(&Seq::dna("ACGT")?, &mut rng),
+                Err(Error::InvalidCoverage)
+            );
+        }
+        Ok(())
+    }
+}
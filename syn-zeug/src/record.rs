@@ -0,0 +1,281 @@
+use bio::io::{fasta, fastq};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::seq::{Error, Seq};
+
+// NOTE: Qualities are stored as decoded Phred scores (not the offset ASCII found in FASTQ files),
+// with one score per element of the sequence
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct SeqRecord {
+    id: String,
+    desc: Option<String>,
+    seq: Seq,
+    qual: Option<Vec<u8>>,
+}
+
+impl SeqRecord {
+    // ===== Constructors ==========================================================================
+
+    pub fn new(id: impl Into<String>, seq: Seq) -> Self {
+        Self {
+            id: id.into(),
+            desc: None,
+            seq,
+            qual: None,
+        }
+    }
+
+    pub fn with_qual(id: impl Into<String>, seq: Seq, qual: Vec<u8>) -> Result<Self, Error> {
+        if seq.len() != qual.len() {
+            return Err(Error::QualityLength(seq.len(), qual.len()));
+        }
+
+        Ok(Self {
+            qual: Some(qual),
+            ..Self::new(id, seq)
+        })
+    }
+
+    pub fn with_desc(self, desc: impl Into<String>) -> Self {
+        Self {
+            desc: Some(desc.into()),
+            ..self
+        }
+    }
+
+    // ===== Getters ===============================================================================
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn desc(&self) -> Option<&str> {
+        self.desc.as_deref()
+    }
+
+    pub fn seq(&self) -> &Seq {
+        &self.seq
+    }
+
+    pub fn qual(&self) -> Option<&[u8]> {
+        self.qual.as_deref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.seq.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seq.is_empty()
+    }
+
+    // ===== Tools =================================================================================
+
+    pub fn subrecord(&self, range: Range<usize>) -> Self {
+        Self {
+            id: self.id.clone(),
+            desc: self.desc.clone(),
+            seq: self.seq.subseq(range.clone()),
+            qual: self.qual.as_ref().map(|q| q[range].to_vec()),
+        }
+    }
+
+    pub fn mean_quality(&self) -> Option<f64> {
+        let qual = self.qual.as_ref()?;
+        if qual.is_empty() {
+            return None;
+        }
+        Some(qual.iter().map(|&q| q as f64).sum::<f64>() / qual.len() as f64)
+    }
+}
+
+impl AsRef<Seq> for SeqRecord {
+    fn as_ref(&self) -> &Seq {
+        &self.seq
+    }
+}
+
+// ===== Interop With rust-bio =====================================================================
+
+// NOTE: FASTQ files store qualities as Phred+33 ASCII, so they are decoded and re-encoded here
+const PHRED_OFFSET: u8 = 33;
+
+impl TryFrom<&fasta::Record> for SeqRecord {
+    type Error = Error;
+
+    fn try_from(record: &fasta::Record) -> Result<Self, Self::Error> {
+        let seq = Seq::new(record.seq())?;
+        Ok(Self {
+            desc: record.desc().map(String::from),
+            ..Self::new(record.id(), seq)
+        })
+    }
+}
+
+impl TryFrom<fasta::Record> for SeqRecord {
+    type Error = Error;
+
+    fn try_from(record: fasta::Record) -> Result<Self, Self::Error> {
+        Self::try_from(&record)
+    }
+}
+
+impl From<&SeqRecord> for fasta::Record {
+    fn from(record: &SeqRecord) -> Self {
+        fasta::Record::with_attrs(record.id(), record.desc(), record.seq().as_bytes())
+    }
+}
+
+impl From<SeqRecord> for fasta::Record {
+    fn from(record: SeqRecord) -> Self {
+        Self::from(&record)
+    }
+}
+
+impl TryFrom<&fastq::Record> for SeqRecord {
+    type Error = Error;
+
+    fn try_from(record: &fastq::Record) -> Result<Self, Self::Error> {
+        let seq = Seq::new(record.seq())?;
+        let qual = record
+            .qual()
+            .iter()
+            .map(|&q| q.checked_sub(PHRED_OFFSET).ok_or(Error::InvalidQuality(q)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            desc: record.desc().map(String::from),
+            ..Self::with_qual(record.id(), seq, qual)?
+        })
+    }
+}
+
+impl TryFrom<fastq::Record> for SeqRecord {
+    type Error = Error;
+
+    fn try_from(record: fastq::Record) -> Result<Self, Self::Error> {
+        Self::try_from(&record)
+    }
+}
+
+impl TryFrom<&SeqRecord> for fastq::Record {
+    type Error = Error;
+
+    fn try_from(record: &SeqRecord) -> Result<Self, Self::Error> {
+        let qual = record
+            .qual()
+            .ok_or(Error::MissingQuality)?
+            .iter()
+            .map(|&q| {
+                q.checked_add(PHRED_OFFSET)
+                    .filter(u8::is_ascii_graphic)
+                    .ok_or(Error::InvalidQuality(q))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(fastq::Record::with_attrs(
+            record.id(),
+            record.desc(),
+            record.seq().as_bytes(),
+            &qual,
+        ))
+    }
+}
+
+impl TryFrom<SeqRecord> for fastq::Record {
+    type Error = Error;
+
+    fn try_from(record: SeqRecord) -> Result<Self, Self::Error> {
+        Self::try_from(&record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_without_qual() -> Result<(), Error> {
+        let record = SeqRecord::new("read_1", Seq::dna("ACGT")?).with_desc("a short read");
+        assert_eq!(record.id(), "read_1");
+        assert_eq!(record.desc(), Some("a short read"));
+        assert_eq!(record.seq().to_string(), "ACGT");
+        assert_eq!(record.qual(), None);
+        assert_eq!(record.mean_quality(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn record_with_qual() -> Result<(), Error> {
+        let record = SeqRecord::with_qual("read_1", Seq::dna("ACGT")?, vec![40, 30, 20, 10])?;
+        assert_eq!(record.len(), 4);
+        assert_eq!(record.qual(), Some([40, 30, 20, 10].as_slice()));
+        assert_eq!(record.mean_quality(), Some(25.0));
+        Ok(())
+    }
+
+    #[test]
+    fn record_qual_length_mismatch() -> Result<(), Error> {
+        let record = SeqRecord::with_qual("read_1", Seq::dna("ACGT")?, vec![40, 30]);
+        assert_eq!(record, Err(Error::QualityLength(4, 2)));
+        Ok(())
+    }
+
+    // ===== rust-bio Interop Tests ================================================================
+
+    #[test]
+    fn fasta_round_trip() -> Result<(), Error> {
+        let bio_record = fasta::Record::with_attrs("seq_1", Some("a plasmid"), b"ACGTNACGT");
+        let record = SeqRecord::try_from(&bio_record)?;
+        assert_eq!(record.id(), "seq_1");
+        assert_eq!(record.desc(), Some("a plasmid"));
+        assert_eq!(record.seq(), &Seq::dna_n("ACGTNACGT")?);
+        assert_eq!(fasta::Record::from(&record), bio_record);
+        Ok(())
+    }
+
+    #[test]
+    fn fasta_invalid_seq() {
+        let bio_record = fasta::Record::with_attrs("seq_1", None, b"ACGT-ACGT");
+        assert!(matches!(
+            SeqRecord::try_from(bio_record),
+            Err(Error::InvalidSeq(_))
+        ));
+    }
+
+    #[test]
+    fn fastq_round_trip() -> Result<(), Error> {
+        let bio_record = fastq::Record::with_attrs("read_1", None, b"ACGU", b"I5+!");
+        let record = SeqRecord::try_from(&bio_record)?;
+        assert_eq!(record.seq(), &Seq::rna("ACGU")?);
+        assert_eq!(record.qual(), Some([40, 20, 10, 0].as_slice()));
+        assert_eq!(fastq::Record::try_from(&record)?, bio_record);
+        Ok(())
+    }
+
+    #[test]
+    fn fastq_invalid_qualities() -> Result<(), Error> {
+        let bio_record = fastq::Record::with_attrs("read_1", None, b"ACGU", b"I5+\x1f");
+        assert_eq!(
+            SeqRecord::try_from(&bio_record),
+            Err(Error::InvalidQuality(0x1f))
+        );
+        let record = SeqRecord::with_qual("read_1", Seq::dna("AC")?, vec![40, 94])?;
+        assert_eq!(
+            fastq::Record::try_from(&record),
+            Err(Error::InvalidQuality(94))
+        );
+        let record = SeqRecord::new("read_1", Seq::dna("AC")?);
+        assert_eq!(fastq::Record::try_from(record), Err(Error::MissingQuality));
+        Ok(())
+    }
+
+    #[test]
+    fn record_subrecord() -> Result<(), Error> {
+        let record = SeqRecord::with_qual("read_1", Seq::dna("ACGT")?, vec![40, 30, 20, 10])?;
+        let sub = record.subrecord(1..3);
+        assert_eq!(sub.id(), "read_1");
+        assert_eq!(sub.seq().to_string(), "CG");
+        assert_eq!(sub.qual(), Some([30, 20].as_slice()));
+        Ok(())
+    }
+}
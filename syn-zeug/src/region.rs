@@ -0,0 +1,262 @@
+use bio::seq_analysis::orf::Orf;
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops::Range};
+
+use crate::{
+    seq::{Error, Seq},
+    types::Strand,
+};
+
+// NOTE: Regions are always stored as 0-based, end-exclusive ranges of the forward strand (the same
+// convention as Rust's `Range`), so converting to anything else has to be done explicitly. The
+// strand only says which way the region is read — it never changes the coordinates
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Region {
+    start: usize,
+    end: usize,
+    strand: Strand,
+}
+
+impl Region {
+    // ===== Constructors ==========================================================================
+
+    pub fn new(range: Range<usize>, strand: Strand) -> Result<Self, Error> {
+        if range.start > range.end {
+            return Err(Error::InvalidRegion(range.start, range.end));
+        }
+        Ok(Self {
+            start: range.start,
+            end: range.end,
+            strand,
+        })
+    }
+
+    // NOTE: For 1-based, end-inclusive coordinates (like those in GenBank and GFF files), an empty
+    // region is written with an end one less than its start
+    pub fn from_one_based(start: usize, end: usize, strand: Strand) -> Result<Self, Error> {
+        let invalid = Error::InvalidRegion(start, end);
+        if start == 0 || start > end.checked_add(1).ok_or(invalid.clone())? {
+            return Err(invalid);
+        }
+        Self::new(start - 1..end, strand)
+    }
+
+    pub fn from_zero_based_inclusive(
+        start: usize,
+        end: usize,
+        strand: Strand,
+    ) -> Result<Self, Error> {
+        let end = end.checked_add(1).ok_or(Error::InvalidRegion(start, end))?;
+        Self::new(start..end, strand)
+    }
+
+    // ===== Getters ===============================================================================
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn one_based(&self) -> (usize, usize) {
+        (self.start + 1, self.end)
+    }
+
+    pub fn zero_based_inclusive(&self) -> Option<(usize, usize)> {
+        (!self.is_empty()).then(|| (self.start, self.end - 1))
+    }
+
+    // ===== Tools =================================================================================
+
+    pub fn with_strand(self, strand: Strand) -> Self {
+        Self { strand, ..self }
+    }
+
+    pub fn contains(&self, position: usize) -> bool {
+        self.range().contains(&position)
+    }
+
+    // NOTE: Overlaps and intersections only look at coordinates, ignoring strands. Intersections
+    // keep the strand of `self`
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+            ..*self
+        })
+    }
+}
+
+impl TryFrom<Range<usize>> for Region {
+    type Error = Error;
+
+    fn try_from(range: Range<usize>) -> Result<Self, Self::Error> {
+        Self::new(range, Strand::Forward)
+    }
+}
+
+impl From<Region> for Range<usize> {
+    fn from(region: Region) -> Self {
+        region.range()
+    }
+}
+
+impl From<&Orf> for Region {
+    fn from(orf: &Orf) -> Self {
+        Self {
+            start: orf.start,
+            end: orf.end,
+            strand: Strand::Forward,
+        }
+    }
+}
+
+// NOTE: Regions are displayed using GenBank's 1-based location syntax
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (start, end) = self.one_based();
+        match self.strand {
+            Strand::Forward => write!(f, "{start}..{end}"),
+            Strand::Reverse => write!(f, "complement({start}..{end})"),
+        }
+    }
+}
+
+impl Seq {
+    // NOTE: Regions on the reverse strand are reverse complemented, so the result always reads 5'
+    // to 3' along the region's own strand
+    pub fn extract(&self, region: &Region) -> Result<Self, Error> {
+        if region.end() > self.len() {
+            return Err(Error::RegionBounds(*region, self.len()));
+        }
+        let subseq = self.subseq(region.range());
+        match region.strand() {
+            Strand::Forward => Ok(subseq),
+            Strand::Reverse => subseq.reverse_complement(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Kind;
+
+    #[test]
+    fn region_conventions() -> Result<(), Error> {
+        let region = Region::new(2..6, Strand::Forward)?;
+        assert_eq!(region, Region::from_one_based(3, 6, Strand::Forward)?);
+        assert_eq!(
+            region,
+            Region::from_zero_based_inclusive(2, 5, Strand::Forward)?
+        );
+        assert_eq!(region.one_based(), (3, 6));
+        assert_eq!(region.zero_based_inclusive(), Some((2, 5)));
+        assert_eq!(region.len(), 4);
+        assert_eq!(Range::from(region), 2..6);
+        assert_eq!(Region::try_from(2..6)?, region);
+
+        let empty = Region::from_one_based(5, 4, Strand::Forward)?;
+        assert!(empty.is_empty());
+        assert_eq!(empty.zero_based_inclusive(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_regions() {
+        assert_eq!(
+            Region::new(Range { start: 6, end: 2 }, Strand::Forward),
+            Err(Error::InvalidRegion(6, 2))
+        );
+        assert_eq!(
+            Region::from_one_based(0, 4, Strand::Reverse),
+            Err(Error::InvalidRegion(0, 4))
+        );
+        assert_eq!(
+            Region::from_one_based(6, 4, Strand::Reverse),
+            Err(Error::InvalidRegion(6, 4))
+        );
+        assert_eq!(
+            Region::from_one_based(1, usize::MAX, Strand::Forward),
+            Err(Error::InvalidRegion(1, usize::MAX))
+        );
+        assert_eq!(
+            Region::from_zero_based_inclusive(0, usize::MAX, Strand::Forward),
+            Err(Error::InvalidRegion(0, usize::MAX))
+        );
+    }
+
+    #[test]
+    fn region_overlaps() -> Result<(), Error> {
+        let a = Region::new(2..6, Strand::Forward)?;
+        let b = Region::new(4..10, Strand::Reverse)?;
+        let c = Region::new(6..8, Strand::Forward)?;
+        assert!(a.contains(2) && a.contains(5) && !a.contains(6));
+        assert!(a.overlaps(&b) && !a.overlaps(&c));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Region::new(4..6, Strand::Forward)?)
+        );
+        assert_eq!(a.intersection(&c), None);
+        Ok(())
+    }
+
+    #[test]
+    fn region_display() -> Result<(), Error> {
+        let region = Region::new(0..10, Strand::Forward)?;
+        assert_eq!(region.to_string(), "1..10");
+        assert_eq!(
+            region.with_strand(Strand::Reverse).to_string(),
+            "complement(1..10)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn extract_regions() -> Result<(), Error> {
+        let seq = Seq::dna("AAGGCCTTAC")?;
+        let forward = Region::new(2..6, Strand::Forward)?;
+        assert_eq!(seq.extract(&forward)?.to_string(), "GGCC");
+        let reverse = Region::new(6..10, Strand::Reverse)?;
+        assert_eq!(seq.extract(&reverse)?.to_string(), "GTAA");
+        let outside = Region::new(8..12, Strand::Forward)?;
+        assert_eq!(seq.extract(&outside), Err(Error::RegionBounds(outside, 10)));
+        assert_eq!(
+            Seq::protein("MAMAPRTEIN")?.extract(&reverse),
+            Err(Error::ReverseComplement(Kind::Protein))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn orf_regions() -> Result<(), Error> {
+        let seq = Seq::dna("GGATGAAATAGGG")?;
+        let orfs = seq.find_orfs(1)?;
+        let (region, orf) = &orfs[0];
+        assert_eq!(region, &Region::new(2..11, Strand::Forward)?);
+        assert_eq!(&seq.extract(region)?, orf);
+        Ok(())
+    }
+}
@@ -1,13 +1,30 @@
 use bio::{
     alignment::distance,
     alphabets::{dna, rna},
-    seq_analysis::orf::{self, Orf},
+    seq_analysis::orf,
+};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Range,
+    slice::SliceIndex,
+    str,
 };
-use serde::{Deserialize, Serialize};
-use std::{fmt, slice::SliceIndex, str};
 
 use crate::{
-    data::{ALPHABETS, ALPHABET_MAP, CODON_TABLE, IUPAC_CODON_TABLE, IUPAC_GC_PROBS},
+    alphabet,
+    data::{
+        ALPHABETS, CODON_TABLE, IUPAC_CODON_TABLE, IUPAC_DNA, IUPAC_GC_PROBS, IUPAC_PROTEIN,
+        IUPAC_RNA,
+    },
+    region::Region,
     types::{ByteMap, Case},
 };
 
@@ -21,29 +38,242 @@ pub enum Error {
     GcContent(Kind),
     HammingDistance(usize, usize),
     DistanceKindMismatch(Kind, Kind),
+    KmerLength(usize),
+    ErrorCorrection(Kind),
+    QualityLength(usize, usize),
+    MissingQuality,
+    WindowSize(usize),
+    EmptySeqSet,
+    InvalidQuality(u8),
+    PatchLength(usize, usize),
+    MergeKindMismatch(Kind, Kind),
+    CodonCount(Kind),
+    TerminatorSearch(Kind),
+    PwmKindMismatch(Kind, Kind),
+    SiteLength(usize, usize),
+    ProfileWidth(usize, usize),
+    PromoterSearch(Kind),
+    RbsSearch(Kind),
+    GuideDesign(Kind),
+    Hydropathy(Kind),
+    Charge(Kind),
+    StructurePrediction(Kind),
+    SubsequenceKindMismatch(Kind, Kind),
+    BackgroundKindMismatch(Kind, Kind),
+    KmerComposition(Kind),
+    InvalidRegion(usize, usize),
+    RegionBounds(Region, usize),
+    InvalidAlphabet(String),
+    DuplicateAlphabet(String),
+    Translation(Kind),
+    AmbiguousCodon(usize),
+    PartialCodon(usize),
+    EmptyFeature(String),
+    GenBank(usize, String),
+    SnapGene(String),
+    EnzymeSearch(Kind),
+    Domestication(String, usize),
+    IllegalSite(String, usize),
+    ReadSimulation(Kind),
+    MutationSimulation(Kind),
+    ZeroMutationRate,
+    Abif(String),
+    InvalidProbability(String),
+    InvalidDivergence,
+    RbsSpacing(usize, usize),
+    ZeroBackground(u8),
+    EmptyProfileRow(usize),
+    InvalidEdit(usize),
+    PatchedLength(usize, usize),
+    InvalidCoverage,
 }
 
+// NOTE: Custom kinds are registered at runtime (see `alphabet::register_alphabet`), so their IDs
+// are only meaningful within the program that registered them
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub enum Kind {
     Dna,
     Rna,
     Protein,
+    Custom(u32),
+}
+
+impl Kind {
+    pub fn is_nucleic_acid(&self) -> bool {
+        matches!(self, Kind::Dna | Kind::Rna)
+    }
 }
 
+// NOTE: `Extended` only exists for proteins, and it is never tried unless it's asked for, since
+// its `U` would otherwise let RNA sequences be mistaken for proteins
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub enum Alphabet {
     Base,
     N,
     Iupac,
+    Extended,
 }
 
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+// TODO: Backing `bytes` with an `Arc<[u8]>` would make clones of large sequences O(1), but nearly
+// every chainable tool builds a fresh buffer anyway, so it's only worth it once there is a
+// copy-on-write path for tools that leave most of the sequence untouched
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct Seq {
     bytes: Vec<u8>,
     kind: Kind,
     alphabet: Alphabet,
 }
 
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AmbiguityReport {
+    pub counts: ByteMap<usize>,
+    pub positions: Vec<usize>,
+}
+
+impl AmbiguityReport {
+    pub fn total(&self) -> usize {
+        self.positions.len()
+    }
+}
+
+// NOTE: Soft-masked (lowercase) and uppercase sequences describe the same molecule, so this wrapper
+// compares, orders, and hashes sequences ignoring case — handy as a `HashSet` or `BTreeMap` key
+#[derive(Copy, Clone, Debug)]
+pub struct IgnoreCase<T: AsRef<Seq>>(pub T);
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct DisplayOptions {
+    line_width: Option<usize>,
+    ruler: bool,
+    preview: Option<usize>,
+}
+
+impl DisplayOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn wrap(self, line_width: usize) -> Self {
+        Self {
+            line_width: Some(line_width).filter(|&w| w > 0),
+            ..self
+        }
+    }
+
+    pub fn ruler(self, ruler: bool) -> Self {
+        Self { ruler, ..self }
+    }
+
+    pub fn preview(self, len: usize) -> Self {
+        Self {
+            preview: Some(len),
+            ..self
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct SeqDisplay<'a> {
+    seq: &'a Seq,
+    options: DisplayOptions,
+}
+
+// NOTE: Built-in kinds are always tried before custom ones, which only have a single tier
+fn potential_kinds(kinds: &[Kind], alphabet: Alphabet) -> Vec<(Kind, Alphabet)> {
+    let custom = kinds
+        .iter()
+        .filter(|k| matches!(k, Kind::Custom(_)))
+        .map(|&k| (k, Alphabet::Base));
+    ALPHABETS
+        .iter()
+        .copied()
+        .filter(|(k, a)| kinds.contains(k) && a <= &alphabet)
+        .chain(custom)
+        .collect()
+}
+
+fn clamp_range(range: Range<usize>, len: usize) -> Range<usize> {
+    let end = range.end.min(len);
+    range.start.min(end)..end
+}
+
+// NOTE: Validation happens as fragments are appended, narrowing down the (kind, alphabet) pairs
+// the sequence could still be, so `build` never rescans and moves the buffer into the `Seq`.
+// Fragments that would make the sequence invalid are rejected and leave the builder untouched
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SeqBuilder {
+    bytes: Vec<u8>,
+    potential_kinds: Vec<(Kind, Alphabet)>,
+    candidates: Vec<(Kind, Alphabet)>,
+}
+
+impl Default for SeqBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeqBuilder {
+    pub fn new() -> Self {
+        Self::with_kind([Kind::Dna, Kind::Rna, Kind::Protein], Alphabet::Iupac)
+    }
+
+    pub fn with_kind(kinds: impl AsRef<[Kind]>, alphabet: Alphabet) -> Self {
+        let potential_kinds = potential_kinds(kinds.as_ref(), alphabet);
+        Self {
+            bytes: Vec::new(),
+            candidates: potential_kinds.clone(),
+            potential_kinds,
+        }
+    }
+
+    pub fn with_capacity(self, capacity: usize) -> Self {
+        Self {
+            bytes: Vec::with_capacity(capacity),
+            ..self
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn append(&mut self, fragment: impl AsRef<[u8]>) -> Result<(), Error> {
+        let fragment = fragment.as_ref();
+        let candidates: Vec<_> = self
+            .candidates
+            .iter()
+            .copied()
+            .filter(|&(k, a)| {
+                alphabet::lookup(k, a)
+                    .is_some_and(|a| fragment.iter().all(|&c| a.symbols.contains(c as usize)))
+            })
+            .collect();
+        if candidates.is_empty() {
+            return Err(Error::InvalidSeq(self.potential_kinds.clone()));
+        }
+        self.candidates = candidates;
+        self.bytes.extend_from_slice(fragment);
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<Seq, Error> {
+        let &(kind, alphabet) = self
+            .candidates
+            .first()
+            .ok_or(Error::InvalidSeq(self.potential_kinds))?;
+        Ok(Seq {
+            bytes: self.bytes,
+            kind,
+            alphabet,
+        })
+    }
+}
+
 impl Seq {
     // ===== "Magic" Constructors ==================================================================
 
@@ -53,16 +283,46 @@ impl Seq {
         alphabet: Alphabet,
     ) -> Result<Self, Error> {
         let seq = seq.as_ref();
-        let kinds = kinds.as_ref();
+        let (kind, alphabet) = Self::validate(seq, kinds.as_ref(), alphabet)?;
+        Ok(Self {
+            bytes: seq.to_vec(),
+            kind,
+            alphabet,
+        })
+    }
 
-        let potential_kinds: Vec<_> = ALPHABETS
-            .iter()
-            .copied()
-            .filter(|(k, a)| kinds.contains(k) && a <= &alphabet)
-            .collect();
+    pub fn new(seq: impl AsRef<[u8]>) -> Result<Self, Error> {
+        Self::new_with_kind(&seq, [Kind::Dna, Kind::Rna, Kind::Protein], Alphabet::Iupac)
+    }
+
+    // NOTE: These take ownership of an existing buffer instead of copying it, which is worth it for
+    // long sequences that have been parsed or built elsewhere
+    pub fn from_vec_with_kind(
+        bytes: Vec<u8>,
+        kinds: impl AsRef<[Kind]>,
+        alphabet: Alphabet,
+    ) -> Result<Self, Error> {
+        let (kind, alphabet) = Self::validate(&bytes, kinds.as_ref(), alphabet)?;
+        Ok(Self {
+            bytes,
+            kind,
+            alphabet,
+        })
+    }
+
+    pub fn from_vec(bytes: Vec<u8>) -> Result<Self, Error> {
+        Self::from_vec_with_kind(
+            bytes,
+            [Kind::Dna, Kind::Rna, Kind::Protein],
+            Alphabet::Iupac,
+        )
+    }
+
+    fn validate(seq: &[u8], kinds: &[Kind], alphabet: Alphabet) -> Result<(Kind, Alphabet), Error> {
+        let potential_kinds = potential_kinds(kinds, alphabet);
         let mut candidates: Vec<_> = potential_kinds
             .iter()
-            .map(|ka| (ka, &ALPHABET_MAP[ka]))
+            .filter_map(|ka| Some((ka, alphabet::lookup(ka.0, ka.1)?)))
             .collect();
 
         // OPTIMISATION: This algorithm will rescan large regions of sequence if the first
@@ -71,7 +331,7 @@ impl Seq {
         // sequence element). To ameliorate the performance hit from rescanning, the mismatch
         // character is used to filter the candidates before a rescan, ensuring that the next
         // attempted candidate won't get stuck on the same character.
-        while let Some((&(kind, alphabet), a)) = candidates.get(0) {
+        while let Some((&(kind, alphabet), a)) = candidates.first() {
             if let Some(c) = seq
                 .iter()
                 .copied()
@@ -79,21 +339,13 @@ impl Seq {
             {
                 candidates.retain(|(_, a)| a.symbols.contains(c as usize));
             } else {
-                return Ok(Self {
-                    bytes: seq.to_vec(),
-                    kind,
-                    alphabet,
-                });
+                return Ok((kind, alphabet));
             }
         }
 
         Err(Error::InvalidSeq(potential_kinds))
     }
 
-    pub fn new(seq: impl AsRef<[u8]>) -> Result<Self, Error> {
-        Self::new_with_kind(&seq, [Kind::Dna, Kind::Rna, Kind::Protein], Alphabet::Iupac)
-    }
-
     // ===== Standard Constructors =================================================================
 
     pub fn dna(seq: impl AsRef<[u8]>) -> Result<Self, Error> {
@@ -128,6 +380,10 @@ impl Seq {
         Self::new_with_kind(seq, [Kind::Protein], Alphabet::Iupac)
     }
 
+    pub fn protein_extended(seq: impl AsRef<[u8]>) -> Result<Self, Error> {
+        Self::new_with_kind(seq, [Kind::Protein], Alphabet::Extended)
+    }
+
     // ===== Getters ===============================================================================
 
     pub fn kind(&self) -> Kind {
@@ -146,6 +402,18 @@ impl Seq {
         self.bytes.is_empty()
     }
 
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn display(&self, options: DisplayOptions) -> SeqDisplay<'_> {
+        SeqDisplay { seq: self, options }
+    }
+
     // ===== Chainable Tools =======================================================================
 
     // OPTIMISATION: In the future, it may be worth looking into a `SubSeq` type that contains
@@ -172,7 +440,7 @@ impl Seq {
                 bytes: rna::revcomp(&self.bytes),
                 ..*self
             }),
-            Kind::Protein => Err(Error::ReverseComplement(self.kind)),
+            Kind::Protein | Kind::Custom(_) => Err(Error::ReverseComplement(self.kind)),
         }
     }
 
@@ -188,6 +456,64 @@ impl Seq {
         Self { bytes, ..*self }
     }
 
+    // NOTE: Masked ranges are clamped to the length of the sequence and may overlap. Inverted
+    // ranges (with a start after their end) are empty, so they mask nothing
+    pub fn soft_mask(&self, ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        let mut bytes = self.bytes.clone();
+        for r in ranges {
+            let r = clamp_range(r, bytes.len());
+            bytes[r].make_ascii_lowercase();
+        }
+        Self { bytes, ..*self }
+    }
+
+    pub fn hard_mask(&self, ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        // NOTE: Custom alphabets don't have a masking symbol, so they can only be soft-masked
+        let (mask, alphabet) = match self.kind {
+            Kind::Dna | Kind::Rna => (b'N', self.alphabet.max(Alphabet::N)),
+            Kind::Protein => (b'X', self.alphabet.max(Alphabet::Iupac)),
+            Kind::Custom(_) => return self.clone(),
+        };
+
+        let mut bytes = self.bytes.clone();
+        let mut masked = false;
+        for r in ranges {
+            let r = clamp_range(r, bytes.len());
+            masked |= !r.is_empty();
+            bytes[r].fill(mask);
+        }
+
+        let alphabet = if masked { alphabet } else { self.alphabet };
+        Self {
+            bytes,
+            alphabet,
+            ..*self
+        }
+    }
+
+    // NOTE: Soft-masked (lowercase) and hard-masked (N or X) elements are both reported, with
+    // neighbouring masked elements merged into a single region
+    pub fn masked_regions(&self) -> Vec<Range<usize>> {
+        let mask = match self.kind {
+            Kind::Dna | Kind::Rna => Some(b'N'),
+            Kind::Protein => Some(b'X'),
+            Kind::Custom(_) => None,
+        };
+        let is_masked = |b: &u8| b.is_ascii_lowercase() || Some(b.to_ascii_uppercase()) == mask;
+
+        let mut regions: Vec<Range<usize>> = Vec::new();
+        for (i, b) in self.bytes.iter().enumerate() {
+            if !is_masked(b) {
+                continue;
+            }
+            match regions.last_mut() {
+                Some(r) if r.end == i => r.end += 1,
+                _ => regions.push(i..i + 1),
+            }
+        }
+        regions
+    }
+
     pub fn convert(&self, kind: Kind) -> Result<Self, Error> {
         match (self.kind, kind) {
             // OPTIMISATION: Using bytestrings and `b + 1` is 103 times faster than converting to a `str`
@@ -239,8 +565,8 @@ impl Seq {
     }
 
     // TODO: Add parameters allowing the user to change which start and stop codons are used
-    pub fn find_orfs(&self, min_len: usize) -> Result<Vec<(Orf, Self)>, Error> {
-        if self.kind == Kind::Protein {
+    pub fn find_orfs(&self, min_len: usize) -> Result<Vec<(Region, Self)>, Error> {
+        if !self.kind.is_nucleic_acid() {
             return Err(Error::FindOrf(self.kind));
         }
 
@@ -259,14 +585,14 @@ impl Seq {
         let finder = orf::Finder::new(start_codons, stop_codons, min_len);
         Ok(finder
             .find_all(&self.convert(Kind::Rna)?.normalize_case(Case::Upper).bytes)
-            .map(|orf| (orf, self.subseq(orf.start..orf.end)))
+            .map(|orf| (Region::from(&orf), self.subseq(orf.start..orf.end)))
             .collect())
     }
 
     // ===== Terminal Tools ========================================================================
 
     pub fn gc_content(&self) -> Result<f64, Error> {
-        if self.kind == Kind::Protein {
+        if !self.kind.is_nucleic_acid() {
             return Err(Error::GcContent(self.kind));
         }
 
@@ -282,7 +608,7 @@ impl Seq {
                 .iter()
                 .filter(|&&b| matches!(b, b'G' | b'C'))
                 .count() as f64,
-            Alphabet::N | Alphabet::Iupac => {
+            Alphabet::N | Alphabet::Iupac | Alphabet::Extended => {
                 let counts = seq.count_elements();
                 IUPAC_GC_PROBS
                     .into_iter()
@@ -302,6 +628,95 @@ impl Seq {
         counts
     }
 
+    pub fn count_kmers(&self, k: usize) -> Result<HashMap<Vec<u8>, usize>, Error> {
+        if k == 0 {
+            return Err(Error::KmerLength(k));
+        }
+
+        let seq = self.normalize_case(Case::Upper);
+        let mut counts = HashMap::new();
+        for kmer in seq.bytes.windows(k) {
+            *counts.entry(kmer.to_vec()).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    // NOTE: Unlike `==`, this ignores the alphabet, so only the kind and elements matter
+    pub fn eq_ignore_case(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.bytes.eq_ignore_ascii_case(&other.bytes)
+    }
+
+    pub fn cmp_ignore_case(&self, other: &Self) -> Ordering {
+        self.kind.cmp(&other.kind).then_with(|| {
+            let upper = |s: &Self| {
+                s.bytes
+                    .iter()
+                    .map(u8::to_ascii_uppercase)
+                    .collect::<Vec<_>>()
+            };
+            upper(self).cmp(&upper(other))
+        })
+    }
+
+    // NOTE: This is a 64-bit FNV-1a hash of the kind and uppercased elements. Unlike the `Hash`
+    // implementation (which depends on the `Hasher` used), it's stable between runs, platforms,
+    // and versions of Rust, so it's safe to store
+    pub fn hash_canonical(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let kind = self.kind.to_string();
+        kind.as_bytes()
+            .iter()
+            .chain(b":")
+            .copied()
+            .chain(self.bytes.iter().map(u8::to_ascii_uppercase))
+            .fold(FNV_OFFSET, |h, b| (h ^ b as u64).wrapping_mul(FNV_PRIME))
+    }
+
+    // NOTE: Ambiguity codes are any IUPAC codes standing for more than one base or residue. Their
+    // counts are keyed by the uppercase code, but positions are reported regardless of case
+    pub fn ambiguous_content(&self) -> AmbiguityReport {
+        let codes = match self.kind {
+            Kind::Dna => &IUPAC_DNA,
+            Kind::Rna => &IUPAC_RNA,
+            Kind::Protein => &IUPAC_PROTEIN,
+            Kind::Custom(_) => {
+                return AmbiguityReport {
+                    counts: ByteMap::default(),
+                    positions: Vec::new(),
+                }
+            }
+        };
+
+        let mut counts = ByteMap::default();
+        let mut positions = Vec::new();
+        for (i, b) in self.bytes.iter().map(u8::to_ascii_uppercase).enumerate() {
+            if codes.contains_key(&b) {
+                counts[b] += 1;
+                positions.push(i);
+            }
+        }
+        AmbiguityReport { counts, positions }
+    }
+
+    // NOTE: Runs are found case-insensitively, and the first of several equally long runs wins
+    pub fn max_homopolymer_run(&self) -> Option<(u8, Range<usize>)> {
+        let mut best: Option<(u8, Range<usize>)> = None;
+        let mut start = 0;
+        for i in 1..=self.bytes.len() {
+            let run_ended =
+                i == self.bytes.len() || !self.bytes[i].eq_ignore_ascii_case(&self.bytes[start]);
+            if run_ended {
+                if best.as_ref().is_none_or(|(_, r)| i - start > r.len()) {
+                    best = Some((self.bytes[start].to_ascii_uppercase(), start..i));
+                }
+                start = i;
+            }
+        }
+        best
+    }
+
     pub fn hamming_distance(&self, other: &Self) -> Result<usize, Error> {
         if self.len() != other.len() {
             return Err(Error::HammingDistance(self.len(), other.len()));
@@ -336,6 +751,64 @@ impl Seq {
             &other.normalize_case(Case::Upper).bytes,
         ) as usize)
     }
+
+    // NOTE: When there are several longest common subsequences, only one is returned. Elements
+    // are compared case-insensitively, and the subsequence keeps the case of `self`
+    pub fn longest_common_subsequence(&self, other: &Self) -> Result<Self, Error> {
+        if self.kind != other.kind {
+            return Err(Error::SubsequenceKindMismatch(self.kind, other.kind));
+        }
+
+        let (a, b) = (&self.bytes, &other.bytes);
+        let width = b.len() + 1;
+        let mut lens = vec![0usize; (a.len() + 1) * width];
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                lens[i * width + j] = if a[i - 1].eq_ignore_ascii_case(&b[j - 1]) {
+                    lens[(i - 1) * width + j - 1] + 1
+                } else {
+                    lens[(i - 1) * width + j].max(lens[i * width + j - 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (a.len(), b.len());
+        let mut lcs = Vec::with_capacity(lens[i * width + j]);
+        while i > 0 && j > 0 {
+            if a[i - 1].eq_ignore_ascii_case(&b[j - 1]) {
+                lcs.push(a[i - 1]);
+                i -= 1;
+                j -= 1;
+            } else if lens[(i - 1) * width + j] >= lens[i * width + j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+        lcs.reverse();
+        Ok(Self {
+            bytes: lcs,
+            ..*self
+        })
+    }
+
+    // NOTE: Returns the (0-indexed) positions of the earliest occurrence of `motif` as a
+    // (not-necessarily contiguous) subsequence, or `None` if it doesn't occur at all
+    pub fn find_subsequence(&self, motif: &Self) -> Result<Option<Vec<usize>>, Error> {
+        if self.kind != motif.kind {
+            return Err(Error::SubsequenceKindMismatch(self.kind, motif.kind));
+        }
+
+        let mut positions = Vec::with_capacity(motif.len());
+        let mut elements = self.bytes.iter().enumerate();
+        for m in &motif.bytes {
+            match elements.find(|(_, b)| b.eq_ignore_ascii_case(m)) {
+                Some((i, _)) => positions.push(i),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(positions))
+    }
 }
 
 impl fmt::Display for Error {
@@ -357,6 +830,115 @@ impl fmt::Display for Error {
             Error::DistanceKindMismatch(k1, k2) => {
                 write!(f, "Cannot compute distance between {k1} and {k2}")?;
             }
+            Error::KmerLength(k) => write!(f, "Cannot use k-mers of length {k}")?,
+            Error::ErrorCorrection(kind) => write!(f, "Cannot correct errors in {kind}")?,
+            Error::QualityLength(l1, l2) => write!(
+                f,
+                "The number of qualities ({l2}) did not match the sequence length ({l1})"
+            )?,
+            Error::MissingQuality => write!(f, "The record did not contain any qualities")?,
+            Error::WindowSize(w) => write!(f, "Cannot use a window of size {w}")?,
+            Error::EmptySeqSet => write!(f, "Cannot summarise an empty set of sequences")?,
+            Error::InvalidQuality(q) => write!(f, "Invalid quality score: {q}")?,
+            Error::PatchLength(l1, l2) => write!(
+                f,
+                "Cannot apply a patch for a sequence of length {l1} to one of length {l2}"
+            )?,
+            Error::MergeKindMismatch(k1, k2) => {
+                write!(f, "Cannot merge a {k1} read with a {k2} read")?;
+            }
+            Error::CodonCount(kind) => write!(f, "Cannot count codons in {kind}")?,
+            Error::TerminatorSearch(kind) => write!(f, "Cannot search for terminators in {kind}")?,
+            Error::PwmKindMismatch(k1, k2) => write!(f, "Cannot use a {k1} matrix with {k2}")?,
+            Error::SiteLength(l1, l2) => write!(
+                f,
+                "All sites must have the same length (expected {l1}, found {l2})"
+            )?,
+            Error::ProfileWidth(w1, w2) => write!(
+                f,
+                "Profile rows must have one count per symbol (expected {w1}, found {w2})"
+            )?,
+            Error::PromoterSearch(kind) => write!(f, "Cannot search for promoters in {kind}")?,
+            Error::RbsSearch(kind) => {
+                write!(f, "Cannot search for ribosome binding sites in {kind}")?;
+            }
+            Error::GuideDesign(kind) => write!(f, "Cannot design CRISPR guides for {kind}")?,
+            Error::Hydropathy(kind) => write!(f, "Cannot compute the hydropathy of {kind}")?,
+            Error::Charge(kind) => write!(f, "Cannot compute the charge of {kind}")?,
+            Error::StructurePrediction(kind) => {
+                write!(f, "Cannot predict the secondary structure of {kind}")?;
+            }
+            Error::SubsequenceKindMismatch(k1, k2) => {
+                write!(f, "Cannot compare subsequences of {k1} and {k2}")?;
+            }
+            Error::BackgroundKindMismatch(k1, k2) => {
+                write!(f, "Cannot use a {k1} background with {k2}")?;
+            }
+            Error::KmerComposition(kind) => {
+                write!(f, "Cannot compute the k-mer composition of {kind}")?;
+            }
+            Error::InvalidRegion(start, end) => {
+                write!(f, "Cannot create a region from {start} to {end}")?;
+            }
+            Error::RegionBounds(region, len) => {
+                write!(f, "The region {region} is out of bounds for a sequence of length {len}")?;
+            }
+            Error::InvalidAlphabet(name) => {
+                write!(f, "The {name} alphabet must contain printable ASCII symbols")?;
+            }
+            Error::DuplicateAlphabet(name) => {
+                write!(f, "An alphabet named {name} has already been registered")?;
+            }
+            Error::Translation(kind) => write!(f, "Cannot translate {kind}")?,
+            Error::AmbiguousCodon(pos) => write!(f, "Found an ambiguous codon at position {pos}")?,
+            Error::PartialCodon(len) => {
+                write!(f, "Found a trailing partial codon of length {len}")?;
+            }
+            Error::EmptyFeature(kind) => write!(f, "The {kind} feature must have a location")?,
+            Error::GenBank(line, reason) => write!(f, "Invalid GenBank file (line {line}): {reason}")?,
+            Error::SnapGene(reason) => write!(f, "Invalid SnapGene file: {reason}")?,
+            Error::EnzymeSearch(kind) => {
+                write!(f, "Cannot search for restriction sites in {kind}")?;
+            }
+            Error::Domestication(site, position) => write!(
+                f,
+                "The {site} site at position {position} cannot be removed by a silent mutation"
+            )?,
+            Error::IllegalSite(enzyme, position) => {
+                write!(f, "Found an illegal {enzyme} site at position {position}")?;
+            }
+            Error::ReadSimulation(kind) => {
+                write!(f, "Cannot simulate sequencing reads from {kind}")?;
+            }
+            Error::MutationSimulation(kind) => {
+                write!(f, "Cannot simulate the mutation of {kind}")?;
+            }
+            Error::ZeroMutationRate => {
+                write!(f, "Cannot reach a target divergence without any mutations")?;
+            }
+            Error::Abif(reason) => write!(f, "Invalid AB1 file: {reason}")?,
+            Error::InvalidProbability(name) => {
+                write!(f, "The {name} must be a probability between 0 and 1")?;
+            }
+            Error::InvalidDivergence => write!(f, "The target divergence must be finite")?,
+            Error::RbsSpacing(start, end) => {
+                write!(f, "Cannot use a spacing range from {start} to {end}")?;
+            }
+            Error::ZeroBackground(symbol) => write!(
+                f,
+                "The background probability of {} must be above zero",
+                *symbol as char
+            )?,
+            Error::EmptyProfileRow(i) => write!(f, "Position {i} of the profile has no counts")?,
+            Error::InvalidEdit(pos) => write!(
+                f,
+                "The edit at position {pos} overlaps an earlier edit or runs past the sequence"
+            )?,
+            Error::PatchedLength(l1, l2) => write!(
+                f,
+                "The patch should produce a sequence of length {l1}, but produced one of length {l2}"
+            )?,
+            Error::InvalidCoverage => write!(f, "The coverage must be finite and non-negative")?,
         }
         Ok(())
     }
@@ -370,6 +952,10 @@ impl fmt::Display for Kind {
             Kind::Dna => write!(f, "DNA")?,
             Kind::Rna => write!(f, "RNA")?,
             Kind::Protein => write!(f, "Protein")?,
+            Kind::Custom(id) => match alphabet::custom_name(*id) {
+                Some(name) => write!(f, "{name}")?,
+                None => write!(f, "Custom({id})")?,
+            },
         }
         Ok(())
     }
@@ -381,20 +967,225 @@ impl fmt::Display for Alphabet {
             Alphabet::Base => write!(f, "Base")?,
             Alphabet::N => write!(f, "N")?,
             Alphabet::Iupac => write!(f, "IUPAC")?,
+            Alphabet::Extended => write!(f, "Extended")?,
         }
         Ok(())
     }
 }
 
+// NOTE: Human-readable formats (like JSON) get the sequence as a string, but binary formats (like
+// bincode or CBOR) get a compact byte string. Either way, the sequence is checked against its
+// alphabet when it's deserialized, so invalid sequences can't be smuggled in
+impl Serialize for Seq {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Bytes<'a>(&'a [u8]);
+
+        impl Serialize for Bytes<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+
+        let human_readable = serializer.is_human_readable();
+        let mut state = serializer.serialize_struct("Seq", 3)?;
+        if human_readable {
+            state.serialize_field(
+                "seq",
+                str::from_utf8(&self.bytes).expect("Seq did not contain valid UTF-8"),
+            )?;
+        } else {
+            state.serialize_field("seq", &Bytes(&self.bytes))?;
+        }
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("alphabet", &self.alphabet)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Seq {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Bytes(Vec<u8>);
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Bytes;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence string or byte string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(Bytes(v.as_bytes().to_vec()))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(Bytes(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(Bytes(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(b) = seq.next_element()? {
+                    bytes.push(b);
+                }
+                Ok(Bytes(bytes))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Bytes {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(BytesVisitor)
+                } else {
+                    deserializer.deserialize_byte_buf(BytesVisitor)
+                }
+            }
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename = "Seq")]
+        struct RawSeq {
+            seq: Bytes,
+            kind: Kind,
+            alphabet: Alphabet,
+        }
+
+        let RawSeq {
+            seq: Bytes(bytes),
+            kind,
+            alphabet,
+        } = RawSeq::deserialize(deserializer)?;
+        let symbols = &alphabet::lookup(kind, alphabet)
+            .ok_or_else(|| de::Error::custom(Error::InvalidSeq(vec![(kind, alphabet)])))?
+            .symbols;
+        if bytes.iter().any(|&b| !symbols.contains(b as usize)) {
+            return Err(de::Error::custom(Error::InvalidSeq(vec![(kind, alphabet)])));
+        }
+
+        Ok(Self {
+            bytes,
+            kind,
+            alphabet,
+        })
+    }
+}
+
+impl<T: AsRef<Seq>> PartialEq for IgnoreCase<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ref().eq_ignore_case(other.0.as_ref())
+    }
+}
+
+impl<T: AsRef<Seq>> Eq for IgnoreCase<T> {}
+
+impl<T: AsRef<Seq>> PartialOrd for IgnoreCase<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: AsRef<Seq>> Ord for IgnoreCase<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.as_ref().cmp_ignore_case(other.0.as_ref())
+    }
+}
+
+impl<T: AsRef<Seq>> Hash for IgnoreCase<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let seq = self.0.as_ref();
+        seq.kind.hash(state);
+        for b in &seq.bytes {
+            state.write_u8(b.to_ascii_uppercase());
+        }
+    }
+}
+
+impl AsRef<[u8]> for Seq {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+// NOTE: This makes `&Seq` an `IntoTextIterator`, so it can be passed straight to rust-bio
+impl<'a> IntoIterator for &'a Seq {
+    type Item = &'a u8;
+    type IntoIter = std::slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.bytes.iter()
+    }
+}
+
+impl AsRef<Seq> for Seq {
+    fn as_ref(&self) -> &Seq {
+        self
+    }
+}
+
+// NOTE: A precision (like `{:.8}`) is shorthand for `DisplayOptions::preview`
 impl fmt::Display for Seq {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(str::from_utf8(&self.bytes).expect("Seq did not contain valid UTF-8"))
+        match f.precision() {
+            Some(len) => self.display(DisplayOptions::new().preview(len)).fmt(f),
+            None => {
+                f.write_str(str::from_utf8(&self.bytes).expect("Seq did not contain valid UTF-8"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for SeqDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn as_str(bytes: &[u8]) -> &str {
+            str::from_utf8(bytes).expect("Seq did not contain valid UTF-8")
+        }
+        let bytes = &self.seq.bytes;
+
+        // NOTE: A preview shows (roughly) equal numbers of elements from each end of the sequence
+        if let Some(len) = self.options.preview.filter(|&l| l < bytes.len()) {
+            let head = len - len / 2;
+            let tail = bytes.len() - len / 2;
+            let units = match self.seq.kind {
+                Kind::Dna => "bp",
+                Kind::Rna => "nt",
+                Kind::Protein => "aa",
+                Kind::Custom(_) => "el",
+            };
+            // NOTE: Format the length with thousands separators
+            let total = bytes.len().to_string();
+            let total: Vec<_> = total.as_bytes().rchunks(3).rev().map(as_str).collect();
+            let total = total.join(",");
+            return write!(
+                f,
+                "{}…{} ({total} {units})",
+                as_str(&bytes[..head]),
+                as_str(&bytes[tail..])
+            );
+        }
+
+        let line_width = self.options.line_width.unwrap_or(bytes.len().max(1));
+        let last_line_start = bytes.len().saturating_sub(1) / line_width * line_width + 1;
+        let number_width = last_line_start.to_string().len();
+        for (i, line) in bytes.chunks(line_width).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            if self.options.ruler {
+                write!(f, "{:>number_width$} ", i * line_width + 1)?;
+            }
+            f.write_str(as_str(line))?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{data::ALPHABET_MAP, types::Strand};
 
     // ===== "Magic" Sequence Constructor Tests (Typical Usage) ====================================
 
@@ -660,32 +1451,143 @@ mod tests {
     }
 
     #[test]
-    fn read_invalid_protein_sequence() {
-        let protein = Seq::protein("MAMAPUTEINSTRINX");
+    fn read_invalid_protein_sequence() {
+        let protein = Seq::protein("MAMAPUTEINSTRINX");
+        assert_eq!(
+            protein,
+            Err(Error::InvalidSeq(vec![(Kind::Protein, Alphabet::Base)]))
+        );
+    }
+
+    #[test]
+    fn read_valid_protein_iupac_sequence() {
+        let protein_iupac = Seq::protein_iupac("MAMXPRTEIBSTRINZ");
+        let protein_iupac = protein_iupac.unwrap();
+        assert_eq!(protein_iupac.kind(), Kind::Protein);
+        assert_eq!(protein_iupac.alphabet(), Alphabet::Iupac);
+    }
+
+    #[test]
+    fn read_invalid_protein_iupac_sequence() {
+        let protein_iupac = Seq::protein_iupac("MAMXPUTEIBSTRINZ");
+        assert_eq!(
+            protein_iupac,
+            Err(Error::InvalidSeq(vec![
+                (Kind::Protein, Alphabet::Base),
+                (Kind::Protein, Alphabet::Iupac)
+            ]))
+        );
+    }
+
+    #[test]
+    fn read_valid_protein_extended_sequence() {
+        let protein_extended = Seq::protein_extended("MAMUPOTEJNSTRINX*");
+        let protein_extended = protein_extended.unwrap();
+        assert_eq!(protein_extended.kind(), Kind::Protein);
+        assert_eq!(protein_extended.alphabet(), Alphabet::Extended);
+        let protein = Seq::protein_extended("MAMAPRTEIN*").unwrap();
+        assert_eq!(protein.alphabet(), Alphabet::Base);
+    }
+
+    #[test]
+    fn read_invalid_protein_extended_sequence() {
+        let protein_extended = Seq::protein_extended("MAMUPOTEJN-");
+        assert_eq!(
+            protein_extended,
+            Err(Error::InvalidSeq(vec![
+                (Kind::Protein, Alphabet::Base),
+                (Kind::Protein, Alphabet::Iupac),
+                (Kind::Protein, Alphabet::Extended)
+            ]))
+        );
+    }
+
+    #[test]
+    fn extended_protein_is_opt_in() -> Result<(), Error> {
+        assert!(Seq::new("MAMUPOTEIN").is_err());
+        assert_eq!(Seq::new("ACGU")?.kind(), Kind::Rna);
+        let seq = Seq::new_with_kind(
+            "ACGU",
+            [Kind::Dna, Kind::Rna, Kind::Protein],
+            Alphabet::Extended,
+        )?;
+        assert_eq!(seq.kind(), Kind::Rna);
+        Ok(())
+    }
+
+    // ===== Owned Buffer Constructor Tests ========================================================
+
+    #[test]
+    fn from_owned_vec() -> Result<(), Error> {
+        let bytes = b"ACGTN".to_vec();
+        let ptr = bytes.as_ptr();
+        let seq = Seq::from_vec(bytes)?;
+        assert_eq!(seq, Seq::dna_n("ACGTN")?);
+        assert_eq!(seq.as_bytes().as_ptr(), ptr);
         assert_eq!(
-            protein,
-            Err(Error::InvalidSeq(vec![(Kind::Protein, Alphabet::Base)]))
+            Seq::from_vec_with_kind(b"MAMAPRTEIN".to_vec(), [Kind::Protein], Alphabet::Base)?,
+            Seq::protein("MAMAPRTEIN")?
         );
+        Ok(())
     }
 
     #[test]
-    fn read_valid_protein_iupac_sequence() {
-        let protein_iupac = Seq::protein_iupac("MAMXPRTEIBSTRINZ");
-        let protein_iupac = protein_iupac.unwrap();
-        assert_eq!(protein_iupac.kind(), Kind::Protein);
-        assert_eq!(protein_iupac.alphabet(), Alphabet::Iupac);
+    fn from_invalid_vec() {
+        assert_eq!(
+            Seq::from_vec_with_kind(b"ACGTN".to_vec(), [Kind::Dna], Alphabet::Base),
+            Err(Error::InvalidSeq(vec![(Kind::Dna, Alphabet::Base)]))
+        );
     }
 
+    // ===== Sequence Builder Tests ================================================================
+
     #[test]
-    fn read_invalid_protein_iupac_sequence() {
-        let protein_iupac = Seq::protein_iupac("MAMXPUTEIBSTRINZ");
+    fn build_incrementally() -> Result<(), Error> {
+        let mut builder = SeqBuilder::new().with_capacity(12);
+        assert!(builder.is_empty());
+        builder.append("ACGT")?;
+        builder.append(b"ACGT")?;
+        assert_eq!(builder.clone().build()?, Seq::dna("ACGTACGT")?);
+        builder.append("NNNN")?;
+        assert_eq!(builder.len(), 12);
+        assert_eq!(builder.build()?, Seq::dna_n("ACGTACGTNNNN")?);
+        Ok(())
+    }
+
+    #[test]
+    fn build_matches_magic_constructor() -> Result<(), Error> {
+        for fragments in [
+            vec!["ACGU", "acgu"],
+            vec!["ACGT", "MAMAPRTEIN"],
+            vec!["ACG", "T", "R"],
+            vec![],
+        ] {
+            let mut builder = SeqBuilder::new();
+            for fragment in &fragments {
+                builder.append(fragment)?;
+            }
+            assert_eq!(builder.build()?, Seq::new(fragments.concat())?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn build_rejects_invalid_fragments() -> Result<(), Error> {
+        let mut builder = SeqBuilder::with_kind([Kind::Dna], Alphabet::N);
+        builder.append("ACGTN")?;
         assert_eq!(
-            protein_iupac,
+            builder.append("ACGR"),
             Err(Error::InvalidSeq(vec![
-                (Kind::Protein, Alphabet::Base),
-                (Kind::Protein, Alphabet::Iupac)
+                (Kind::Dna, Alphabet::Base),
+                (Kind::Dna, Alphabet::N)
             ]))
         );
+        assert_eq!(builder.build()?, Seq::dna_n("ACGTN")?);
+        assert_eq!(
+            SeqBuilder::with_kind([], Alphabet::Iupac).build(),
+            Err(Error::InvalidSeq(vec![]))
+        );
+        Ok(())
     }
 
     // ===== Case Conversion Tests =================================================================
@@ -720,6 +1622,48 @@ mod tests {
         Ok(())
     }
 
+    // ===== Masking Tests =========================================================================
+
+    #[test]
+    fn soft_mask_regions() -> Result<(), Error> {
+        let dna = Seq::dna("ACGTACGTACGT")?;
+        let masked = dna.soft_mask([1..3, 8..20]);
+        assert_eq!(masked.to_string(), "AcgTACGTacgt");
+        assert_eq!(masked.alphabet(), Alphabet::Base);
+        assert_eq!(masked.masked_regions(), [1..3, 8..12]);
+        assert!(dna.masked_regions().is_empty());
+        let inverted = [Range { start: 6, end: 2 }, Range { start: 20, end: 15 }];
+        assert_eq!(dna.soft_mask(inverted.clone()), dna);
+        assert_eq!(dna.hard_mask(inverted), dna);
+        Ok(())
+    }
+
+    #[test]
+    fn hard_mask_regions() -> Result<(), Error> {
+        let dna = Seq::dna("ACGTACGTACGT")?;
+        let masked = dna.hard_mask([2..4, 4..6]);
+        assert_eq!(masked.to_string(), "ACNNNNGTACGT");
+        assert_eq!(masked.alphabet(), Alphabet::N);
+        assert_eq!(masked.masked_regions(), [Range { start: 2, end: 6 }]);
+        assert_eq!(dna.hard_mask([]).alphabet(), Alphabet::Base);
+
+        let protein = Seq::protein("MAMAPRTEINSTRING")?;
+        let masked = protein.hard_mask(Some(0..1));
+        assert_eq!(masked.to_string(), "XAMAPRTEINSTRING");
+        assert_eq!(masked.alphabet(), Alphabet::Iupac);
+        Ok(())
+    }
+
+    #[test]
+    fn mixed_mask_regions() -> Result<(), Error> {
+        let rna = Seq::rna("ACGUACGUACGU")?
+            .soft_mask(Some(0..2))
+            .hard_mask([2..4, 9..10]);
+        assert_eq!(rna.to_string(), "acNNACGUANGU");
+        assert_eq!(rna.masked_regions(), [0..4, 9..10]);
+        Ok(())
+    }
+
     // ===== String Conversion Tests ===============================================================
 
     #[test]
@@ -743,6 +1687,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn display_wrapped() -> Result<(), Error> {
+        let dna = Seq::dna("AGCTTTTCATTCTGACTGCA")?;
+        let options = DisplayOptions::new().wrap(8);
+        assert_eq!(dna.display(options).to_string(), "AGCTTTTC\nATTCTGAC\nTGCA");
+        assert_eq!(
+            dna.display(DisplayOptions::new().wrap(20)).to_string(),
+            dna.to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn display_ruler() -> Result<(), Error> {
+        let dna = Seq::dna("AGCTTTTCATTCTGACTGCA")?;
+        let options = DisplayOptions::new().wrap(8).ruler(true);
+        assert_eq!(
+            dna.display(options).to_string(),
+            " 1 AGCTTTTC\n 9 ATTCTGAC\n17 TGCA"
+        );
+        let options = DisplayOptions::new().ruler(true);
+        assert_eq!(dna.display(options).to_string(), "1 AGCTTTTCATTCTGACTGCA");
+        Ok(())
+    }
+
+    #[test]
+    fn display_preview() -> Result<(), Error> {
+        let dna = Seq::dna("ACGT".repeat(3086) + "T")?;
+        let options = DisplayOptions::new().preview(8);
+        assert_eq!(dna.display(options).to_string(), "ACGT…CGTT (12,345 bp)");
+        assert_eq!(format!("{dna:.8}"), "ACGT…CGTT (12,345 bp)");
+        let protein = Seq::protein("MAMAPRTEINSTRING")?;
+        assert_eq!(format!("{protein:.5}"), "MAM…NG (16 aa)");
+        assert_eq!(format!("{protein:.16}"), "MAMAPRTEINSTRING");
+        let rna = Seq::rna("ACGUACGU")?;
+        assert_eq!(format!("{rna:.2}"), "A…U (8 nt)");
+        Ok(())
+    }
+
     // ===== Sequence Length Tests =================================================================
 
     #[test]
@@ -761,6 +1744,53 @@ mod tests {
         Ok(())
     }
 
+    // ===== Byte View Tests =======================================================================
+
+    #[test]
+    fn bytes_work_with_rust_bio() -> Result<(), Error> {
+        let dna = Seq::dna("AGCTTTTCATTCTGACTGCA")?;
+        assert_eq!(dna.as_bytes(), b"AGCTTTTCATTCTGACTGCA");
+        assert_eq!(bio::seq_analysis::gc::gc_content(&dna), 0.4);
+        assert_eq!(
+            bio::alignment::distance::hamming(dna.as_ref(), &dna.rev().into_bytes()),
+            14
+        );
+        Ok(())
+    }
+
+    // ===== Serialization Tests ===================================================================
+
+    #[test]
+    fn serialize_human_readable() -> Result<(), Error> {
+        let dna = Seq::dna_n("ACGTN")?;
+        let json = serde_json::to_string(&dna).unwrap();
+        assert_eq!(json, r#"{"seq":"ACGTN","kind":"Dna","alphabet":"N"}"#);
+        assert_eq!(serde_json::from_str::<Seq>(&json).unwrap(), dna);
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_compact() -> Result<(), Error> {
+        let protein = Seq::protein("MAMAPRTEINSTRING")?;
+        let mut cbor = Vec::new();
+        ciborium::ser::into_writer(&protein, &mut cbor).unwrap();
+        // NOTE: CBOR prefixes a 16-byte byte string with 0x50, and a string with 0x70
+        assert!(cbor.windows(17).any(|w| w == b"\x50MAMAPRTEINSTRING"));
+        let round_trip: Seq = ciborium::de::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(round_trip, protein);
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_invalid_seq() {
+        let json = r#"{"seq":"ACGTN","kind":"Dna","alphabet":"Base"}"#;
+        let err = serde_json::from_str::<Seq>(json).unwrap_err();
+        assert!(err.to_string().contains("not valid"));
+        assert!(err.to_string().contains("DNA (Base)"));
+        let json = r#"{"seq":"ACGTN","kind":"Protein","alphabet":"N"}"#;
+        assert!(serde_json::from_str::<Seq>(json).is_err());
+    }
+
     // ===== Subsequence Tests =====================================================================
 
     #[test]
@@ -841,6 +1871,28 @@ mod tests {
         Ok(())
     }
 
+    // ===== K-mer Counting Tool Tests =============================================================
+
+    #[test]
+    fn count_dna_kmers() -> Result<(), Error> {
+        let dna = Seq::dna("ACGTacgtAC")?;
+        let counts = dna.count_kmers(4)?;
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[b"ACGT".as_slice()], 2);
+        assert_eq!(counts[b"CGTA".as_slice()], 2);
+        assert_eq!(counts[b"GTAC".as_slice()], 2);
+        assert_eq!(counts[b"TACG".as_slice()], 1);
+        Ok(())
+    }
+
+    #[test]
+    fn count_kmers_longer_than_seq() -> Result<(), Error> {
+        let dna = Seq::dna("ACGT")?;
+        assert!(dna.count_kmers(5)?.is_empty());
+        assert_eq!(dna.count_kmers(0), Err(Error::KmerLength(0)));
+        Ok(())
+    }
+
     // ===== GC Content Tool Tests =================================================================
 
     #[test]
@@ -888,6 +1940,103 @@ mod tests {
         Ok(())
     }
 
+    // ===== Ambiguity + Homopolymer Tool Tests ====================================================
+
+    #[test]
+    fn dna_ambiguous_content() -> Result<(), Error> {
+        let dna = Seq::dna_iupac("ACGTNNRYacgtn")?;
+        let report = dna.ambiguous_content();
+        assert_eq!(report.total(), 5);
+        assert_eq!(report.positions, [4, 5, 6, 7, 12]);
+        assert_eq!(report.counts[b'N'], 3);
+        assert_eq!(report.counts[b'R'], 1);
+        assert_eq!(report.counts[b'Y'], 1);
+        assert_eq!(Seq::dna("ACGT")?.ambiguous_content().total(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn protein_ambiguous_content() -> Result<(), Error> {
+        let protein = Seq::protein_iupac("MAXBZNDEQ")?;
+        let report = protein.ambiguous_content();
+        assert_eq!(report.positions, [2, 3, 4]);
+        assert_eq!(report.counts[b'N'], 0);
+        let protein = Seq::protein_extended("MUJOXJ*")?;
+        let report = protein.ambiguous_content();
+        assert_eq!(report.positions, [2, 4, 5]);
+        assert_eq!(report.counts[b'J'], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn longest_homopolymer() -> Result<(), Error> {
+        let dna = Seq::dna("ACCCGTTTtTAAAA")?;
+        assert_eq!(dna.max_homopolymer_run(), Some((b'T', 5..10)));
+        let dna = Seq::dna("AACCGG")?;
+        assert_eq!(dna.max_homopolymer_run(), Some((b'A', 0..2)));
+        let dna = Seq::dna("G")?;
+        assert_eq!(dna.max_homopolymer_run(), Some((b'G', 0..1)));
+        assert_eq!(Seq::dna("")?.max_homopolymer_run(), None);
+        Ok(())
+    }
+
+    // ===== Case-Insensitive Comparison Tests =====================================================
+
+    #[test]
+    fn compare_ignoring_case() -> Result<(), Error> {
+        let upper = Seq::dna("ACGTACGT")?;
+        let masked = Seq::dna("ACGTacgt")?;
+        assert_ne!(upper, masked);
+        assert!(upper.eq_ignore_case(&masked));
+        assert!(!upper.eq_ignore_case(&Seq::dna("ACGTACGA")?));
+        assert!(!Seq::dna("ACGA")?.eq_ignore_case(&Seq::protein("ACGA")?));
+        assert_eq!(upper.cmp_ignore_case(&masked), Ordering::Equal);
+        assert_eq!(
+            Seq::dna("acga")?.cmp_ignore_case(&Seq::dna("ACGT")?),
+            Ordering::Less
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_hash() -> Result<(), Error> {
+        let upper = Seq::dna("ACGTACGT")?;
+        let masked = Seq::dna("acgtACGT")?;
+        assert_eq!(upper.hash_canonical(), masked.hash_canonical());
+        assert_ne!(
+            upper.hash_canonical(),
+            Seq::dna("ACGTACGA")?.hash_canonical()
+        );
+        assert_ne!(
+            Seq::dna("ACGA")?.hash_canonical(),
+            Seq::protein("ACGA")?.hash_canonical()
+        );
+        // NOTE: This value must never change, since canonical hashes are meant to be stored
+        assert_eq!(Seq::dna("ACGT")?.hash_canonical(), 0xe38d_cc5b_8a5d_c34b);
+        Ok(())
+    }
+
+    #[test]
+    fn ignore_case_collections() -> Result<(), Error> {
+        use std::collections::{BTreeSet, HashSet};
+
+        let seqs = [
+            Seq::dna("ACGT")?,
+            Seq::dna("acgt")?,
+            Seq::dna("AcGt")?,
+            Seq::dna("TTTT")?,
+        ];
+        let unique: HashSet<_> = seqs.iter().map(IgnoreCase).collect();
+        assert_eq!(unique.len(), 2);
+        let sorted: BTreeSet<_> = seqs.into_iter().map(IgnoreCase).collect();
+        let sorted: Vec<_> = sorted
+            .into_iter()
+            .map(|s| s.0.to_string().to_ascii_uppercase())
+            .collect();
+        assert_eq!(sorted, ["ACGT", "TTTT"]);
+        Ok(())
+    }
+
     // ===== Hamming + Levenshtein Distance Tool Tests =============================================
 
     #[test]
@@ -984,6 +2133,52 @@ mod tests {
         Ok(())
     }
 
+    // ===== Subsequence Tool Tests ================================================================
+
+    #[test]
+    fn longest_common_subsequence() -> Result<(), Error> {
+        let a = Seq::dna("AACCTTGG")?;
+        let b = Seq::dna("acactgtga")?;
+        let lcs = a.longest_common_subsequence(&b)?;
+        assert_eq!(lcs.len(), 6);
+        assert_eq!(lcs.kind(), Kind::Dna);
+        assert!(a.find_subsequence(&lcs)?.is_some());
+        assert!(b.find_subsequence(&lcs)?.is_some());
+        assert!(a.longest_common_subsequence(&Seq::dna("")?)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn find_subsequence() -> Result<(), Error> {
+        let seq = Seq::dna("ACGTACGTGACG")?;
+        assert_eq!(
+            seq.find_subsequence(&Seq::dna("GTA")?)?,
+            Some(vec![2, 3, 4])
+        );
+        assert_eq!(
+            seq.find_subsequence(&Seq::dna("aag")?)?,
+            Some(vec![0, 4, 6])
+        );
+        assert_eq!(seq.find_subsequence(&Seq::dna("")?)?, Some(vec![]));
+        assert_eq!(seq.find_subsequence(&Seq::dna("TTTT")?)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn subsequence_type_mismatch() -> Result<(), Error> {
+        let a = Seq::dna("ACGT")?;
+        let b = Seq::rna("ACGU")?;
+        assert_eq!(
+            a.longest_common_subsequence(&b),
+            Err(Error::SubsequenceKindMismatch(Kind::Dna, Kind::Rna))
+        );
+        assert_eq!(
+            a.find_subsequence(&b),
+            Err(Error::SubsequenceKindMismatch(Kind::Dna, Kind::Rna))
+        );
+        Ok(())
+    }
+
     // ===== ORF Finding + Translation Tool Tests ==================================================
 
     #[test]
@@ -994,28 +2189,13 @@ mod tests {
         )?;
         assert_eq!(
             vec![
+                (Region::new(4..10, Strand::Forward)?, Seq::dna("ATGTAG")?),
                 (
-                    Orf {
-                        start: 4,
-                        end: 10,
-                        offset: 1,
-                    },
-                    Seq::dna("ATGTAG")?
-                ),
-                (
-                    Orf {
-                        start: 24,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(24..69, Strand::Forward)?,
                     Seq::dna("ATGGGGATGACCCCGCGACTTGGATTAGAGTCTCTTTTGGAATAA")?
                 ),
                 (
-                    Orf {
-                        start: 30,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(30..69, Strand::Forward)?,
                     Seq::dna("ATGACCCCGCGACTTGGATTAGAGTCTCTTTTGGAATAA")?
                 )
             ],
@@ -1024,19 +2204,11 @@ mod tests {
         assert_eq!(
             vec![
                 (
-                    Orf {
-                        start: 70,
-                        end: 76,
-                        offset: 1,
-                    },
+                    Region::new(70..76, Strand::Forward)?,
                     Seq::dna("ATGTAA")?
                 ),
                 (
-                    Orf {
-                        start: 5,
-                        end: 86,
-                        offset: 2,
-                    },
+                    Region::new(5..86, Strand::Forward)?,
                     Seq::dna("ATGCTACTCGGATCATTCAGGCTTATTCCAAAAGAGACTCTAATCCAAGTCGCGGGGTCATCCCCATGTAACCTGAGTTAG")?
                 ),
             ],
@@ -1053,28 +2225,13 @@ mod tests {
         )?;
         assert_eq!(
             vec![
+                (Region::new(4..10, Strand::Forward)?, Seq::dna("atgtag")?),
                 (
-                    Orf {
-                        start: 4,
-                        end: 10,
-                        offset: 1,
-                    },
-                    Seq::dna("atgtag")?
-                ),
-                (
-                    Orf {
-                        start: 24,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(24..69, Strand::Forward)?,
                     Seq::dna("atggggatgaccccgcgacttggattagagtctcttttggaataa")?
                 ),
                 (
-                    Orf {
-                        start: 30,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(30..69, Strand::Forward)?,
                     Seq::dna("atgaccccgcgacttggattagagtctcttttggaataa")?
                 )
             ],
@@ -1083,19 +2240,11 @@ mod tests {
         assert_eq!(
             vec![
                 (
-                    Orf {
-                        start: 70,
-                        end: 76,
-                        offset: 1,
-                    },
+                    Region::new(70..76, Strand::Forward)?,
                     Seq::dna("atgtaa")?
                 ),
                 (
-                    Orf {
-                        start: 5,
-                        end: 86,
-                        offset: 2,
-                    },
+                    Region::new(5..86, Strand::Forward)?,
                     Seq::dna("atgctactcggatcattcaggcttattccaaaagagactctaatccaagtcgcggggtcatccccatgtaacctgagttag")?
                 ),
             ],
@@ -1112,28 +2261,13 @@ mod tests {
         )?;
         assert_eq!(
             vec![
+                (Region::new(4..10, Strand::Forward)?, Seq::rna("AUGUAG")?),
                 (
-                    Orf {
-                        start: 4,
-                        end: 10,
-                        offset: 1,
-                    },
-                    Seq::rna("AUGUAG")?
-                ),
-                (
-                    Orf {
-                        start: 24,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(24..69, Strand::Forward)?,
                     Seq::rna("AUGGGGAUGACCCCGCGACUUGGAUUAGAGUCUCUUUUGGAAUAA")?
                 ),
                 (
-                    Orf {
-                        start: 30,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(30..69, Strand::Forward)?,
                     Seq::rna("AUGACCCCGCGACUUGGAUUAGAGUCUCUUUUGGAAUAA")?
                 )
             ],
@@ -1142,19 +2276,11 @@ mod tests {
         assert_eq!(
             vec![
                 (
-                    Orf {
-                        start: 70,
-                        end: 76,
-                        offset: 1,
-                    },
+                    Region::new(70..76, Strand::Forward)?,
                     Seq::rna("AUGUAA")?
                 ),
                 (
-                    Orf {
-                        start: 5,
-                        end: 86,
-                        offset: 2,
-                    },
+                    Region::new(5..86, Strand::Forward)?,
                     Seq::rna("AUGCUACUCGGAUCAUUCAGGCUUAUUCCAAAAGAGACUCUAAUCCAAGUCGCGGGGUCAUCCCCAUGUAACCUGAGUUAG")?
                 ),
             ],
@@ -1172,27 +2298,15 @@ mod tests {
         assert_eq!(
             vec![
                 (
-                    Orf {
-                        start: 4,
-                        end: 10,
-                        offset: 1,
-                    },
+                    Region::new(4..10, Strand::Forward)?,
                     Seq::rna_iupac("AUGUAR")?
                 ),
                 (
-                    Orf {
-                        start: 24,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(24..69, Strand::Forward)?,
                     Seq::rna_iupac("AUGGGGAUGACCCCGCGACUUGGAUUAGAGUCUCUUUUGGAAURA")?
                 ),
                 (
-                    Orf {
-                        start: 30,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(30..69, Strand::Forward)?,
                     Seq::rna_iupac("AUGACCCCGCGACUUGGAUUAGAGUCUCUUUUGGAAURA")?
                 )
             ],
@@ -1217,19 +2331,11 @@ mod tests {
         assert_eq!(
             vec![
                 (
-                    Orf {
-                        start: 24,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(24..69, Strand::Forward)?,
                     Seq::dna("ATGGGGATGACCCCGCGACTTGGATTAGAGTCTCTTTTGGAATAA")?
                 ),
                 (
-                    Orf {
-                        start: 30,
-                        end: 69,
-                        offset: 0,
-                    },
+                    Region::new(30..69, Strand::Forward)?,
                     Seq::dna("ATGACCCCGCGACTTGGATTAGAGTCTCTTTTGGAATAA")?
                 )
             ],
@@ -1238,11 +2344,7 @@ mod tests {
         assert_eq!(
             vec![
                 (
-                    Orf {
-                        start: 5,
-                        end: 86,
-                        offset: 2,
-                    },
+                    Region::new(5..86, Strand::Forward)?,
                     Seq::dna("ATGCTACTCGGATCATTCAGGCTTATTCCAAAAGAGACTCTAATCCAAGTCGCGGGGTCATCCCCATGTAACCTGAGTTAG")?
                 ),
             ],
@@ -1663,5 +2765,201 @@ mod tests {
             &Error::DistanceKindMismatch(Kind::Protein, Kind::Rna).to_string(),
             "Cannot compute distance between Protein and RNA"
         );
+        assert_eq!(
+            &Error::KmerLength(0).to_string(),
+            "Cannot use k-mers of length 0"
+        );
+        assert_eq!(
+            &Error::ErrorCorrection(Kind::Protein).to_string(),
+            "Cannot correct errors in Protein"
+        );
+        assert_eq!(
+            &Error::QualityLength(4, 2).to_string(),
+            "The number of qualities (2) did not match the sequence length (4)"
+        );
+        assert_eq!(
+            &Error::MissingQuality.to_string(),
+            "The record did not contain any qualities"
+        );
+        assert_eq!(
+            &Error::WindowSize(0).to_string(),
+            "Cannot use a window of size 0"
+        );
+        assert_eq!(
+            &Error::EmptySeqSet.to_string(),
+            "Cannot summarise an empty set of sequences"
+        );
+        assert_eq!(
+            &Error::InvalidQuality(94).to_string(),
+            "Invalid quality score: 94"
+        );
+        assert_eq!(
+            &Error::PatchLength(8, 3).to_string(),
+            "Cannot apply a patch for a sequence of length 8 to one of length 3"
+        );
+        assert_eq!(
+            &Error::MergeKindMismatch(Kind::Dna, Kind::Rna).to_string(),
+            "Cannot merge a DNA read with a RNA read"
+        );
+        assert_eq!(
+            &Error::CodonCount(Kind::Protein).to_string(),
+            "Cannot count codons in Protein"
+        );
+        assert_eq!(
+            &Error::TerminatorSearch(Kind::Protein).to_string(),
+            "Cannot search for terminators in Protein"
+        );
+        assert_eq!(
+            &Error::PwmKindMismatch(Kind::Dna, Kind::Protein).to_string(),
+            "Cannot use a DNA matrix with Protein"
+        );
+        assert_eq!(
+            &Error::SiteLength(6, 4).to_string(),
+            "All sites must have the same length (expected 6, found 4)"
+        );
+        assert_eq!(
+            &Error::ProfileWidth(4, 3).to_string(),
+            "Profile rows must have one count per symbol (expected 4, found 3)"
+        );
+        assert_eq!(
+            &Error::PromoterSearch(Kind::Rna).to_string(),
+            "Cannot search for promoters in RNA"
+        );
+        assert_eq!(
+            &Error::RbsSearch(Kind::Protein).to_string(),
+            "Cannot search for ribosome binding sites in Protein"
+        );
+        assert_eq!(
+            &Error::GuideDesign(Kind::Rna).to_string(),
+            "Cannot design CRISPR guides for RNA"
+        );
+        assert_eq!(
+            &Error::Hydropathy(Kind::Dna).to_string(),
+            "Cannot compute the hydropathy of DNA"
+        );
+        assert_eq!(
+            &Error::Charge(Kind::Rna).to_string(),
+            "Cannot compute the charge of RNA"
+        );
+        assert_eq!(
+            &Error::StructurePrediction(Kind::Dna).to_string(),
+            "Cannot predict the secondary structure of DNA"
+        );
+        assert_eq!(
+            &Error::SubsequenceKindMismatch(Kind::Dna, Kind::Protein).to_string(),
+            "Cannot compare subsequences of DNA and Protein"
+        );
+        assert_eq!(
+            &Error::BackgroundKindMismatch(Kind::Dna, Kind::Rna).to_string(),
+            "Cannot use a DNA background with RNA"
+        );
+        assert_eq!(
+            &Error::KmerComposition(Kind::Protein).to_string(),
+            "Cannot compute the k-mer composition of Protein"
+        );
+        assert_eq!(
+            &Error::InvalidRegion(6, 2).to_string(),
+            "Cannot create a region from 6 to 2"
+        );
+        assert_eq!(
+            &Error::RegionBounds(
+                crate::region::Region::new(8..12, crate::types::Strand::Reverse).unwrap(),
+                10
+            )
+            .to_string(),
+            "The region complement(9..12) is out of bounds for a sequence of length 10"
+        );
+        assert_eq!(
+            &Error::InvalidAlphabet("Empty".to_string()).to_string(),
+            "The Empty alphabet must contain printable ASCII symbols"
+        );
+        assert_eq!(
+            &Error::DuplicateAlphabet("Hachimoji".to_string()).to_string(),
+            "An alphabet named Hachimoji has already been registered"
+        );
+        assert_eq!(
+            &Error::Translation(Kind::Protein).to_string(),
+            "Cannot translate Protein"
+        );
+        assert_eq!(
+            &Error::AmbiguousCodon(3).to_string(),
+            "Found an ambiguous codon at position 3"
+        );
+        assert_eq!(
+            &Error::PartialCodon(2).to_string(),
+            "Found a trailing partial codon of length 2"
+        );
+        assert_eq!(
+            &Error::EmptyFeature("CDS".to_string()).to_string(),
+            "The CDS feature must have a location"
+        );
+        assert_eq!(
+            &Error::GenBank(3, "expected a LOCUS line".to_string()).to_string(),
+            "Invalid GenBank file (line 3): expected a LOCUS line"
+        );
+        assert_eq!(
+            &Error::SnapGene("missing a DNA sequence".to_string()).to_string(),
+            "Invalid SnapGene file: missing a DNA sequence"
+        );
+        assert_eq!(
+            &Error::EnzymeSearch(Kind::Protein).to_string(),
+            "Cannot search for restriction sites in Protein"
+        );
+        assert_eq!(
+            &Error::Domestication("BsaI".to_string(), 42).to_string(),
+            "The BsaI site at position 42 cannot be removed by a silent mutation"
+        );
+        assert_eq!(
+            &Error::IllegalSite("EcoRI".to_string(), 7).to_string(),
+            "Found an illegal EcoRI site at position 7"
+        );
+        assert_eq!(
+            &Error::ReadSimulation(Kind::Protein).to_string(),
+            "Cannot simulate sequencing reads from Protein"
+        );
+        assert_eq!(
+            &Error::MutationSimulation(Kind::Protein).to_string(),
+            "Cannot simulate the mutation of Protein"
+        );
+        assert_eq!(
+            &Error::ZeroMutationRate.to_string(),
+            "Cannot reach a target divergence without any mutations"
+        );
+        assert_eq!(
+            &Error::Abif("missing the called bases".to_string()).to_string(),
+            "Invalid AB1 file: missing the called bases"
+        );
+        assert_eq!(
+            &Error::InvalidProbability("deletion rate".to_string()).to_string(),
+            "The deletion rate must be a probability between 0 and 1"
+        );
+        assert_eq!(
+            &Error::InvalidDivergence.to_string(),
+            "The target divergence must be finite"
+        );
+        assert_eq!(
+            &Error::RbsSpacing(5, 5).to_string(),
+            "Cannot use a spacing range from 5 to 5"
+        );
+        assert_eq!(
+            &Error::ZeroBackground(b'G').to_string(),
+            "The background probability of G must be above zero"
+        );
+        assert_eq!(
+            &Error::EmptyProfileRow(3).to_string(),
+            "Position 3 of the profile has no counts"
+        );
+        assert_eq!(
+            &Error::InvalidEdit(4).to_string(),
+            "The edit at position 4 overlaps an earlier edit or runs past the sequence"
+        );
+        assert_eq!(
+            &Error::PatchedLength(5, 4).to_string(),
+            "The patch should produce a sequence of length 5, but produced one of length 4"
+        );
+        assert_eq!(
+            &Error::InvalidCoverage.to_string(),
+            "The coverage must be finite and non-negative"
+        );
     }
 }
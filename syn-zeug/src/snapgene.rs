@@ -0,0 +1,401 @@
+use std::collections::HashMap;
+
+use crate::{
+    annotation::{AnnotatedRecord, Feature},
+    record::SeqRecord,
+    region::Region,
+    seq::{Alphabet, Error, Kind, Seq},
+    types::{Strand, Topology},
+};
+
+// ===== SnapGene Packets ==========================================================================
+
+// NOTE: SnapGene files are a series of packets, each a 1-byte type and a 4-byte, big-endian length
+// followed by that many bytes of data. Only the packets needed for an annotated record are read —
+// everything else (primers, history, display settings, etc.) is skipped
+const COOKIE_PACKET: u8 = 0x09;
+const DNA_PACKET: u8 = 0x00;
+const FEATURES_PACKET: u8 = 0x0a;
+
+fn packets(bytes: &[u8]) -> Result<Vec<(u8, &[u8])>, Error> {
+    let mut packets = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let header = bytes
+            .get(pos..pos + 5)
+            .ok_or_else(|| Error::SnapGene(format!("truncated packet header at byte {pos}")))?;
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        let data = bytes
+            .get(pos + 5..pos + 5 + len)
+            .ok_or_else(|| Error::SnapGene(format!("truncated packet at byte {pos}")))?;
+        packets.push((header[0], data));
+        pos += 5 + len;
+    }
+    Ok(packets)
+}
+
+impl AnnotatedRecord {
+    // NOTE: SnapGene files don't store a name (SnapGene uses the file name), so an ID must be given
+    pub fn from_snapgene(id: impl Into<String>, bytes: &[u8]) -> Result<Self, Error> {
+        let packets = packets(bytes)?;
+        match packets.first() {
+            Some(&(COOKIE_PACKET, cookie)) if cookie.starts_with(b"SnapGene") => (),
+            _ => {
+                return Err(Error::SnapGene(
+                    "missing the SnapGene file header".to_string(),
+                ))
+            }
+        }
+
+        let (flags, seq) = packets
+            .iter()
+            .find(|(t, _)| *t == DNA_PACKET)
+            .and_then(|(_, data)| data.split_first())
+            .ok_or_else(|| Error::SnapGene("missing a DNA sequence".to_string()))?;
+        let seq = Seq::new_with_kind(seq, [Kind::Dna], Alphabet::Iupac)?;
+        let topology = if flags & 0x01 == 1 {
+            Topology::Circular
+        } else {
+            Topology::Linear
+        };
+
+        let mut record = AnnotatedRecord::new(SeqRecord::new(id, seq), topology);
+        if let Some((_, xml)) = packets.iter().find(|(t, _)| *t == FEATURES_PACKET) {
+            for feature in parse_features(&String::from_utf8_lossy(xml), record.len())? {
+                record = record.with_feature(feature)?;
+            }
+        }
+        Ok(record)
+    }
+}
+
+// ===== Feature XML ===============================================================================
+
+// NOTE: Features are stored as XML. Only tags and their attributes carry anything needed here, so
+// this is a minimal tag scanner rather than a full XML parser
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Tag {
+    name: String,
+    attributes: HashMap<String, String>,
+    closing: bool,
+}
+
+fn tags(xml: &str) -> Result<Vec<Tag>, Error> {
+    let mut tags = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if rest.starts_with('?') || rest.starts_with('!') {
+            let end = rest
+                .find('>')
+                .ok_or_else(|| Error::SnapGene("unterminated XML tag".to_string()))?;
+            rest = &rest[end + 1..];
+            continue;
+        }
+
+        let mut in_quote = None;
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| match in_quote {
+                Some(q) if c == q => {
+                    in_quote = None;
+                    false
+                }
+                Some(_) => false,
+                None if c == '"' || c == '\'' => {
+                    in_quote = Some(c);
+                    false
+                }
+                None => c == '>',
+            })
+            .map(|(i, _)| i)
+            .ok_or_else(|| Error::SnapGene("unterminated XML tag".to_string()))?;
+        let body = rest[..end].trim_end_matches('/');
+        rest = &rest[end + 1..];
+
+        let closing = body.starts_with('/');
+        let body = body.trim_start_matches('/');
+        let (name, mut attrs) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+        let mut attributes = HashMap::new();
+        while let Some((key, value)) = attrs.split_once('=') {
+            let value = value.trim_start();
+            let quote = value
+                .chars()
+                .next()
+                .filter(|&c| c == '"' || c == '\'')
+                .ok_or_else(|| Error::SnapGene("unquoted XML attribute".to_string()))?;
+            let value = &value[1..];
+            let close = value
+                .find(quote)
+                .ok_or_else(|| Error::SnapGene("unterminated XML attribute".to_string()))?;
+            attributes.insert(key.trim().to_string(), unescape(&value[..close]));
+            attrs = &value[close + 1..];
+        }
+        tags.push(Tag {
+            name: name.to_string(),
+            attributes,
+            closing,
+        });
+    }
+    Ok(tags)
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match c {
+            Some(c) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+struct OpenFeature {
+    kind: String,
+    strand: Strand,
+    qualifiers: Vec<(String, String)>,
+    parts: Vec<Region>,
+}
+
+// NOTE: Segment ranges are 1-based and inclusive, and a segment whose start is after its end wraps
+// around the origin. A directionality of 2 puts a feature on the reverse strand — anything else
+// (forward, bidirectional, or none) is read as the forward strand
+fn parse_features(xml: &str, len: usize) -> Result<Vec<Feature>, Error> {
+    let mut features = Vec::new();
+    let mut current: Option<OpenFeature> = None;
+    let mut qualifier: Option<String> = None;
+
+    for tag in tags(xml)? {
+        match (tag.name.as_str(), tag.closing) {
+            ("Feature", false) => {
+                let kind = tag
+                    .attributes
+                    .get("type")
+                    .cloned()
+                    .unwrap_or_else(|| "misc_feature".to_string());
+                let strand = match tag.attributes.get("directionality").map(String::as_str) {
+                    Some("2") => Strand::Reverse,
+                    _ => Strand::Forward,
+                };
+                let qualifiers = tag
+                    .attributes
+                    .get("name")
+                    .map(|name| ("label".to_string(), name.clone()))
+                    .into_iter()
+                    .collect();
+                current = Some(OpenFeature {
+                    kind,
+                    strand,
+                    qualifiers,
+                    parts: Vec::new(),
+                });
+            }
+            ("Feature", true) => {
+                let Some(OpenFeature {
+                    kind,
+                    strand,
+                    qualifiers,
+                    mut parts,
+                }) = current.take()
+                else {
+                    continue;
+                };
+                if strand == Strand::Reverse {
+                    parts.reverse();
+                }
+                let feature = qualifiers
+                    .into_iter()
+                    .fold(Feature::from_parts(kind, parts)?, |f, (k, v)| {
+                        f.with_qualifier(k, v)
+                    });
+                features.push(feature);
+            }
+            ("Segment", false) => {
+                let Some(OpenFeature { strand, parts, .. }) = current.as_mut() else {
+                    continue;
+                };
+                let range = tag
+                    .attributes
+                    .get("range")
+                    .ok_or_else(|| Error::SnapGene("segment without a range".to_string()))?;
+                let (start, end) = range
+                    .split_once('-')
+                    .and_then(|(s, e)| Some((s.trim().parse().ok()?, e.trim().parse().ok()?)))
+                    .ok_or_else(|| Error::SnapGene(format!("malformed segment range {range}")))?;
+                let segment = if start > end {
+                    vec![
+                        Region::from_one_based(start, len, *strand)?,
+                        Region::from_one_based(1, end, *strand)?,
+                    ]
+                } else {
+                    vec![Region::from_one_based(start, end, *strand)?]
+                };
+                parts.extend(segment);
+            }
+            ("Q", false) => qualifier = tag.attributes.get("name").cloned(),
+            ("Q", true) => qualifier = None,
+            ("V", false) => {
+                let (Some(OpenFeature { qualifiers, .. }), Some(key)) =
+                    (current.as_mut(), &qualifier)
+                else {
+                    continue;
+                };
+                let value = ["text", "int", "predef"]
+                    .into_iter()
+                    .find_map(|a| tag.attributes.get(a))
+                    .cloned()
+                    .unwrap_or_default();
+                qualifiers.push((key.clone(), value));
+            }
+            _ => (),
+        }
+    }
+    Ok(features)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(kind: u8, data: &[u8]) -> Vec<u8> {
+        [&[kind][..], &(data.len() as u32).to_be_bytes(), data].concat()
+    }
+
+    fn snapgene(flags: u8, seq: &str, features: Option<&str>) -> Vec<u8> {
+        let mut bytes = packet(COOKIE_PACKET, b"SnapGene\x00\x01\x00\x0f\x00\x13");
+        bytes.extend(packet(DNA_PACKET, &[&[flags], seq.as_bytes()].concat()));
+        bytes.extend(packet(0x08, b"<AdditionalSequenceProperties/>"));
+        if let Some(xml) = features {
+            bytes.extend(packet(FEATURES_PACKET, xml.as_bytes()));
+        }
+        bytes
+    }
+
+    const FEATURES: &str = r##"<?xml version="1.0"?><Features nextValidID="3">
+<Feature recentID="0" name="lac &amp; friends" directionality="1" type="CDS" swappedSegmentNumbering="1">
+  <Segment range="3-8" color="#993366" type="standard" translated="1"/>
+  <Q name="gene"><V text="lacZ"/></Q>
+  <Q name="codon_start"><V int="1"/></Q>
+  <Q name="note"><V text="first"/><V text="&lt;b&gt;second&lt;/b&gt;"/></Q>
+</Feature>
+<Feature recentID="1" name="ori" directionality="2" type="rep_origin">
+  <Segment range="9-2" color="#ffff00" type="standard"/>
+</Feature>
+<Feature recentID="2" name="site" type="misc_feature">
+  <Segment range="5-5" type="standard"/>
+</Feature>
+</Features>"##;
+
+    #[test]
+    fn read_snapgene() -> Result<(), Error> {
+        let bytes = snapgene(0x03, "AACCGGTTAC", Some(FEATURES));
+        let record = AnnotatedRecord::from_snapgene("pTest", &bytes)?;
+        assert_eq!(record.id(), "pTest");
+        assert_eq!(record.seq(), &Seq::dna("AACCGGTTAC")?);
+        assert_eq!(record.topology(), Topology::Circular);
+
+        let features = record.features();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[0].kind(), "CDS");
+        assert_eq!(features[0].label(), Some("lac & friends"));
+        assert_eq!(features[0].parts(), [Region::new(2..8, Strand::Forward)?]);
+        assert_eq!(features[0].qualifier("codon_start"), Some("1"));
+        let notes: Vec<_> = features[0]
+            .qualifiers()
+            .iter()
+            .filter(|(k, _)| k == "note")
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(notes, ["first", "<b>second</b>"]);
+
+        assert_eq!(features[1].strand(), Strand::Reverse);
+        assert_eq!(
+            features[1].parts(),
+            [
+                Region::new(0..2, Strand::Reverse)?,
+                Region::new(8..10, Strand::Reverse)?
+            ]
+        );
+        assert_eq!(features[1].extract(record.seq())?.to_string(), "TTGT");
+        assert_eq!(features[2].parts(), [Region::new(4..5, Strand::Forward)?]);
+        Ok(())
+    }
+
+    #[test]
+    fn read_snapgene_without_features() -> Result<(), Error> {
+        let record = AnnotatedRecord::from_snapgene("linear", &snapgene(0x02, "acgt", None))?;
+        assert_eq!(record.topology(), Topology::Linear);
+        assert!(record.features().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn snapgene_to_genbank() -> Result<(), Error> {
+        let bytes = snapgene(0x01, "AACCGGTTAC", Some(FEATURES));
+        let record = AnnotatedRecord::from_snapgene("pTest", &bytes)?;
+        let genbank = record.to_genbank();
+        assert!(genbank.contains("     rep_origin      complement(join(9..10,1..2))\n"));
+        assert_eq!(
+            AnnotatedRecord::from_genbank(&genbank)?[0].features(),
+            record.features()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_invalid_snapgene() {
+        let error = |message: &str| Err(Error::SnapGene(message.to_string()));
+        let dna = packet(DNA_PACKET, b"\x00ACGT");
+        assert_eq!(
+            AnnotatedRecord::from_snapgene("bad", &dna),
+            error("missing the SnapGene file header")
+        );
+        let cookie = packet(COOKIE_PACKET, b"SnapGene\x00\x01\x00\x0f\x00\x13");
+        assert_eq!(
+            AnnotatedRecord::from_snapgene("bad", &cookie),
+            error("missing a DNA sequence")
+        );
+        let truncated = [&cookie[..], &dna[..dna.len() - 1]].concat();
+        assert_eq!(
+            AnnotatedRecord::from_snapgene("bad", &truncated),
+            error("truncated packet at byte 19")
+        );
+        let bytes = snapgene(
+            0x00,
+            "ACGT",
+            Some(r#"<Feature type="CDS"><Segment range="2"/>"#),
+        );
+        assert_eq!(
+            AnnotatedRecord::from_snapgene("bad", &bytes),
+            error("malformed segment range 2")
+        );
+    }
+}
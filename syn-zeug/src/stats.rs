@@ -0,0 +1,425 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::{IUPAC_DNA, IUPAC_RNA},
+    pwm::Background,
+    record::SeqRecord,
+    seq::{Error, Kind, Seq},
+    types::ByteMap,
+};
+
+// ===== Sequence Set Statistics ===================================================================
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SeqSetStats {
+    pub count: usize,
+    pub total_len: usize,
+    pub min_len: usize,
+    pub max_len: usize,
+    pub mean_len: f64,
+    pub n50: usize,
+    pub l50: usize,
+    pub gc_content: Option<f64>,
+}
+
+// NOTE: GC content is pooled over every element of the set (so longer sequences count for more)
+// and is only reported if the set doesn't contain any proteins
+pub fn seq_set_stats<T: AsRef<Seq>>(
+    seqs: impl IntoIterator<Item = T>,
+) -> Result<SeqSetStats, Error> {
+    let mut lens = Vec::new();
+    let mut gc = Some(0.0);
+    for seq in seqs {
+        let seq = seq.as_ref();
+        lens.push(seq.len());
+        gc = match (gc, seq.kind()) {
+            (Some(gc), Kind::Dna | Kind::Rna) if !seq.is_empty() => {
+                Some(gc + seq.gc_content()? * seq.len() as f64)
+            }
+            (gc, Kind::Dna | Kind::Rna) => gc,
+            _ => None,
+        };
+    }
+    if lens.is_empty() {
+        return Err(Error::EmptySeqSet);
+    }
+
+    lens.sort_unstable_by(|a, b| b.cmp(a));
+    let total_len: usize = lens.iter().sum();
+    let mut running_len = 0;
+    let l50 = lens
+        .iter()
+        .position(|&l| {
+            running_len += l;
+            running_len * 2 >= total_len
+        })
+        .expect("The running length should always reach the total length")
+        + 1;
+
+    Ok(SeqSetStats {
+        count: lens.len(),
+        total_len,
+        min_len: lens[lens.len() - 1],
+        max_len: lens[0],
+        mean_len: total_len as f64 / lens.len() as f64,
+        n50: lens[l50 - 1],
+        l50,
+        gc_content: gc.filter(|_| total_len > 0).map(|gc| gc / total_len as f64),
+    })
+}
+
+// ===== Per-Position Composition ==================================================================
+
+// NOTE: Reads of different lengths are allowed, so frequencies and mean qualities at each position
+// are calculated from only the reads that reached it. Setting a `max_len` truncates longer reads
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct PositionComposition {
+    max_len: Option<usize>,
+    reads: usize,
+    counts: Vec<ByteMap<usize>>,
+    depths: Vec<usize>,
+    qual_sums: Vec<u64>,
+    qual_depths: Vec<usize>,
+}
+
+impl PositionComposition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_len(max_len: usize) -> Self {
+        Self {
+            max_len: Some(max_len),
+            ..Self::default()
+        }
+    }
+
+    pub fn add_seq(&mut self, seq: &Seq) {
+        let bytes = seq.as_bytes();
+        let len = self.max_len.map_or(bytes.len(), |m| m.min(bytes.len()));
+        if self.counts.len() < len {
+            self.counts.resize(len, ByteMap::default());
+            self.depths.resize(len, 0);
+        }
+
+        for (i, b) in bytes[..len].iter().enumerate() {
+            self.counts[i][b.to_ascii_uppercase()] += 1;
+            self.depths[i] += 1;
+        }
+        self.reads += 1;
+    }
+
+    pub fn add(&mut self, record: &SeqRecord) {
+        self.add_seq(record.seq());
+        if let Some(qual) = record.qual() {
+            let len = self.max_len.map_or(qual.len(), |m| m.min(qual.len()));
+            if self.qual_sums.len() < len {
+                self.qual_sums.resize(len, 0);
+                self.qual_depths.resize(len, 0);
+            }
+            for (i, &q) in qual[..len].iter().enumerate() {
+                self.qual_sums[i] += q as u64;
+                self.qual_depths[i] += 1;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn reads(&self) -> usize {
+        self.reads
+    }
+
+    pub fn counts(&self, position: usize) -> &ByteMap<usize> {
+        &self.counts[position]
+    }
+
+    // NOTE: Sequences only ever contain ASCII, so non-ASCII elements are never seen at any position
+    pub fn frequencies(&self, element: u8) -> Vec<f64> {
+        if !element.is_ascii() {
+            return vec![0.0; self.len()];
+        }
+        let element = element.to_ascii_uppercase();
+        self.counts
+            .iter()
+            .zip(&self.depths)
+            .map(|(c, &d)| c[element] as f64 / d as f64)
+            .collect()
+    }
+
+    pub fn mean_qualities(&self) -> Vec<Option<f64>> {
+        (0..self.len())
+            .map(|i| {
+                let depth = *self.qual_depths.get(i)?;
+                (depth > 0).then(|| self.qual_sums[i] as f64 / depth as f64)
+            })
+            .collect()
+    }
+}
+
+// ===== Motif Statistics ==========================================================================
+
+// NOTE: Occurrences are modelled as independent at every position, ignoring the self-overlap of
+// motifs like AAAA, which is a good approximation so long as the motif is rare
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MotifOccurrence {
+    pub probability: f64,
+    pub positions: usize,
+}
+
+impl MotifOccurrence {
+    // NOTE: This only counts positions on one strand, so `positions` should be doubled when both
+    // strands of a nucleic acid are searched
+    pub fn new(motif: &Seq, background: &Background, seq_len: usize) -> Result<Self, Error> {
+        Ok(Self {
+            probability: motif_probability(motif, background)?,
+            positions: (seq_len + 1).saturating_sub(motif.len()),
+        })
+    }
+
+    // NOTE: This is the E-value of finding the motif anywhere in the sequence
+    pub fn expected(&self) -> f64 {
+        self.probability * self.positions as f64
+    }
+
+    pub fn p_at_least_one(&self) -> f64 {
+        if self.positions == 0 {
+            return 0.0;
+        }
+        -(self.positions as f64 * (-self.probability).ln_1p()).exp_m1()
+    }
+
+    // NOTE: The chance of seeing at least `observed` occurrences, using the Poisson approximation
+    // to the number of occurrences. The terms are scaled by the largest one in whichever tail is
+    // summed (the upper tail past the mean, otherwise the lower one), since `exp(-lambda)` alone
+    // underflows for large E-values
+    pub fn p_value(&self, observed: usize) -> f64 {
+        let lambda = self.expected();
+        if observed == 0 {
+            return 1.0;
+        } else if lambda <= 0.0 {
+            return 0.0;
+        }
+        let ln_term = |k: usize| (1..=k).fold(-lambda, |t, i| t + lambda.ln() - (i as f64).ln());
+
+        let mut sum = 0.0;
+        let mut ratio = 1.0;
+        if observed as f64 > lambda {
+            let mut i = observed;
+            while ratio > sum * f64::EPSILON {
+                sum += ratio;
+                i += 1;
+                ratio *= lambda / i as f64;
+            }
+            (ln_term(observed) + sum.ln()).exp().min(1.0)
+        } else {
+            for i in (0..observed).rev() {
+                sum += ratio;
+                ratio *= i as f64 / lambda;
+                if ratio <= sum * f64::EPSILON {
+                    break;
+                }
+            }
+            (1.0 - (ln_term(observed - 1) + sum.ln()).exp()).max(0.0)
+        }
+    }
+}
+
+// NOTE: The chance of the motif occurring at any single position. Ambiguity codes in nucleic acid
+// motifs match any of the bases they stand for (so N always matches), but other symbols missing
+// from the background never match
+pub fn motif_probability(motif: &Seq, background: &Background) -> Result<f64, Error> {
+    if motif.kind() != background.kind() {
+        return Err(Error::BackgroundKindMismatch(
+            background.kind(),
+            motif.kind(),
+        ));
+    }
+
+    let codes = match motif.kind() {
+        Kind::Dna => Some(&IUPAC_DNA),
+        Kind::Rna => Some(&IUPAC_RNA),
+        Kind::Protein | Kind::Custom(_) => None,
+    };
+    Ok(motif
+        .as_bytes()
+        .iter()
+        .map(|b| {
+            let b = b.to_ascii_uppercase();
+            match codes.and_then(|codes| codes.get(&b)) {
+                Some(bases) => bases.iter().map(|&b| background.prob(b)).sum(),
+                None => background.prob(b),
+            }
+        })
+        .product())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ===== Sequence Set Statistics Tests =========================================================
+
+    #[test]
+    fn contig_stats() -> Result<(), Error> {
+        let contigs = [
+            Seq::dna("GGGGGGGGGG")?,
+            Seq::dna("AAAAAAAA")?,
+            Seq::dna("CCCCCC")?,
+            Seq::dna("TTTT")?,
+            Seq::dna("AA")?,
+        ];
+        let stats = seq_set_stats(&contigs)?;
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.total_len, 30);
+        assert_eq!(stats.min_len, 2);
+        assert_eq!(stats.max_len, 10);
+        assert_eq!(stats.mean_len, 6.0);
+        assert_eq!(stats.n50, 8);
+        assert_eq!(stats.l50, 2);
+        assert_eq!(stats.gc_content, Some(16.0 / 30.0));
+        Ok(())
+    }
+
+    #[test]
+    fn single_seq_stats() -> Result<(), Error> {
+        let stats = seq_set_stats([Seq::rna("ACGU")?])?;
+        assert_eq!(stats.n50, 4);
+        assert_eq!(stats.l50, 1);
+        assert_eq!(stats.gc_content, Some(0.5));
+        Ok(())
+    }
+
+    #[test]
+    fn protein_set_stats() -> Result<(), Error> {
+        let stats = seq_set_stats([Seq::dna("ACGT")?, Seq::protein("MAMAPRTEIN")?])?;
+        assert_eq!(stats.total_len, 14);
+        assert_eq!(stats.gc_content, None);
+        Ok(())
+    }
+
+    #[test]
+    fn empty_set_stats() {
+        assert_eq!(seq_set_stats(Vec::<Seq>::new()), Err(Error::EmptySeqSet));
+    }
+
+    // ===== Per-Position Composition Tests ========================================================
+
+    #[test]
+    fn equal_length_composition() -> Result<(), Error> {
+        let mut composition = PositionComposition::new();
+        for (seq, qual) in [("ACGT", [40, 30, 20, 10]), ("AGGa", [20, 30, 40, 10])] {
+            composition.add(&SeqRecord::with_qual(
+                "read",
+                Seq::dna(seq)?,
+                qual.to_vec(),
+            )?);
+        }
+        assert_eq!(composition.reads(), 2);
+        assert_eq!(composition.len(), 4);
+        assert_eq!(composition.counts(1)[b'G'], 1);
+        assert_eq!(composition.frequencies(b'A'), [1.0, 0.0, 0.0, 0.5]);
+        assert_eq!(composition.frequencies(b'g'), [0.0, 0.5, 1.0, 0.0]);
+        assert_eq!(composition.frequencies(200), [0.0; 4]);
+        assert_eq!(
+            composition.mean_qualities(),
+            [Some(30.0), Some(30.0), Some(30.0), Some(10.0)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ragged_composition() -> Result<(), Error> {
+        let mut composition = PositionComposition::new();
+        composition.add_seq(&Seq::dna("ACGT")?);
+        composition.add_seq(&Seq::dna("AC")?);
+        composition.add(&SeqRecord::with_qual("read", Seq::dna("T")?, vec![20])?);
+        assert_eq!(composition.frequencies(b'A'), [2.0 / 3.0, 0.0, 0.0, 0.0]);
+        assert_eq!(composition.frequencies(b'C'), [0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(composition.mean_qualities(), [Some(20.0), None, None, None]);
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_composition() -> Result<(), Error> {
+        let mut composition = PositionComposition::with_max_len(2);
+        composition.add(&SeqRecord::with_qual(
+            "read",
+            Seq::dna("ACGT")?,
+            vec![1, 2, 3, 4],
+        )?);
+        assert_eq!(composition.len(), 2);
+        assert_eq!(composition.mean_qualities(), [Some(1.0), Some(2.0)]);
+        Ok(())
+    }
+
+    // ===== Motif Statistics Tests ================================================================
+
+    #[test]
+    fn motif_probabilities() -> Result<(), Error> {
+        let uniform = Background::uniform(Kind::Dna);
+        assert_eq!(
+            motif_probability(&Seq::dna("ACGT")?, &uniform)?,
+            1.0 / 256.0
+        );
+        assert_eq!(
+            motif_probability(&Seq::dna_iupac("ANGW")?, &uniform)?,
+            1.0 / 32.0
+        );
+
+        // NOTE: One of the Rosalind PROB sample cases (ACGATACAA at 12.9% GC)
+        let gc = Background::gc(Kind::Dna, 0.129)?;
+        let p = motif_probability(&Seq::dna("ACGATACAA")?, &gc)?;
+        assert!((p.log10() + 5.737).abs() < 1e-3);
+
+        assert_eq!(
+            motif_probability(&Seq::rna("ACGU")?, &uniform),
+            Err(Error::BackgroundKindMismatch(Kind::Dna, Kind::Rna))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn motif_occurrences() -> Result<(), Error> {
+        let uniform = Background::uniform(Kind::Dna);
+        let occurrence = MotifOccurrence::new(&Seq::dna("GAATTC")?, &uniform, 4099)?;
+        assert_eq!(occurrence.positions, 4094);
+        assert!((occurrence.expected() - 4094.0 / 4096.0).abs() < 1e-12);
+        assert!((occurrence.p_at_least_one() - 0.632).abs() < 1e-3);
+        assert_eq!(occurrence.p_value(0), 1.0);
+        assert!((occurrence.p_value(1) - occurrence.p_at_least_one()).abs() < 1e-3);
+        assert!(occurrence.p_value(3) < occurrence.p_value(2));
+
+        let short = MotifOccurrence::new(&Seq::dna("GAATTC")?, &uniform, 4)?;
+        assert_eq!(short.positions, 0);
+        assert_eq!(short.p_at_least_one(), 0.0);
+
+        // NOTE: Neither of these should overflow or underflow for genome-sized searches
+        let genome = MotifOccurrence {
+            probability: 1.0 / 4096.0,
+            positions: 3_000_000_000,
+        };
+        assert_eq!(genome.p_at_least_one(), 1.0);
+        let rare = MotifOccurrence {
+            probability: 1e-10,
+            positions: 3_000_000_000,
+        };
+        assert!((rare.p_at_least_one() - -(-0.3f64).exp_m1()).abs() < 1e-9);
+
+        let frequent = MotifOccurrence {
+            probability: 0.001,
+            positions: 1_000_000,
+        };
+        assert!((frequent.p_value(2000) / 3.0582e-170 - 1.0).abs() < 1e-3);
+        assert!((frequent.p_value(1000) - 0.504205).abs() < 1e-6);
+        assert!((frequent.p_value(900) - 0.999377).abs() < 1e-6);
+        assert_eq!(frequent.p_value(1), 1.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::{
+    region::Region,
+    seq::{Error, Seq},
+    types::{Case, Strand},
+    utils::pair_score,
+};
+
+// NOTE: A candidate intrinsic terminator: a stem-loop (`hairpin`) immediately followed by a U-tract
+// (`u_tract`, which is a run of T in DNA). The hairpin score sums the strength of every stem pair
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Terminator {
+    pub hairpin: Region,
+    pub stem_len: usize,
+    pub loop_len: usize,
+    pub hairpin_score: f64,
+    pub u_tract: Region,
+    pub u_count: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TerminatorFinder {
+    stem_lens: Range<usize>,
+    loop_lens: Range<usize>,
+    min_hairpin_score: f64,
+    u_tract_len: usize,
+    min_u_count: usize,
+}
+
+impl Default for TerminatorFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerminatorFinder {
+    // NOTE: The defaults look for stems of 4–12 pairs around loops of 3–9 bases, scoring at least
+    // as well as five G–C pairs, followed by at least 4 Us in the next 8 bases
+    pub fn new() -> Self {
+        Self {
+            stem_lens: 4..13,
+            loop_lens: 3..10,
+            min_hairpin_score: 15.0,
+            u_tract_len: 8,
+            min_u_count: 4,
+        }
+    }
+
+    pub fn stem_lens(self, stem_lens: Range<usize>) -> Self {
+        Self { stem_lens, ..self }
+    }
+
+    pub fn loop_lens(self, loop_lens: Range<usize>) -> Self {
+        Self { loop_lens, ..self }
+    }
+
+    pub fn min_hairpin_score(self, min_hairpin_score: f64) -> Self {
+        Self {
+            min_hairpin_score,
+            ..self
+        }
+    }
+
+    pub fn u_tract(self, u_tract_len: usize, min_u_count: usize) -> Self {
+        Self {
+            u_tract_len,
+            min_u_count,
+            ..self
+        }
+    }
+
+    // TODO: Swap this stem search for proper secondary structure prediction (and a free energy
+    // score) once there is a folding module to build on. For now stems must be perfectly paired
+    // (allowing G–U wobble pairs) and must close with a G–C pair next to the U-tract, which keeps
+    // upstream A-tracts from being "paired" with the U-tract itself
+    pub fn find(&self, seq: &Seq) -> Result<Vec<Terminator>, Error> {
+        if !seq.kind().is_nucleic_acid() {
+            return Err(Error::TerminatorSearch(seq.kind()));
+        }
+        let bytes: Vec<_> = seq
+            .normalize_case(Case::Upper)
+            .as_bytes()
+            .iter()
+            .map(|&b| if b == b'T' { b'U' } else { b })
+            .collect();
+
+        let mut candidates = Vec::new();
+        for t in 0..bytes.len() {
+            if bytes[t] != b'U' {
+                continue;
+            }
+            let u_tract = Region::new(t..(t + self.u_tract_len).min(bytes.len()), Strand::Forward)?;
+            let u_count = bytes[u_tract.range()]
+                .iter()
+                .filter(|&&b| b == b'U')
+                .count();
+            if u_count < self.min_u_count {
+                continue;
+            }
+            if let Some(hairpin) = self.best_hairpin(&bytes, t) {
+                candidates.push(Terminator {
+                    u_tract,
+                    u_count,
+                    ..hairpin
+                });
+            }
+        }
+
+        // NOTE: Neighbouring U-tract starts tend to find the same hairpin, so only the best of any
+        // overlapping candidates are kept
+        candidates.sort_by(|a, b| b.hairpin_score.total_cmp(&a.hairpin_score));
+        let mut terminators: Vec<Terminator> = Vec::new();
+        for candidate in candidates {
+            if terminators
+                .iter()
+                .all(|t| !t.hairpin.overlaps(&candidate.hairpin))
+            {
+                terminators.push(candidate);
+            }
+        }
+        terminators.sort_by_key(|t| t.hairpin.start());
+        Ok(terminators)
+    }
+
+    fn best_hairpin(&self, bytes: &[u8], end: usize) -> Option<Terminator> {
+        let mut best: Option<Terminator> = None;
+        for stem_len in self.stem_lens.clone() {
+            for loop_len in self.loop_lens.clone() {
+                let Some(start) = end.checked_sub(2 * stem_len + loop_len) else {
+                    continue;
+                };
+                if pair_score(bytes[start], bytes[end - 1]) != Some(3.0) {
+                    continue;
+                }
+                let score: Option<f64> = (0..stem_len)
+                    .map(|i| pair_score(bytes[start + i], bytes[end - 1 - i]))
+                    .sum();
+                let Some(score) = score.filter(|&s| s >= self.min_hairpin_score) else {
+                    continue;
+                };
+                if best.as_ref().is_none_or(|b| score > b.hairpin_score) {
+                    let region = |range| {
+                        Region::new(range, Strand::Forward)
+                            .expect("The hairpin starts before `end`")
+                    };
+                    best = Some(Terminator {
+                        hairpin: region(start..end),
+                        stem_len,
+                        loop_len,
+                        hairpin_score: score,
+                        u_tract: region(end..end),
+                        u_count: 0,
+                    });
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq::Kind;
+
+    // NOTE: A GC-rich, 6 bp stem around a GAAA tetraloop, then 8 Ts
+    const TERMINATOR: &str = "AAAAGCCCGCGAAAGCGGGCTTTTTTTTAAAA";
+
+    #[test]
+    fn find_dna_terminator() -> Result<(), Error> {
+        let terminators = TerminatorFinder::new().find(&Seq::dna(TERMINATOR)?)?;
+        assert_eq!(
+            terminators,
+            [Terminator {
+                hairpin: Region::new(4..20, Strand::Forward)?,
+                stem_len: 6,
+                loop_len: 4,
+                hairpin_score: 18.0,
+                u_tract: Region::new(20..28, Strand::Forward)?,
+                u_count: 8,
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_rna_terminator() -> Result<(), Error> {
+        let rna = Seq::dna(TERMINATOR)?.convert(Kind::Rna)?;
+        let terminators = TerminatorFinder::new().find(&rna.normalize_case(Case::Lower))?;
+        assert_eq!(terminators.len(), 1);
+        assert_eq!(terminators[0].hairpin.range(), 4..20);
+        Ok(())
+    }
+
+    #[test]
+    fn terminator_needs_u_tract() -> Result<(), Error> {
+        let no_tract = Seq::dna("AAAAGCCCGCGAAAGCGGGCTTAGACAGAAAA")?;
+        assert!(TerminatorFinder::new().find(&no_tract)?.is_empty());
+        let finder = TerminatorFinder::new().u_tract(4, 2);
+        assert_eq!(finder.find(&no_tract)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn terminator_needs_strong_hairpin() -> Result<(), Error> {
+        let weak = Seq::dna("AAAAGATATCGAAAGATATCTTTTTTTTAAAA")?;
+        assert!(TerminatorFinder::new().find(&weak)?.is_empty());
+        let finder = TerminatorFinder::new().min_hairpin_score(8.0);
+        assert_eq!(finder.find(&weak)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn terminator_protein() -> Result<(), Error> {
+        assert_eq!(
+            TerminatorFinder::new().find(&Seq::protein("MAMAPRTEIN")?),
+            Err(Error::TerminatorSearch(Kind::Protein))
+        );
+        Ok(())
+    }
+}
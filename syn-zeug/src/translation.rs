@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    data::{CODON_TABLE, IUPAC_CODON_TABLE},
+    seq::{Alphabet, Error, Kind, Seq},
+    types::Case,
+};
+
+// ===== Translation Options =======================================================================
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum StopCodons {
+    // NOTE: Stops are translated as `*` and translation carries on past them
+    TranslateThrough,
+    // NOTE: Translation ends at (and leaves out) the first stop
+    ToFirstStop,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum AmbiguousCodons {
+    // NOTE: Ambiguous codons are resolved to a single residue (or B / Z) whenever every codon they
+    // could stand for agrees, otherwise they become X
+    Resolve,
+    Mask,
+    Error,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum PartialCodons {
+    Ignore,
+    // NOTE: A trailing partial codon is translated as a single X
+    Mask,
+    Error,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct TranslationOptions {
+    stop_codons: StopCodons,
+    ambiguous_codons: AmbiguousCodons,
+    partial_codons: PartialCodons,
+}
+
+impl Default for TranslationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TranslationOptions {
+    // NOTE: The defaults behave exactly like `Seq::convert(Kind::Protein)`
+    pub fn new() -> Self {
+        Self {
+            stop_codons: StopCodons::TranslateThrough,
+            ambiguous_codons: AmbiguousCodons::Resolve,
+            partial_codons: PartialCodons::Ignore,
+        }
+    }
+
+    pub fn stop_codons(self, stop_codons: StopCodons) -> Self {
+        Self {
+            stop_codons,
+            ..self
+        }
+    }
+
+    pub fn ambiguous_codons(self, ambiguous_codons: AmbiguousCodons) -> Self {
+        Self {
+            ambiguous_codons,
+            ..self
+        }
+    }
+
+    pub fn partial_codons(self, partial_codons: PartialCodons) -> Self {
+        Self {
+            partial_codons,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for TranslationOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stop_codons = match self.stop_codons {
+            StopCodons::TranslateThrough => "translate_through",
+            StopCodons::ToFirstStop => "to_first_stop",
+        };
+        let ambiguous_codons = match self.ambiguous_codons {
+            AmbiguousCodons::Resolve => "resolve_ambiguous",
+            AmbiguousCodons::Mask => "mask_ambiguous",
+            AmbiguousCodons::Error => "error_on_ambiguous",
+        };
+        let partial_codons = match self.partial_codons {
+            PartialCodons::Ignore => "ignore_partial",
+            PartialCodons::Mask => "mask_partial",
+            PartialCodons::Error => "error_on_partial",
+        };
+        write!(f, "{stop_codons}, {ambiguous_codons}, {partial_codons}")
+    }
+}
+
+// ===== Translation ===============================================================================
+
+impl Seq {
+    pub fn translate(&self, options: &TranslationOptions) -> Result<Self, Error> {
+        if !self.kind().is_nucleic_acid() {
+            return Err(Error::Translation(self.kind()));
+        }
+
+        let rna = self.convert(Kind::Rna)?.normalize_case(Case::Upper);
+        let codons = rna.as_bytes().chunks_exact(3);
+        let partial = codons.remainder().len();
+        let mut bytes = Vec::with_capacity(rna.len() / 3 + 1);
+        for (i, codon) in codons.enumerate() {
+            let residue = match CODON_TABLE.get(codon) {
+                Some(&residue) => residue,
+                None => match options.ambiguous_codons {
+                    AmbiguousCodons::Resolve => IUPAC_CODON_TABLE[codon],
+                    AmbiguousCodons::Mask => b'X',
+                    AmbiguousCodons::Error => return Err(Error::AmbiguousCodon(i * 3)),
+                },
+            };
+            if residue == b'*' && options.stop_codons == StopCodons::ToFirstStop {
+                return Self::from_vec_with_kind(bytes, [Kind::Protein], Alphabet::Iupac);
+            }
+            bytes.push(residue);
+        }
+
+        if partial > 0 {
+            match options.partial_codons {
+                PartialCodons::Ignore => (),
+                PartialCodons::Mask => bytes.push(b'X'),
+                PartialCodons::Error => return Err(Error::PartialCodon(partial)),
+            }
+        }
+        Self::from_vec_with_kind(bytes, [Kind::Protein], Alphabet::Iupac)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_translation() -> Result<(), Error> {
+        let dna = Seq::dna_iupac("ATGGCCTAAGGNTAYCA")?;
+        let protein = dna.translate(&TranslationOptions::new())?;
+        assert_eq!(protein, dna.convert(Kind::Protein)?);
+        assert_eq!(protein.to_string(), "MA*GY");
+        Ok(())
+    }
+
+    #[test]
+    fn translate_to_first_stop() -> Result<(), Error> {
+        let options = TranslationOptions::new().stop_codons(StopCodons::ToFirstStop);
+        let rna = Seq::rna("AUGGCCUAAGGG")?;
+        assert_eq!(rna.translate(&options)?.to_string(), "MA");
+        let rna = Seq::rna_iupac("AUGGCCURAGGG")?;
+        assert_eq!(rna.translate(&options)?.to_string(), "MA");
+        let rna = Seq::rna("GGGGG")?;
+        assert_eq!(rna.translate(&options)?.to_string(), "G");
+        Ok(())
+    }
+
+    #[test]
+    fn translate_ambiguous_codons() -> Result<(), Error> {
+        let dna = Seq::dna_iupac("ATGGCNCAYNNN")?;
+        let masked = TranslationOptions::new().ambiguous_codons(AmbiguousCodons::Mask);
+        assert_eq!(dna.translate(&masked)?.to_string(), "MXXX");
+        let resolved = TranslationOptions::new().ambiguous_codons(AmbiguousCodons::Resolve);
+        assert_eq!(dna.translate(&resolved)?.to_string(), "MAHX");
+        let strict = TranslationOptions::new().ambiguous_codons(AmbiguousCodons::Error);
+        assert_eq!(dna.translate(&strict), Err(Error::AmbiguousCodon(3)));
+        Ok(())
+    }
+
+    #[test]
+    fn translate_partial_codons() -> Result<(), Error> {
+        let dna = Seq::dna("atggccTA")?;
+        let ignored = TranslationOptions::new().partial_codons(PartialCodons::Ignore);
+        assert_eq!(dna.translate(&ignored)?.to_string(), "MA");
+        let masked = TranslationOptions::new().partial_codons(PartialCodons::Mask);
+        assert_eq!(dna.translate(&masked)?.to_string(), "MAX");
+        let strict = TranslationOptions::new().partial_codons(PartialCodons::Error);
+        assert_eq!(dna.translate(&strict), Err(Error::PartialCodon(2)));
+        assert_eq!(dna.subseq(0..6).translate(&strict)?.to_string(), "MA");
+        Ok(())
+    }
+
+    #[test]
+    fn translate_protein() -> Result<(), Error> {
+        let protein = Seq::protein("MAMAPRTEIN")?;
+        assert_eq!(
+            protein.translate(&TranslationOptions::default()),
+            Err(Error::Translation(Kind::Protein))
+        );
+        Ok(())
+    }
+}
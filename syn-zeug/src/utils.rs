@@ -21,6 +21,17 @@ pub fn expand_iupac(seq: impl AsRef<[u8]>, map: &Map<u8, &[u8]>) -> Vec<Vec<u8>>
     expansions
 }
 
+// NOTE: Rough strengths for each kind of RNA base pair, in units of hydrogen bonds (with G–U wobble
+// pairs given a single bond). Bases must be uppercase RNA
+pub fn pair_score(a: u8, b: u8) -> Option<f64> {
+    match (a, b) {
+        (b'G', b'C') | (b'C', b'G') => Some(3.0),
+        (b'A', b'U') | (b'U', b'A') => Some(2.0),
+        (b'G', b'U') | (b'U', b'G') => Some(1.0),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;